@@ -0,0 +1,17 @@
+use anchor_lang::prelude::*;
+
+// tokenization wrap program, owner of the block list PDAs. The wrap program depends on this
+// crate and asserts at compile time that this id and the seeds below match its own
+pub const TOKENIZATION_WRAP_PROGRAM_ID: Pubkey = pubkey!("2LbadSfQEGMooXUB3tmkXufVGKrQBkjR7UybxnvmwH4L");
+
+// seeds
+// transfer hook interface
+pub const EXTRA_ACCOUNT_METAS_SEED: &[u8] = b"extra-account-metas";
+
+// tokenization wrap, must match the seeds used by tokenization_wrap
+pub const BLOCK_LIST_SEED: &[u8] = b"block_list";
+pub const WRAPPED_MINT_AUTHORITY_SEED: &[u8] = b"wrapped_mint_authority";
+
+// token account layout, owner is stored right after the mint
+pub const TOKEN_ACCOUNT_OWNER_OFFSET: u8 = 32;
+pub const PUBKEY_SIZE: u8 = 32;
@@ -0,0 +1,19 @@
+use anchor_lang::prelude::*;
+
+#[error_code]
+pub enum TokenizationHookError {
+    #[msg("Un authorized")]
+    Unauthorized,
+
+    #[msg("Invalid account data")]
+    InvalidAccountData,
+
+    #[msg("Not transferring")]
+    NotTransferring,
+
+    #[msg("Source owner block listed")]
+    SourceBlockListed,
+
+    #[msg("Destination owner block listed")]
+    DestinationBlockListed,
+}
@@ -0,0 +1,111 @@
+use crate::constants::*;
+use crate::errors::TokenizationHookError;
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount};
+use spl_token_2022_v9::{
+    extension::{transfer_hook::TransferHookAccount, BaseStateWithExtensions, StateWithExtensions},
+    state::Account as AccountState,
+};
+
+/// Called by Token-2022 on every transfer of a mint pointing at this program.
+/// Rejects the transfer when the source or destination owner is on the
+/// tokenization wrap block list of the mint. Transfers signed by the wrapped mint
+/// authority PDA are exempt, which only happens in `MintAuthorityMode::Program` where
+/// the PDA is the permanent delegate; in owner mode the owner key signs seizures
+/// itself and a block listed holder can't be seized through the hook.
+pub fn execute(ctx: Context<Execute>, amount: u64) -> Result<()> {
+    let source_token = &ctx.accounts.source_token;
+    let destination_token = &ctx.accounts.destination_token;
+    let mint_key = ctx.accounts.mint.key();
+
+    // only run as part of a Token-2022 transfer, not as a direct call
+    let transferring = {
+        let source_info = source_token.to_account_info();
+        let source_data = source_info.try_borrow_data()?;
+        let source_state = StateWithExtensions::<AccountState>::unpack(&source_data)
+            .map_err(|_| TokenizationHookError::InvalidAccountData)?;
+        let transfer_hook_account = source_state
+            .get_extension::<TransferHookAccount>()
+            .map_err(|_| TokenizationHookError::InvalidAccountData)?;
+        bool::from(transfer_hook_account.transferring)
+    };
+    require!(transferring, TokenizationHookError::NotTransferring);
+
+    // the permanent delegate held by the tokenization wrap program moves funds on behalf
+    // of regulators, e.g. seizing a block listed holder's balance
+    let (wrapped_mint_authority, _) = Pubkey::find_program_address(
+        &[WRAPPED_MINT_AUTHORITY_SEED, mint_key.as_ref()],
+        &TOKENIZATION_WRAP_PROGRAM_ID,
+    );
+    if ctx.accounts.owner.key() == wrapped_mint_authority {
+        return Ok(());
+    }
+
+    require!(
+        !is_block_listed(&ctx.accounts.source_block_list),
+        TokenizationHookError::SourceBlockListed
+    );
+    require!(
+        !is_block_listed(&ctx.accounts.destination_block_list),
+        TokenizationHookError::DestinationBlockListed
+    );
+
+    msg!("mint: {:?}", mint_key);
+    msg!("from: {:?}", source_token.owner);
+    msg!("to: {:?}", destination_token.owner);
+    msg!("amount: {:?}", amount);
+
+    return Ok(());
+}
+
+// block list PDAs only exist while the owner is listed
+fn is_block_listed(block_list: &AccountInfo) -> bool {
+    block_list.owner == &TOKENIZATION_WRAP_PROGRAM_ID && !block_list.data_is_empty()
+}
+
+#[derive(Accounts)]
+pub struct Execute<'info> {
+    #[account(
+        token::mint = mint,
+    )]
+    pub source_token: InterfaceAccount<'info, TokenAccount>,
+
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        token::mint = mint,
+    )]
+    pub destination_token: InterfaceAccount<'info, TokenAccount>,
+
+    /// CHECK: source token account owner or delegate
+    pub owner: UncheckedAccount<'info>,
+
+    /// CHECK: extra account meta list PDA
+    #[account(
+        seeds = [EXTRA_ACCOUNT_METAS_SEED, mint.key().as_ref()],
+        bump,
+    )]
+    pub extra_account_meta_list: UncheckedAccount<'info>,
+
+    /// CHECK: tokenization wrap program, owner of the block list PDAs
+    #[account(
+        address = TOKENIZATION_WRAP_PROGRAM_ID,
+    )]
+    pub tokenization_wrap_program: UncheckedAccount<'info>,
+
+    /// CHECK: block list PDA of the source owner, only checked for existence
+    #[account(
+        seeds = [BLOCK_LIST_SEED, mint.key().as_ref(), source_token.owner.as_ref()],
+        bump,
+        seeds::program = TOKENIZATION_WRAP_PROGRAM_ID,
+    )]
+    pub source_block_list: UncheckedAccount<'info>,
+
+    /// CHECK: block list PDA of the destination owner, only checked for existence
+    #[account(
+        seeds = [BLOCK_LIST_SEED, mint.key().as_ref(), destination_token.owner.as_ref()],
+        bump,
+        seeds::program = TOKENIZATION_WRAP_PROGRAM_ID,
+    )]
+    pub destination_block_list: UncheckedAccount<'info>,
+}
@@ -0,0 +1,111 @@
+use crate::constants::*;
+use crate::errors::TokenizationHookError;
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::Mint;
+use spl_tlv_account_resolution::{account::ExtraAccountMeta, seeds::Seed, state::ExtraAccountMetaList};
+use spl_token_2022_v9::{
+    extension::{transfer_hook::TransferHook, BaseStateWithExtensions, StateWithExtensions},
+    state::Mint as MintState,
+};
+use spl_transfer_hook_interface::instruction::ExecuteInstruction;
+
+/// Writes the extra accounts the Execute instruction needs for `mint`.
+/// Only the mint's transfer hook authority can initialize the list.
+pub fn initialize_extra_account_meta_list(
+    ctx: Context<InitializeExtraAccountMetaList>,
+) -> Result<()> {
+    let mint = &ctx.accounts.mint;
+    let authority_key = ctx.accounts.authority.key();
+
+    let transfer_hook_authority: Option<Pubkey> = {
+        let mint_info = mint.to_account_info();
+        let mint_data = mint_info.try_borrow_data()?;
+        let mint_state = StateWithExtensions::<MintState>::unpack(&mint_data)
+            .map_err(|_| TokenizationHookError::InvalidAccountData)?;
+        let transfer_hook = mint_state
+            .get_extension::<TransferHook>()
+            .map_err(|_| TokenizationHookError::InvalidAccountData)?;
+        transfer_hook.authority.into()
+    };
+    require!(
+        transfer_hook_authority == Some(authority_key),
+        TokenizationHookError::Unauthorized
+    );
+
+    let extra_account_meta_list = &ctx.accounts.extra_account_meta_list;
+    ExtraAccountMetaList::init::<ExecuteInstruction>(
+        &mut extra_account_meta_list.try_borrow_mut_data()?,
+        &extra_account_metas()?,
+    )?;
+
+    msg!("mint: {:?}", mint.key());
+    msg!("extra account meta list: {:?}", extra_account_meta_list.key());
+
+    return Ok(());
+}
+
+/// Extra accounts appended to Execute, after
+/// source(0), mint(1), destination(2), owner(3) and the extra account meta list(4):
+/// 5. tokenization wrap program
+/// 6. block list PDA of the source token account owner
+/// 7. block list PDA of the destination token account owner
+pub fn extra_account_metas() -> Result<Vec<ExtraAccountMeta>> {
+    return Ok(vec![
+        ExtraAccountMeta::new_with_pubkey(&TOKENIZATION_WRAP_PROGRAM_ID, false, false)?,
+        ExtraAccountMeta::new_external_pda_with_seeds(
+            5,
+            &[
+                Seed::Literal {
+                    bytes: BLOCK_LIST_SEED.to_vec(),
+                },
+                Seed::AccountKey { index: 1 },
+                Seed::AccountData {
+                    account_index: 0,
+                    data_index: TOKEN_ACCOUNT_OWNER_OFFSET,
+                    length: PUBKEY_SIZE,
+                },
+            ],
+            false,
+            false,
+        )?,
+        ExtraAccountMeta::new_external_pda_with_seeds(
+            5,
+            &[
+                Seed::Literal {
+                    bytes: BLOCK_LIST_SEED.to_vec(),
+                },
+                Seed::AccountKey { index: 1 },
+                Seed::AccountData {
+                    account_index: 2,
+                    data_index: TOKEN_ACCOUNT_OWNER_OFFSET,
+                    length: PUBKEY_SIZE,
+                },
+            ],
+            false,
+            false,
+        )?,
+    ]);
+}
+
+#[derive(Accounts)]
+pub struct InitializeExtraAccountMetaList<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// CHECK: extra account meta list PDA, written with the TLV layout of the interface
+    #[account(
+        init,
+        seeds = [EXTRA_ACCOUNT_METAS_SEED, mint.key().as_ref()],
+        bump,
+        space = ExtraAccountMetaList::size_of(extra_account_metas()?.len())?,
+        payer = payer,
+    )]
+    pub extra_account_meta_list: UncheckedAccount<'info>,
+
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    // transfer hook authority of the mint
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
@@ -0,0 +1,2 @@
+pub mod execute;
+pub mod initialize_extra_account_meta_list;
@@ -0,0 +1,37 @@
+#![allow(unexpected_cfgs)]
+#![allow(deprecated)]
+#![allow(clippy::needless_return)]
+use anchor_lang::prelude::*;
+use spl_discriminator::SplDiscriminate;
+use spl_transfer_hook_interface::instruction::{
+    ExecuteInstruction, InitializeExtraAccountMetaListInstruction,
+};
+
+pub mod constants;
+pub mod errors;
+pub mod instructions;
+
+use crate::instructions::execute;
+use crate::instructions::execute::*;
+use crate::instructions::initialize_extra_account_meta_list;
+use crate::instructions::initialize_extra_account_meta_list::*;
+
+declare_id!("8pvUM5y1Z3jcqm5uwo3Q2XRRhm6nGYaWJFPmsMAAjmP1");
+
+#[program]
+pub mod tokenization_hook {
+    use super::*;
+
+    // spl-transfer-hook-interface
+    #[instruction(discriminator = InitializeExtraAccountMetaListInstruction::SPL_DISCRIMINATOR_SLICE)]
+    pub fn initialize_extra_account_meta_list(
+        ctx: Context<InitializeExtraAccountMetaList>,
+    ) -> Result<()> {
+        return initialize_extra_account_meta_list::initialize_extra_account_meta_list(ctx);
+    }
+
+    #[instruction(discriminator = ExecuteInstruction::SPL_DISCRIMINATOR_SLICE)]
+    pub fn execute(ctx: Context<Execute>, amount: u64) -> Result<()> {
+        return execute::execute(ctx, amount);
+    }
+}
@@ -14,6 +14,56 @@ pub const WRAPPED_MINT_SIZE: usize = 400;
 pub const UNWRAPPED_MINT_VAULT_SEED: &[u8] = b"unwrapped_mint_vault";
 pub const WRAPPED_MINT_SEED: &[u8] = b"wrapped_mint";
 pub const WRAPPED_MINT_STATE_SEED: &[u8] = b"wrapped_mint_state";
+pub const WRAPPED_MINT_AUTHORITY_SEED: &[u8] = b"wrapped_mint_authority";
+// wrapped mint state layout, accounts created before the version field are migrated
+// by migrate_mint_state
+pub const WRAPPED_MINT_STATE_VERSION: u8 = 1;
 
 // role
 pub const WRAPPED_ROLE_SEED: &[u8] = b"wrapped_role";
+// allowance of a new role account, no cap on the outstanding amount
+pub const UNLIMITED_ALLOWANCE: u64 = u64::MAX;
+
+// program config
+pub const PROGRAM_CONFIG_SEED: &[u8] = b"program_config";
+
+// access list
+pub const ACCESS_LIST_SEED: &[u8] = b"access_list";
+pub const BLOCK_LIST_SEED: &[u8] = b"block_list";
+
+// freeze hold of a token account frozen by a freezer, approve_holder can't thaw it
+pub const FREEZE_HOLD_SEED: &[u8] = b"freeze_hold";
+
+// transfer hook interface, extra account meta list of the tokenization hook program
+pub const EXTRA_ACCOUNT_METAS_SEED: &[u8] = b"extra-account-metas";
+
+// log line and return data of an unwrap the circuit breaker refused
+pub const CIRCUIT_BREAKER_TRIPPED: &str = "circuit breaker tripped";
+
+// longest transfer hook timelock, 30 days, the timelock can't be lowered once set and
+// keeps the authorities with the wrapped mint authority PDA, reclaim_mint_authorities fails
+pub const MAX_TRANSFER_HOOK_TIMELOCK: i64 = 30 * 24 * 60 * 60;
+
+// underlying risk flags, reported by get_underlying_risk_profile and
+// checked against the program config's allowed_underlying_risks in create_mint
+pub const UNDERLYING_RISK_FREEZE_AUTHORITY: u32 = 1 << 0;
+pub const UNDERLYING_RISK_PERMANENT_DELEGATE: u32 = 1 << 1;
+pub const UNDERLYING_RISK_NON_TRANSFERABLE: u32 = 1 << 2;
+pub const UNDERLYING_RISK_PAUSABLE: u32 = 1 << 3;
+pub const UNDERLYING_RISK_MINT_CLOSE_AUTHORITY: u32 = 1 << 4;
+pub const UNDERLYING_RISK_TRANSFER_FEE: u32 = 1 << 5;
+pub const UNDERLYING_RISK_TRANSFER_HOOK: u32 = 1 << 6;
+pub const UNDERLYING_RISK_DEFAULT_FROZEN: u32 = 1 << 7;
+pub const UNDERLYING_RISK_CONFIDENTIAL_TRANSFER: u32 = 1 << 8;
+pub const UNDERLYING_RISK_INTEREST_BEARING: u32 = 1 << 9;
+pub const UNDERLYING_RISK_SCALED_UI_AMOUNT: u32 = 1 << 10;
+
+// token metadata additional key/value pairs, e.g. ISIN, issuer, jurisdiction.
+// create_mint carries them in a single 1232 byte transaction next to its 12 accounts:
+// MAX_ADDITIONAL_METADATA entries of MAX_ADDITIONAL_METADATA_SIZE key and value bytes
+// in total still fit with a full length name and symbol and a uri of up to 150 bytes,
+// more or larger fields are added afterwards with update_metadata_field
+pub const MAX_ADDITIONAL_METADATA: usize = 4;
+pub const MAX_ADDITIONAL_METADATA_SIZE: usize = 320;
+pub const MAX_METADATA_KEY_LEN: usize = 32;
+pub const MAX_METADATA_VALUE_LEN: usize = 256;
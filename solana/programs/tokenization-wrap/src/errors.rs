@@ -37,4 +37,88 @@ pub enum TokenizationWrapError {
 
     #[msg("Invalid role")]
     InvalidRole,
+
+    #[msg("Invalid mint authority mode")]
+    InvalidMintAuthorityMode,
+
+    #[msg("Wrap and unwrap paused")]
+    Paused,
+
+    #[msg("Wrap and unwrap not paused")]
+    NotPaused,
+
+    #[msg("Invalid remaining accounts")]
+    InvalidRemainingAccounts,
+
+    #[msg("Invalid access list account")]
+    InvalidAccessListAccount,
+
+    #[msg("Not access listed")]
+    NotAccessListed,
+
+    #[msg("Block listed")]
+    BlockListed,
+
+    #[msg("Unwrapped mint has a risk not allowed by the policy")]
+    UnderlyingRiskNotAllowed,
+
+    #[msg("Invalid create mint config")]
+    InvalidMintConfig,
+
+    #[msg("Invalid metadata key")]
+    InvalidMetadataKey,
+
+    #[msg("Metadata key too long")]
+    MetadataKeyTooLong,
+
+    #[msg("Metadata value too long")]
+    MetadataValueTooLong,
+
+    #[msg("Too many additional metadata fields")]
+    TooManyMetadataFields,
+
+    #[msg("Multiplier out of range")]
+    MultiplierOutOfRange,
+
+    #[msg("No pending transfer hook change")]
+    NoPendingTransferHook,
+
+    #[msg("Transfer hook change is still timelocked")]
+    TransferHookTimelocked,
+
+    #[msg("Invalid timelock")]
+    InvalidTimelock,
+
+    #[msg("Allowance exceeded")]
+    AllowanceExceeded,
+
+    #[msg("Rate limit exceeded")]
+    RateLimitExceeded,
+
+    #[msg("Invalid rate limit")]
+    InvalidRateLimit,
+
+    #[msg("Invalid role validity window")]
+    InvalidRoleValidity,
+
+    #[msg("Role has not expired")]
+    RoleNotExpired,
+
+    #[msg("Invalid role account")]
+    InvalidRoleAccount,
+
+    #[msg("Ownership transfer pending")]
+    OwnershipTransferPending,
+
+    #[msg("Account already migrated")]
+    AlreadyMigrated,
+
+    #[msg("Additional metadata too large")]
+    AdditionalMetadataTooLarge,
+
+    #[msg("Account is under a freeze hold")]
+    FreezeHold,
+
+    #[msg("Token account is frozen")]
+    AccountFrozen,
 }
@@ -0,0 +1,347 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::*;
+use crate::errors::TokenizationWrapError;
+use crate::instructions::create_mint::WrappedMintState;
+use crate::instructions::role::{RoleKind, RoleState};
+use crate::utils::{close_pda_account, create_pda_account, is_program_account};
+use anchor_spl::token_interface::Mint;
+
+pub fn toggle_access_list(ctx: Context<ToggleAccessList>, enabled: bool) -> Result<()> {
+    let wrapped_mint_state = &mut ctx.accounts.wrapped_mint_state;
+    wrapped_mint_state.access_list_enabled = enabled;
+
+    emit!(AccessListToggledEvent {
+        wrapped_mint: ctx.accounts.wrapped_mint.key(),
+        enabled: enabled,
+    });
+
+    msg!("wrapped mint: {:?}", ctx.accounts.wrapped_mint.key());
+    msg!("access list enabled: {:?}", enabled);
+
+    return Ok(());
+}
+
+/// Adds `users` to the access list. The matching access list PDAs are passed through
+/// `remaining_accounts` in the same order as `users`.
+pub fn access_list_add<'info>(
+    ctx: Context<'_, '_, 'info, 'info, ManageAccessList<'info>>,
+    users: Vec<Pubkey>,
+) -> Result<()> {
+    let wrapped_mint_key = ctx.accounts.wrapped_mint.key();
+    let added = add_list_entries(
+        ListKind::AccessList,
+        &wrapped_mint_key,
+        &ctx.accounts.manager.to_account_info(),
+        &ctx.accounts.system_program.to_account_info(),
+        &users,
+        ctx.remaining_accounts,
+    )?;
+    for user in added {
+        emit!(AccessListAddressAddedEvent {
+            wrapped_mint: wrapped_mint_key,
+            user: user,
+        });
+        msg!("access list added: {:?}", user);
+    }
+
+    return Ok(());
+}
+
+/// Removes `users` from the access list, the rent goes back to the manager.
+pub fn access_list_remove<'info>(
+    ctx: Context<'_, '_, 'info, 'info, ManageAccessList<'info>>,
+    users: Vec<Pubkey>,
+) -> Result<()> {
+    let wrapped_mint_key = ctx.accounts.wrapped_mint.key();
+    let removed = remove_list_entries(
+        ListKind::AccessList,
+        &wrapped_mint_key,
+        &ctx.accounts.manager.to_account_info(),
+        &users,
+        ctx.remaining_accounts,
+    )?;
+    for user in removed {
+        emit!(AccessListAddressRemovedEvent {
+            wrapped_mint: wrapped_mint_key,
+            user: user,
+        });
+        msg!("access list removed: {:?}", user);
+    }
+
+    return Ok(());
+}
+
+/// Adds `users` to the block list. The matching block list PDAs are passed through
+/// `remaining_accounts` in the same order as `users`.
+pub fn block_list_add<'info>(
+    ctx: Context<'_, '_, 'info, 'info, ManageAccessList<'info>>,
+    users: Vec<Pubkey>,
+) -> Result<()> {
+    let wrapped_mint_key = ctx.accounts.wrapped_mint.key();
+    let added = add_list_entries(
+        ListKind::BlockList,
+        &wrapped_mint_key,
+        &ctx.accounts.manager.to_account_info(),
+        &ctx.accounts.system_program.to_account_info(),
+        &users,
+        ctx.remaining_accounts,
+    )?;
+    for user in added {
+        emit!(BlockListAddressAddedEvent {
+            wrapped_mint: wrapped_mint_key,
+            user: user,
+        });
+        msg!("block list added: {:?}", user);
+    }
+
+    return Ok(());
+}
+
+/// Removes `users` from the block list, the rent goes back to the manager.
+pub fn block_list_remove<'info>(
+    ctx: Context<'_, '_, 'info, 'info, ManageAccessList<'info>>,
+    users: Vec<Pubkey>,
+) -> Result<()> {
+    let wrapped_mint_key = ctx.accounts.wrapped_mint.key();
+    let removed = remove_list_entries(
+        ListKind::BlockList,
+        &wrapped_mint_key,
+        &ctx.accounts.manager.to_account_info(),
+        &users,
+        ctx.remaining_accounts,
+    )?;
+    for user in removed {
+        emit!(BlockListAddressRemovedEvent {
+            wrapped_mint: wrapped_mint_key,
+            user: user,
+        });
+        msg!("block list removed: {:?}", user);
+    }
+
+    return Ok(());
+}
+
+/// Rejects a user that is block listed, or that is not access listed while the
+/// access list is enabled. `access_list` and `block_list` must be the user's PDAs.
+pub fn check_access_lists(
+    wrapped_mint_state: &WrappedMintState,
+    access_list: &AccountInfo,
+    block_list: &AccountInfo,
+) -> Result<()> {
+    require!(
+        !is_program_account(block_list),
+        TokenizationWrapError::BlockListed
+    );
+    if wrapped_mint_state.access_list_enabled {
+        require!(
+            is_program_account(access_list),
+            TokenizationWrapError::NotAccessListed
+        );
+    }
+
+    return Ok(());
+}
+
+// creates the list PDAs that don't exist yet and returns the users that were added
+fn add_list_entries<'info>(
+    list: ListKind,
+    wrapped_mint_key: &Pubkey,
+    payer: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    users: &[Pubkey],
+    entries: &[AccountInfo<'info>],
+) -> Result<Vec<Pubkey>> {
+    require!(
+        users.len() == entries.len(),
+        TokenizationWrapError::InvalidRemainingAccounts
+    );
+
+    let mut added = Vec::new();
+    for (user, entry) in users.iter().zip(entries.iter()) {
+        let (entry_key, entry_bump) = Pubkey::find_program_address(
+            &[list.seed(), wrapped_mint_key.as_ref(), user.as_ref()],
+            &crate::ID,
+        );
+        require_keys_eq!(
+            entry.key(),
+            entry_key,
+            TokenizationWrapError::InvalidAccessListAccount
+        );
+        // already listed
+        if is_program_account(entry) {
+            continue;
+        }
+
+        create_pda_account(
+            payer,
+            entry,
+            system_program,
+            ANCHOR_DISCRIMINATOR_SIZE + AccessListState::INIT_SPACE,
+            &[&[
+                list.seed(),
+                wrapped_mint_key.as_ref(),
+                user.as_ref(),
+                &[entry_bump],
+            ]],
+        )?;
+
+        let entry_state = AccessListState {
+            wrapped_mint: *wrapped_mint_key,
+            user: *user,
+            list: list,
+        };
+        entry_state.try_serialize(&mut &mut entry.try_borrow_mut_data()?[..])?;
+        added.push(*user);
+    }
+
+    return Ok(added);
+}
+
+// closes the list PDAs that exist and returns the users that were removed
+fn remove_list_entries<'info>(
+    list: ListKind,
+    wrapped_mint_key: &Pubkey,
+    destination: &AccountInfo<'info>,
+    users: &[Pubkey],
+    entries: &[AccountInfo<'info>],
+) -> Result<Vec<Pubkey>> {
+    require!(
+        users.len() == entries.len(),
+        TokenizationWrapError::InvalidRemainingAccounts
+    );
+
+    let mut removed = Vec::new();
+    for (user, entry) in users.iter().zip(entries.iter()) {
+        let (entry_key, _) = Pubkey::find_program_address(
+            &[list.seed(), wrapped_mint_key.as_ref(), user.as_ref()],
+            &crate::ID,
+        );
+        require_keys_eq!(
+            entry.key(),
+            entry_key,
+            TokenizationWrapError::InvalidAccessListAccount
+        );
+        // not listed
+        if !is_program_account(entry) {
+            continue;
+        }
+
+        close_pda_account(entry, destination)?;
+        removed.push(*user);
+    }
+
+    return Ok(removed);
+}
+
+#[derive(Accounts)]
+pub struct ToggleAccessList<'info> {
+    // access list manager
+    pub manager: Signer<'info>,
+
+    // The wrapped mint
+    #[account(
+        constraint = (wrapped_mint_state.wrapped_mint == wrapped_mint.key()) @ TokenizationWrapError::InvalidWrappedMint,
+    )]
+    pub wrapped_mint: InterfaceAccount<'info, Mint>,
+
+    // wrapped mint state
+    #[account(
+        mut,
+        seeds = [WRAPPED_MINT_STATE_SEED, &wrapped_mint.key().as_ref()],
+        bump,
+    )]
+    pub wrapped_mint_state: Account<'info, WrappedMintState>,
+
+    // access list manager role check
+    #[account(
+        seeds=[WRAPPED_ROLE_SEED,&wrapped_mint.key().as_ref(),&[RoleKind::AccessListManager.as_u8()],manager.key().as_ref()],
+        bump,
+        constraint = manager_role.has_role(&manager.key(), &[RoleKind::AccessListManager]) @ TokenizationWrapError::Unauthorized,
+    )]
+    pub manager_role: Account<'info, RoleState>,
+}
+
+#[derive(Accounts)]
+pub struct ManageAccessList<'info> {
+    // access list manager, pays for and receives the rent of the list accounts
+    #[account(mut)]
+    pub manager: Signer<'info>,
+
+    // The wrapped mint
+    #[account(
+        constraint = (wrapped_mint_state.wrapped_mint == wrapped_mint.key()) @ TokenizationWrapError::InvalidWrappedMint,
+    )]
+    pub wrapped_mint: InterfaceAccount<'info, Mint>,
+
+    // wrapped mint state
+    #[account(
+        seeds = [WRAPPED_MINT_STATE_SEED, &wrapped_mint.key().as_ref()],
+        bump,
+    )]
+    pub wrapped_mint_state: Account<'info, WrappedMintState>,
+
+    // access list manager role check
+    #[account(
+        seeds=[WRAPPED_ROLE_SEED,&wrapped_mint.key().as_ref(),&[RoleKind::AccessListManager.as_u8()],manager.key().as_ref()],
+        bump,
+        constraint = manager_role.has_role(&manager.key(), &[RoleKind::AccessListManager]) @ TokenizationWrapError::Unauthorized,
+    )]
+    pub manager_role: Account<'info, RoleState>,
+
+    pub system_program: Program<'info, System>,
+}
+
+// one account per listed user, seeds = [list seed, wrapped mint, user]
+#[account]
+#[derive(InitSpace)]
+pub struct AccessListState {
+    pub wrapped_mint: Pubkey,
+    pub user: Pubkey,
+    pub list: ListKind,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug, InitSpace)]
+pub enum ListKind {
+    AccessList,
+    BlockList,
+}
+
+impl ListKind {
+    pub fn seed(self) -> &'static [u8] {
+        match self {
+            ListKind::AccessList => ACCESS_LIST_SEED,
+            ListKind::BlockList => BLOCK_LIST_SEED,
+        }
+    }
+}
+
+#[event]
+pub struct AccessListToggledEvent {
+    pub wrapped_mint: Pubkey,
+    pub enabled: bool,
+}
+
+#[event]
+pub struct AccessListAddressAddedEvent {
+    pub wrapped_mint: Pubkey,
+    pub user: Pubkey,
+}
+
+#[event]
+pub struct AccessListAddressRemovedEvent {
+    pub wrapped_mint: Pubkey,
+    pub user: Pubkey,
+}
+
+#[event]
+pub struct BlockListAddressAddedEvent {
+    pub wrapped_mint: Pubkey,
+    pub user: Pubkey,
+}
+
+#[event]
+pub struct BlockListAddressRemovedEvent {
+    pub wrapped_mint: Pubkey,
+    pub user: Pubkey,
+}
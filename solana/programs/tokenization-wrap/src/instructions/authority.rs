@@ -0,0 +1,383 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::*;
+use crate::errors::TokenizationWrapError;
+use crate::instructions::create_mint::WrappedMintState;
+use anchor_lang::solana_program::program::invoke_signed;
+use anchor_spl::token_interface::spl_pod::optional_keys::OptionalNonZeroPubkey;
+use anchor_spl::token_interface::{Mint, Token2022};
+use spl_token_2022_v9::{
+    extension::{
+        confidential_transfer::ConfidentialTransferMint,
+        interest_bearing_mint::InterestBearingConfig, metadata_pointer::MetadataPointer,
+        mint_close_authority::MintCloseAuthority, pausable::PausableConfig,
+        permanent_delegate::PermanentDelegate, scaled_ui_amount::ScaledUiAmountConfig,
+        transfer_fee::TransferFeeConfig, transfer_hook::TransferHook, BaseStateWithExtensions,
+        StateWithExtensions,
+    },
+    instruction::{set_authority, AuthorityType},
+    state::Mint as MintState,
+};
+use spl_token_metadata_interface::state::TokenMetadata;
+
+/// Hands every mint authority the owner holds over to the wrapped mint authority PDA,
+/// so the compliance powers can only be used through the program's role-gated instructions.
+/// The metadata pointer and mint close authorities stay with the owner, no program
+/// instruction uses them.
+pub fn delegate_mint_authorities(ctx: Context<DelegateMintAuthorities>) -> Result<()> {
+    let wrapped_mint = &ctx.accounts.wrapped_mint;
+    let wrapped_mint_key = wrapped_mint.key();
+    let wrapped_mint_owner = &ctx.accounts.wrapped_mint_owner;
+    let wrapped_mint_owner_key = wrapped_mint_owner.key();
+    let wrapped_mint_authority_key = ctx.accounts.wrapped_mint_authority.key();
+    let token_program = &ctx.accounts.token_program;
+
+    let rotated_authorities = rotate_mint_authorities(
+        &wrapped_mint.to_account_info(),
+        &wrapped_mint_owner.to_account_info(),
+        &wrapped_mint_authority_key,
+        &token_program.to_account_info(),
+        &[],
+        |authority_kind| !authority_kind.owner_retained(),
+    )?;
+
+    let wrapped_mint_state = &mut ctx.accounts.wrapped_mint_state;
+    wrapped_mint_state.authority_mode = MintAuthorityMode::Program;
+
+    emit!(MintAuthorityModeEvent {
+        wrapped_mint: wrapped_mint_key,
+        authority_mode: MintAuthorityMode::Program,
+        previous_authority: wrapped_mint_owner_key,
+        new_authority: wrapped_mint_authority_key,
+        rotated_authorities: rotated_authorities.clone(),
+    });
+
+    msg!("Wrapped mint: {}", wrapped_mint_key);
+    msg!("Wrapped mint authority: {}", wrapped_mint_authority_key);
+    msg!("Rotated authorities: {:?}", rotated_authorities);
+
+    return Ok(());
+}
+
+/// Hands every mint authority the wrapped mint authority PDA holds back to the owner.
+/// Not possible once a transfer hook timelock is set, the owner would hold the transfer
+/// hook authority and could swap the hook without waiting for the timelock.
+pub fn reclaim_mint_authorities(ctx: Context<ReclaimMintAuthorities>) -> Result<()> {
+    let wrapped_mint = &ctx.accounts.wrapped_mint;
+    let wrapped_mint_key = wrapped_mint.key();
+    let wrapped_mint_owner_key = ctx.accounts.wrapped_mint_owner.key();
+    let wrapped_mint_authority = &ctx.accounts.wrapped_mint_authority;
+    let wrapped_mint_authority_key = wrapped_mint_authority.key();
+    let token_program = &ctx.accounts.token_program;
+
+    // wrapped mint authority seeds
+    let wrapped_mint_authority_bump = ctx.bumps.wrapped_mint_authority;
+    let wrapped_mint_authority_seeds: &[&[&[u8]]] = &[&[
+        WRAPPED_MINT_AUTHORITY_SEED,
+        wrapped_mint_key.as_ref(),
+        &[wrapped_mint_authority_bump],
+    ]];
+
+    let rotated_authorities = rotate_mint_authorities(
+        &wrapped_mint.to_account_info(),
+        &wrapped_mint_authority.to_account_info(),
+        &wrapped_mint_owner_key,
+        &token_program.to_account_info(),
+        wrapped_mint_authority_seeds,
+        |_| true,
+    )?;
+
+    let wrapped_mint_state = &mut ctx.accounts.wrapped_mint_state;
+    wrapped_mint_state.authority_mode = MintAuthorityMode::Owner;
+
+    emit!(MintAuthorityModeEvent {
+        wrapped_mint: wrapped_mint_key,
+        authority_mode: MintAuthorityMode::Owner,
+        previous_authority: wrapped_mint_authority_key,
+        new_authority: wrapped_mint_owner_key,
+        rotated_authorities: rotated_authorities.clone(),
+    });
+
+    msg!("Wrapped mint: {}", wrapped_mint_key);
+    msg!("Wrapped mint owner: {}", wrapped_mint_owner_key);
+    msg!("Rotated authorities: {:?}", rotated_authorities);
+
+    return Ok(());
+}
+
+/// Moves every authority on `wrapped_mint` that is currently held by `current_authority`
+/// to `new_authority` and returns the list of authorities that were rotated.
+/// Authorities held by anyone else (or already cleared) and the ones `include` rejects are
+/// left untouched. `signer_seeds` is empty when `current_authority` signs the transaction itself and holds
+/// the seeds of the wrapped mint authority PDA when the program hands its authorities over.
+pub fn rotate_mint_authorities<'info>(
+    wrapped_mint: &AccountInfo<'info>,
+    current_authority: &AccountInfo<'info>,
+    new_authority: &Pubkey,
+    token_program: &AccountInfo<'info>,
+    signer_seeds: &[&[&[u8]]],
+    include: impl Fn(MintAuthorityKind) -> bool,
+) -> Result<Vec<MintAuthorityKind>> {
+    let current_authority_key = current_authority.key();
+    let wrapped_mint_key = wrapped_mint.key();
+
+    // collect first, the mint data borrow must be released before any CPI
+    let (held_authorities, metadata_held) = {
+        let wrapped_mint_data = wrapped_mint.try_borrow_data()?;
+        let mint_state = StateWithExtensions::<MintState>::unpack(&wrapped_mint_data)
+            .map_err(|_| TokenizationWrapError::InvalidAccountData)?;
+        let held_by_current = |authority: Option<Pubkey>| authority == Some(current_authority_key);
+
+        let mut held_authorities = Vec::new();
+        if held_by_current(mint_state.base.freeze_authority.into()) {
+            held_authorities.push(MintAuthorityKind::Freeze);
+        }
+        if let Ok(extension) = mint_state.get_extension::<PausableConfig>() {
+            if held_by_current(extension.authority.into()) {
+                held_authorities.push(MintAuthorityKind::Pause);
+            }
+        }
+        if let Ok(extension) = mint_state.get_extension::<PermanentDelegate>() {
+            if held_by_current(extension.delegate.into()) {
+                held_authorities.push(MintAuthorityKind::PermanentDelegate);
+            }
+        }
+        if let Ok(extension) = mint_state.get_extension::<TransferHook>() {
+            if held_by_current(extension.authority.into()) {
+                held_authorities.push(MintAuthorityKind::TransferHook);
+            }
+        }
+        if let Ok(extension) = mint_state.get_extension::<MetadataPointer>() {
+            if held_by_current(extension.authority.into()) {
+                held_authorities.push(MintAuthorityKind::MetadataPointer);
+            }
+        }
+        if let Ok(extension) = mint_state.get_extension::<MintCloseAuthority>() {
+            if held_by_current(extension.close_authority.into()) {
+                held_authorities.push(MintAuthorityKind::CloseMint);
+            }
+        }
+        if let Ok(extension) = mint_state.get_extension::<ScaledUiAmountConfig>() {
+            if held_by_current(extension.authority.into()) {
+                held_authorities.push(MintAuthorityKind::ScaledUiAmount);
+            }
+        }
+        if let Ok(extension) = mint_state.get_extension::<ConfidentialTransferMint>() {
+            if held_by_current(extension.authority.into()) {
+                held_authorities.push(MintAuthorityKind::ConfidentialTransferMint);
+            }
+        }
+        if let Ok(extension) = mint_state.get_extension::<InterestBearingConfig>() {
+            if held_by_current(extension.rate_authority.into()) {
+                held_authorities.push(MintAuthorityKind::InterestRate);
+            }
+        }
+        if let Ok(extension) = mint_state.get_extension::<TransferFeeConfig>() {
+            if held_by_current(extension.transfer_fee_config_authority.into()) {
+                held_authorities.push(MintAuthorityKind::TransferFeeConfig);
+            }
+            if held_by_current(extension.withdraw_withheld_authority.into()) {
+                held_authorities.push(MintAuthorityKind::WithheldWithdraw);
+            }
+        }
+        held_authorities.retain(|authority_kind| include(*authority_kind));
+        let metadata_held = include(MintAuthorityKind::TokenMetadata)
+            && mint_state
+                .get_variable_len_extension::<TokenMetadata>()
+                .map(|metadata| held_by_current(metadata.update_authority.into()))
+                .unwrap_or(false);
+
+        (held_authorities, metadata_held)
+    };
+
+    let mut rotated_authorities = Vec::new();
+    for authority_kind in held_authorities {
+        let authority_type = authority_kind
+            .authority_type()
+            .ok_or(TokenizationWrapError::InvalidAccountData)?;
+        invoke_signed(
+            &set_authority(
+                &spl_token_2022_v9::ID,
+                &wrapped_mint_key,
+                Some(new_authority),
+                authority_type,
+                &current_authority_key,
+                &[],
+            )?,
+            &[wrapped_mint.clone(), current_authority.clone()],
+            signer_seeds,
+        )?;
+        rotated_authorities.push(authority_kind);
+    }
+
+    // token metadata update authority is managed by the metadata interface, not set_authority
+    if metadata_held {
+        invoke_signed(
+            &spl_token_metadata_interface::instruction::update_authority(
+                &spl_token_2022_v9::ID,
+                &wrapped_mint_key,
+                &current_authority_key,
+                OptionalNonZeroPubkey(*new_authority),
+            ),
+            &[
+                token_program.clone(),
+                wrapped_mint.clone(),
+                current_authority.clone(),
+            ],
+            signer_seeds,
+        )?;
+        rotated_authorities.push(MintAuthorityKind::TokenMetadata);
+    }
+
+    return Ok(rotated_authorities);
+}
+
+#[derive(Accounts)]
+pub struct DelegateMintAuthorities<'info> {
+    // wrapped mint owner
+    #[account(
+        mut,
+        address = wrapped_mint_state.wrapped_mint_owner @TokenizationWrapError::NotOwner,
+    )]
+    pub wrapped_mint_owner: Signer<'info>,
+
+    // The wrapped mint
+    #[account(
+        mut,
+        constraint = (wrapped_mint_state.wrapped_mint == wrapped_mint.key()) @ TokenizationWrapError::InvalidWrappedMint,
+    )]
+    pub wrapped_mint: InterfaceAccount<'info, Mint>,
+
+    // wrapped mint state
+    #[account(
+        mut,
+        seeds = [WRAPPED_MINT_STATE_SEED, &wrapped_mint.key().as_ref()],
+        bump,
+        constraint = (wrapped_mint_state.authority_mode == MintAuthorityMode::Owner) @ TokenizationWrapError::InvalidMintAuthorityMode,
+        constraint = !wrapped_mint_state.ownership_transfer_pending() @ TokenizationWrapError::OwnershipTransferPending,
+    )]
+    pub wrapped_mint_state: Account<'info, WrappedMintState>,
+
+    /// CHECK: PDA holding the wrapped mint authorities, no data
+    #[account(
+        seeds = [WRAPPED_MINT_AUTHORITY_SEED, &wrapped_mint.key().as_ref()],
+        bump,
+    )]
+    pub wrapped_mint_authority: UncheckedAccount<'info>,
+
+    pub token_program: Program<'info, Token2022>,
+}
+
+#[derive(Accounts)]
+pub struct ReclaimMintAuthorities<'info> {
+    // wrapped mint owner
+    #[account(
+        mut,
+        address = wrapped_mint_state.wrapped_mint_owner @TokenizationWrapError::NotOwner,
+    )]
+    pub wrapped_mint_owner: Signer<'info>,
+
+    // The wrapped mint
+    #[account(
+        mut,
+        constraint = (wrapped_mint_state.wrapped_mint == wrapped_mint.key()) @ TokenizationWrapError::InvalidWrappedMint,
+    )]
+    pub wrapped_mint: InterfaceAccount<'info, Mint>,
+
+    // wrapped mint state
+    #[account(
+        mut,
+        seeds = [WRAPPED_MINT_STATE_SEED, &wrapped_mint.key().as_ref()],
+        bump,
+        constraint = (wrapped_mint_state.authority_mode == MintAuthorityMode::Program) @ TokenizationWrapError::InvalidMintAuthorityMode,
+        constraint = !wrapped_mint_state.ownership_transfer_pending() @ TokenizationWrapError::OwnershipTransferPending,
+        constraint = (wrapped_mint_state.transfer_hook_timelock == 0) @ TokenizationWrapError::TransferHookTimelocked,
+    )]
+    pub wrapped_mint_state: Account<'info, WrappedMintState>,
+
+    /// CHECK: PDA holding the wrapped mint authorities, no data
+    #[account(
+        seeds = [WRAPPED_MINT_AUTHORITY_SEED, &wrapped_mint.key().as_ref()],
+        bump,
+    )]
+    pub wrapped_mint_authority: UncheckedAccount<'info>,
+
+    pub token_program: Program<'info, Token2022>,
+}
+
+/// Who holds the wrapped mint's extension authorities.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug, InitSpace)]
+pub enum MintAuthorityMode {
+    // the wrapped mint owner key signs Token-2022 instructions directly
+    Owner,
+    // the wrapped mint authority PDA, powers are used through role-gated program instructions
+    Program,
+}
+
+impl MintAuthorityMode {
+    /// Whether the owner key holds `authority_kind` in this mode, in program mode the owner
+    /// only keeps the authorities no program instruction uses.
+    pub fn owner_holds(self, authority_kind: MintAuthorityKind) -> bool {
+        match self {
+            MintAuthorityMode::Owner => true,
+            MintAuthorityMode::Program => authority_kind.owner_retained(),
+        }
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum MintAuthorityKind {
+    Freeze,
+    Pause,
+    PermanentDelegate,
+    TransferHook,
+    MetadataPointer,
+    CloseMint,
+    ScaledUiAmount,
+    ConfidentialTransferMint,
+    TokenMetadata,
+    InterestRate,
+    TransferFeeConfig,
+    WithheldWithdraw,
+}
+
+impl MintAuthorityKind {
+    /// Authorities that stay with the owner key in program mode, the program has no
+    /// instruction that repoints the metadata or closes the mint.
+    pub fn owner_retained(self) -> bool {
+        return matches!(
+            self,
+            MintAuthorityKind::MetadataPointer | MintAuthorityKind::CloseMint
+        );
+    }
+
+    /// Token-2022 `set_authority` type for this authority, `None` for the token metadata
+    /// update authority which is rotated through the token metadata interface instead.
+    pub fn authority_type(self) -> Option<AuthorityType> {
+        match self {
+            MintAuthorityKind::Freeze => Some(AuthorityType::FreezeAccount),
+            MintAuthorityKind::Pause => Some(AuthorityType::Pause),
+            MintAuthorityKind::PermanentDelegate => Some(AuthorityType::PermanentDelegate),
+            MintAuthorityKind::TransferHook => Some(AuthorityType::TransferHookProgramId),
+            MintAuthorityKind::MetadataPointer => Some(AuthorityType::MetadataPointer),
+            MintAuthorityKind::CloseMint => Some(AuthorityType::CloseMint),
+            MintAuthorityKind::ScaledUiAmount => Some(AuthorityType::ScaledUiAmount),
+            MintAuthorityKind::ConfidentialTransferMint => {
+                Some(AuthorityType::ConfidentialTransferMint)
+            }
+            MintAuthorityKind::TokenMetadata => None,
+            MintAuthorityKind::InterestRate => Some(AuthorityType::InterestRate),
+            MintAuthorityKind::TransferFeeConfig => Some(AuthorityType::TransferFeeConfig),
+            MintAuthorityKind::WithheldWithdraw => Some(AuthorityType::WithheldWithdraw),
+        }
+    }
+}
+
+#[event]
+pub struct MintAuthorityModeEvent {
+    pub wrapped_mint: Pubkey,
+    pub authority_mode: MintAuthorityMode,
+    pub previous_authority: Pubkey,
+    pub new_authority: Pubkey,
+    pub rotated_authorities: Vec<MintAuthorityKind>,
+}
@@ -3,8 +3,7 @@ use crate::errors::TokenizationWrapError;
 use crate::instructions::create_mint::WrappedMintState;
 use anchor_lang::prelude::*;
 use anchor_spl::associated_token::AssociatedToken;
-use anchor_spl::token_2022::Token2022;
-use anchor_spl::token_interface::{close_account, CloseAccount, Mint, TokenAccount};
+use anchor_spl::token_interface::{close_account, CloseAccount, Mint, TokenAccount, TokenInterface};
 
 /// Closes a stuck escrow account when its extensions don't match the mint's requirements.
 /// The escrow ATA can get "stuck" when an unwrapped mint with a close authority is closed
@@ -14,7 +13,7 @@ pub fn close_unwrapped_mint_vault(ctx: Context<CloseUnwrappedMintVault>) -> Resu
     let unwrapped_mint_vault = &ctx.accounts.unwrapped_mint_vault;
     let unwrapped_mint = &ctx.accounts.unwrapped_mint;
     let wrapped_mint_owner = &ctx.accounts.wrapped_mint_owner;
-    let token_program = &ctx.accounts.token_program;
+    let unwrapped_token_program = &ctx.accounts.unwrapped_token_program;
     let wrapped_mint = &ctx.accounts.wrapped_mint;
     let wrapped_mint_state = &ctx.accounts.wrapped_mint_state;
 
@@ -32,7 +31,7 @@ pub fn close_unwrapped_mint_vault(ctx: Context<CloseUnwrappedMintVault>) -> Resu
     ]];
 
     close_account(CpiContext::new_with_signer(
-        token_program.to_account_info(),
+        unwrapped_token_program.to_account_info(),
         CloseAccount {
             account: unwrapped_mint_vault.to_account_info(),
             destination: wrapped_mint_owner.to_account_info(),
@@ -91,12 +90,13 @@ pub struct CloseUnwrappedMintVault<'info> {
         mut,
         associated_token::mint = unwrapped_mint,
         associated_token::authority = wrapped_mint, // wrapped mint is authority, holding unwrapped tokens
-        associated_token::token_program = token_program,
+        associated_token::token_program = unwrapped_token_program,
         constraint = (wrapped_mint_state.unwrapped_mint_vault == unwrapped_mint_vault.key()) @ TokenizationWrapError::InvalidUnwrappedMintVault,
     )]
     pub unwrapped_mint_vault: InterfaceAccount<'info, TokenAccount>,
     pub associated_token_program: Program<'info, AssociatedToken>,
-    pub token_program: Program<'info, Token2022>,
+    // token program of the unwrapped mint, SPL Token or Token-2022
+    pub unwrapped_token_program: Interface<'info, TokenInterface>,
 }
 
 #[event]
@@ -0,0 +1,236 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::*;
+use crate::errors::TokenizationWrapError;
+use crate::instructions::authority::MintAuthorityMode;
+use crate::instructions::create_mint::{CreateMintConfig, WrappedMintState};
+use crate::instructions::role::{RoleKind, RoleState};
+use anchor_lang::solana_program::program::invoke_signed;
+use anchor_spl::token_interface::{Mint, Token2022, TokenAccount};
+use spl_token_2022_v9::{
+    extension::confidential_transfer,
+    solana_zk_sdk::encryption::pod::elgamal::PodElGamalPubkey,
+};
+
+/// Sets the ElGamal pubkey of the auditor able to decrypt confidential transfer amounts,
+/// `None` removes the auditor.
+pub fn set_confidential_auditor(
+    ctx: Context<UpdateConfidentialTransferMint>,
+    auditor_elgamal_pubkey: Option<[u8; 32]>,
+) -> Result<()> {
+    let auto_approve = CreateMintConfig::from_mint(&ctx.accounts.wrapped_mint.to_account_info())?
+        .confidential_auto_approve;
+    return update_confidential_transfer_mint(ctx, auto_approve, auditor_elgamal_pubkey);
+}
+
+/// Toggles whether new accounts may use confidential transfers without an
+/// `approve_confidential_account` from the compliance role.
+pub fn set_confidential_auto_approve(
+    ctx: Context<UpdateConfidentialTransferMint>,
+    auto_approve: bool,
+) -> Result<()> {
+    let auditor_elgamal_pubkey = CreateMintConfig::from_mint(&ctx.accounts.wrapped_mint.to_account_info())?
+        .confidential_auditor_elgamal_pubkey;
+    return update_confidential_transfer_mint(ctx, auto_approve, auditor_elgamal_pubkey);
+}
+
+// Token-2022 updates auto approve and the auditor together, the unchanged one is read
+// from the mint's ConfidentialTransferMint extension, the wrapped mint state only mirrors it
+fn update_confidential_transfer_mint(
+    ctx: Context<UpdateConfidentialTransferMint>,
+    auto_approve: bool,
+    auditor_elgamal_pubkey: Option<[u8; 32]>,
+) -> Result<()> {
+    let wrapped_mint = &ctx.accounts.wrapped_mint;
+    let wrapped_mint_key = wrapped_mint.key();
+    let wrapped_mint_owner = &ctx.accounts.wrapped_mint_owner;
+    let wrapped_mint_authority = &ctx.accounts.wrapped_mint_authority;
+
+    // wrapped mint authority seeds
+    let wrapped_mint_authority_bump = ctx.bumps.wrapped_mint_authority;
+    let wrapped_mint_authority_seeds: &[&[&[u8]]] = &[&[
+        WRAPPED_MINT_AUTHORITY_SEED,
+        wrapped_mint_key.as_ref(),
+        &[wrapped_mint_authority_bump],
+    ]];
+
+    // confidential transfer authority is the owner key or the wrapped mint authority PDA
+    let (confidential_authority, signer_seeds) = match ctx.accounts.wrapped_mint_state.authority_mode {
+        MintAuthorityMode::Owner => (wrapped_mint_owner.to_account_info(), &[][..]),
+        MintAuthorityMode::Program => (
+            wrapped_mint_authority.to_account_info(),
+            wrapped_mint_authority_seeds,
+        ),
+    };
+
+    invoke_signed(
+        &confidential_transfer::instruction::update_mint(
+            &spl_token_2022_v9::ID,
+            &wrapped_mint_key,
+            &confidential_authority.key(),
+            &[],
+            auto_approve,
+            auditor_elgamal_pubkey.map(PodElGamalPubkey::from),
+        )?,
+        &[wrapped_mint.to_account_info(), confidential_authority.clone()],
+        signer_seeds,
+    )?;
+
+    let wrapped_mint_state = &mut ctx.accounts.wrapped_mint_state;
+    wrapped_mint_state.mint_config.confidential_auto_approve = auto_approve;
+    wrapped_mint_state.mint_config.confidential_auditor_elgamal_pubkey = auditor_elgamal_pubkey;
+
+    emit!(ConfidentialTransferMintUpdatedEvent {
+        wrapped_mint: wrapped_mint_key,
+        auto_approve: auto_approve,
+        auditor_elgamal_pubkey: auditor_elgamal_pubkey,
+    });
+
+    msg!("wrapped mint: {:?}", wrapped_mint_key);
+    msg!("confidential auto approve: {:?}", auto_approve);
+    msg!("confidential auditor: {:?}", auditor_elgamal_pubkey);
+
+    return Ok(());
+}
+
+/// Approves a wrapped token account configured for confidential transfers, needed
+/// while auto approve is off.
+pub fn approve_confidential_account(ctx: Context<ApproveConfidentialAccount>) -> Result<()> {
+    let wrapped_mint = &ctx.accounts.wrapped_mint;
+    let wrapped_mint_key = wrapped_mint.key();
+    let wrapped_mint_authority = &ctx.accounts.wrapped_mint_authority;
+    let holder_token_account = &ctx.accounts.holder_token_account;
+    let compliance_key = ctx.accounts.compliance.key();
+
+    // wrapped mint authority seeds
+    let wrapped_mint_authority_bump = ctx.bumps.wrapped_mint_authority;
+    let wrapped_mint_authority_seeds: &[&[&[u8]]] = &[&[
+        WRAPPED_MINT_AUTHORITY_SEED,
+        wrapped_mint_key.as_ref(),
+        &[wrapped_mint_authority_bump],
+    ]];
+
+    invoke_signed(
+        &confidential_transfer::instruction::approve_account(
+            &spl_token_2022_v9::ID,
+            &holder_token_account.key(),
+            &wrapped_mint_key,
+            &wrapped_mint_authority.key(),
+            &[],
+        )?,
+        &[
+            holder_token_account.to_account_info(),
+            wrapped_mint.to_account_info(),
+            wrapped_mint_authority.to_account_info(),
+        ],
+        wrapped_mint_authority_seeds,
+    )?;
+
+    emit!(ConfidentialAccountApprovedEvent {
+        wrapped_mint: wrapped_mint_key,
+        holder: holder_token_account.owner,
+        token_account: holder_token_account.key(),
+        compliance: compliance_key,
+    });
+
+    msg!("wrapped mint: {:?}", wrapped_mint_key);
+    msg!("confidential account approved: {:?}", holder_token_account.key());
+
+    return Ok(());
+}
+
+#[derive(Accounts)]
+pub struct UpdateConfidentialTransferMint<'info> {
+    // wrapped mint owner
+    #[account(
+        address = wrapped_mint_state.wrapped_mint_owner @TokenizationWrapError::NotOwner,
+    )]
+    pub wrapped_mint_owner: Signer<'info>,
+
+    // The wrapped mint
+    #[account(
+        mut,
+        constraint = (wrapped_mint_state.wrapped_mint == wrapped_mint.key()) @ TokenizationWrapError::InvalidWrappedMint,
+    )]
+    pub wrapped_mint: InterfaceAccount<'info, Mint>,
+
+    // wrapped mint state, the mint must have been created with the confidential transfer extension
+    #[account(
+        mut,
+        seeds = [WRAPPED_MINT_STATE_SEED, &wrapped_mint.key().as_ref()],
+        bump,
+        constraint = wrapped_mint_state.mint_config.confidential_transfer @ TokenizationWrapError::InvalidMintConfig,
+    )]
+    pub wrapped_mint_state: Account<'info, WrappedMintState>,
+
+    /// CHECK: PDA holding the wrapped mint authorities, no data
+    #[account(
+        seeds = [WRAPPED_MINT_AUTHORITY_SEED, &wrapped_mint.key().as_ref()],
+        bump,
+    )]
+    pub wrapped_mint_authority: UncheckedAccount<'info>,
+
+    pub token_program: Program<'info, Token2022>,
+}
+
+#[derive(Accounts)]
+pub struct ApproveConfidentialAccount<'info> {
+    // compliance
+    pub compliance: Signer<'info>,
+
+    // The wrapped mint
+    #[account(
+        constraint = (wrapped_mint_state.wrapped_mint == wrapped_mint.key()) @ TokenizationWrapError::InvalidWrappedMint,
+    )]
+    pub wrapped_mint: InterfaceAccount<'info, Mint>,
+
+    // wrapped mint state
+    #[account(
+        seeds = [WRAPPED_MINT_STATE_SEED, &wrapped_mint.key().as_ref()],
+        bump,
+        constraint = (wrapped_mint_state.authority_mode == MintAuthorityMode::Program) @ TokenizationWrapError::InvalidMintAuthorityMode,
+        constraint = wrapped_mint_state.mint_config.confidential_transfer @ TokenizationWrapError::InvalidMintConfig,
+    )]
+    pub wrapped_mint_state: Account<'info, WrappedMintState>,
+
+    // compliance role check
+    #[account(
+        seeds=[WRAPPED_ROLE_SEED,&wrapped_mint.key().as_ref(),&[RoleKind::Compliance.as_u8()],compliance.key().as_ref()],
+        bump,
+        constraint = compliance_role.has_role(&compliance.key(), &[RoleKind::Compliance]) @ TokenizationWrapError::Unauthorized,
+    )]
+    pub compliance_role: Account<'info, RoleState>,
+
+    // holder's wrapped token account, configured for confidential transfers
+    #[account(
+        mut,
+        token::mint = wrapped_mint,
+        token::token_program = token_program,
+    )]
+    pub holder_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// CHECK: PDA holding the wrapped mint authorities, no data
+    #[account(
+        seeds = [WRAPPED_MINT_AUTHORITY_SEED, &wrapped_mint.key().as_ref()],
+        bump,
+    )]
+    pub wrapped_mint_authority: UncheckedAccount<'info>,
+
+    pub token_program: Program<'info, Token2022>,
+}
+
+#[event]
+pub struct ConfidentialTransferMintUpdatedEvent {
+    pub wrapped_mint: Pubkey,
+    pub auto_approve: bool,
+    // None when there is no auditor
+    pub auditor_elgamal_pubkey: Option<[u8; 32]>,
+}
+
+#[event]
+pub struct ConfidentialAccountApprovedEvent {
+    pub wrapped_mint: Pubkey,
+    pub holder: Pubkey,
+    pub token_account: Pubkey,
+    pub compliance: Pubkey,
+}
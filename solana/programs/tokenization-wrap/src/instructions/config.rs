@@ -0,0 +1,104 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::*;
+use crate::errors::TokenizationWrapError;
+use crate::program::TokenizationWrap;
+
+/// Creates the program wide configuration, signed by the program's upgrade authority.
+/// `authority` manages the configuration afterwards.
+pub fn initialize_program_config(
+    ctx: Context<InitializeProgramConfig>,
+    authority: Pubkey,
+    allowed_underlying_risks: u32,
+) -> Result<()> {
+    let program_config = &mut ctx.accounts.program_config;
+    program_config.authority = authority;
+    program_config.allowed_underlying_risks = allowed_underlying_risks;
+
+    emit!(UnderlyingRiskPolicyUpdatedEvent {
+        authority: authority,
+        old_allowed_underlying_risks: 0,
+        new_allowed_underlying_risks: allowed_underlying_risks,
+    });
+
+    msg!("program config authority: {}", authority);
+    msg!("allowed underlying risks: {:#x}", allowed_underlying_risks);
+
+    return Ok(());
+}
+
+/// Sets the UNDERLYING_RISK_* flags create_mint accepts on an unwrapped mint. Wrapped mints
+/// created earlier keep the risk flags recorded at creation.
+pub fn set_underlying_risk_policy(ctx: Context<SetUnderlyingRiskPolicy>, allowed_underlying_risks: u32) -> Result<()> {
+    let program_config = &mut ctx.accounts.program_config;
+    let old_allowed_underlying_risks = program_config.allowed_underlying_risks;
+    program_config.allowed_underlying_risks = allowed_underlying_risks;
+
+    emit!(UnderlyingRiskPolicyUpdatedEvent {
+        authority: program_config.authority,
+        old_allowed_underlying_risks: old_allowed_underlying_risks,
+        new_allowed_underlying_risks: allowed_underlying_risks,
+    });
+
+    msg!("allowed underlying risks: {:#x}", allowed_underlying_risks);
+
+    return Ok(());
+}
+
+#[derive(Accounts)]
+pub struct InitializeProgramConfig<'info> {
+    // upgrade authority of this program
+    #[account(mut)]
+    pub upgrade_authority: Signer<'info>,
+
+    #[account(
+        init,
+        seeds = [PROGRAM_CONFIG_SEED],
+        bump,
+        payer = upgrade_authority,
+        space = ANCHOR_DISCRIMINATOR_SIZE + ProgramConfig::INIT_SPACE,
+    )]
+    pub program_config: Account<'info, ProgramConfig>,
+
+    #[account(
+        constraint = program.programdata_address()? == Some(program_data.key()) @ TokenizationWrapError::InvalidAccountData,
+    )]
+    pub program: Program<'info, TokenizationWrap>,
+
+    #[account(
+        constraint = program_data.upgrade_authority_address == Some(upgrade_authority.key()) @ TokenizationWrapError::Unauthorized,
+    )]
+    pub program_data: Account<'info, ProgramData>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SetUnderlyingRiskPolicy<'info> {
+    #[account(
+        address = program_config.authority @ TokenizationWrapError::Unauthorized,
+    )]
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [PROGRAM_CONFIG_SEED],
+        bump,
+    )]
+    pub program_config: Account<'info, ProgramConfig>,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct ProgramConfig {
+    pub authority: Pubkey,
+    // UNDERLYING_RISK_* flags create_mint accepts on an unwrapped mint
+    pub allowed_underlying_risks: u32,
+}
+
+#[event]
+pub struct UnderlyingRiskPolicyUpdatedEvent {
+    pub authority: Pubkey,
+    pub old_allowed_underlying_risks: u32,
+    pub new_allowed_underlying_risks: u32,
+}
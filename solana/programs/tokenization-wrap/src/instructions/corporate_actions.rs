@@ -0,0 +1,237 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::*;
+use crate::errors::TokenizationWrapError;
+use crate::instructions::authority::MintAuthorityMode;
+use crate::instructions::create_mint::WrappedMintState;
+use crate::instructions::role::{RoleKind, RoleState};
+use anchor_lang::solana_program::program::invoke_signed;
+use anchor_spl::token_interface::{Mint, Token2022};
+use spl_token_2022_v9::{
+    extension::{
+        scaled_ui_amount::{self, ScaledUiAmountConfig},
+        BaseStateWithExtensions, StateWithExtensions,
+    },
+    state::Mint as MintState,
+};
+
+/// Schedules a new scaled UI amount multiplier for a stock split or another corporate
+/// action. The change takes effect at `effective_timestamp`, a timestamp in the past
+/// applies it immediately. The new multiplier must stay within `scaled_ui_max_change`
+/// of the current one in either direction.
+///
+/// Token-2022 only keeps one pending multiplier and never copies it into `multiplier`
+/// once it kicks in, so a previously scheduled multiplier already in effect is locked
+/// in first, otherwise scheduling the next one would fall back to the older multiplier
+/// until the new one takes effect.
+pub fn update_multiplier(
+    ctx: Context<UpdateMultiplier>,
+    new_multiplier: f64,
+    effective_timestamp: i64,
+) -> Result<()> {
+    require!(
+        new_multiplier.is_finite() && new_multiplier > 0.0,
+        TokenizationWrapError::MultiplierOutOfRange
+    );
+
+    let wrapped_mint = &ctx.accounts.wrapped_mint;
+    let wrapped_mint_key = wrapped_mint.key();
+    let wrapped_mint_authority = &ctx.accounts.wrapped_mint_authority;
+    let corporate_actions_key = ctx.accounts.corporate_actions.key();
+    let now = Clock::get()?.unix_timestamp;
+
+    // multiplier in effect now, a previously scheduled one may already have kicked in
+    let (old_multiplier, stored_multiplier) = {
+        let wrapped_mint_info = wrapped_mint.to_account_info();
+        let wrapped_mint_data = wrapped_mint_info.try_borrow_data()?;
+        let mint_state = StateWithExtensions::<MintState>::unpack(&wrapped_mint_data)
+            .map_err(|_| TokenizationWrapError::InvalidWrappedMint)?;
+        let scaled_ui_config = mint_state
+            .get_extension::<ScaledUiAmountConfig>()
+            .map_err(|_| TokenizationWrapError::InvalidMintConfig)?;
+        let stored_multiplier = f64::from(scaled_ui_config.multiplier);
+        if now >= i64::from(scaled_ui_config.new_multiplier_effective_timestamp) {
+            (f64::from(scaled_ui_config.new_multiplier), stored_multiplier)
+        } else {
+            (stored_multiplier, stored_multiplier)
+        }
+    };
+
+    // bound the change to the configured range, e.g. 10.0 allows up to a 10:1 split
+    // or a 1:10 reverse split
+    let max_change = ctx.accounts.wrapped_mint_state.mint_config.scaled_ui_max_change;
+    let change = new_multiplier / old_multiplier;
+    require!(
+        change <= max_change && change >= 1.0 / max_change,
+        TokenizationWrapError::MultiplierOutOfRange
+    );
+
+    // wrapped mint authority seeds
+    let wrapped_mint_authority_bump = ctx.bumps.wrapped_mint_authority;
+    let wrapped_mint_authority_seeds: &[&[&[u8]]] = &[&[
+        WRAPPED_MINT_AUTHORITY_SEED,
+        wrapped_mint_key.as_ref(),
+        &[wrapped_mint_authority_bump],
+    ]];
+
+    // lock in the multiplier in effect before scheduling over it
+    if old_multiplier != stored_multiplier {
+        invoke_update_multiplier(
+            &wrapped_mint.to_account_info(),
+            &wrapped_mint_authority.to_account_info(),
+            wrapped_mint_authority_seeds,
+            old_multiplier,
+            now,
+        )?;
+    }
+
+    invoke_update_multiplier(
+        &wrapped_mint.to_account_info(),
+        &wrapped_mint_authority.to_account_info(),
+        wrapped_mint_authority_seeds,
+        new_multiplier,
+        effective_timestamp,
+    )?;
+
+    emit!(MultiplierUpdatedEvent {
+        wrapped_mint: wrapped_mint_key,
+        old_multiplier: old_multiplier,
+        new_multiplier: new_multiplier,
+        effective_timestamp: effective_timestamp.max(now),
+        corporate_actions: corporate_actions_key,
+    });
+
+    msg!("wrapped mint: {:?}", wrapped_mint_key);
+    msg!("old multiplier: {}", old_multiplier);
+    msg!("new multiplier: {}", new_multiplier);
+    msg!("effective timestamp: {}", effective_timestamp.max(now));
+
+    return Ok(());
+}
+
+/// Sets the largest factor a single `update_multiplier` may scale the multiplier by.
+pub fn set_multiplier_bound(ctx: Context<SetMultiplierBound>, max_change: f64) -> Result<()> {
+    require!(
+        max_change.is_finite() && max_change >= 1.0,
+        TokenizationWrapError::InvalidMintConfig
+    );
+
+    let wrapped_mint_state = &mut ctx.accounts.wrapped_mint_state;
+    let old_max_change = wrapped_mint_state.mint_config.scaled_ui_max_change;
+    wrapped_mint_state.mint_config.scaled_ui_max_change = max_change;
+
+    emit!(MultiplierBoundUpdatedEvent {
+        wrapped_mint: wrapped_mint_state.wrapped_mint,
+        old_max_change: old_max_change,
+        new_max_change: max_change,
+    });
+
+    msg!("wrapped mint: {:?}", wrapped_mint_state.wrapped_mint);
+    msg!("multiplier max change: {}", max_change);
+
+    return Ok(());
+}
+
+fn invoke_update_multiplier<'info>(
+    wrapped_mint: &AccountInfo<'info>,
+    wrapped_mint_authority: &AccountInfo<'info>,
+    wrapped_mint_authority_seeds: &[&[&[u8]]],
+    multiplier: f64,
+    effective_timestamp: i64,
+) -> Result<()> {
+    invoke_signed(
+        &scaled_ui_amount::instruction::update_multiplier(
+            &spl_token_2022_v9::ID,
+            wrapped_mint.key,
+            wrapped_mint_authority.key,
+            &[],
+            multiplier,
+            effective_timestamp,
+        )?,
+        &[wrapped_mint.clone(), wrapped_mint_authority.clone()],
+        wrapped_mint_authority_seeds,
+    )?;
+
+    return Ok(());
+}
+
+#[derive(Accounts)]
+pub struct UpdateMultiplier<'info> {
+    // corporate actions
+    pub corporate_actions: Signer<'info>,
+
+    // The wrapped mint
+    #[account(
+        mut,
+        constraint = (wrapped_mint_state.wrapped_mint == wrapped_mint.key()) @ TokenizationWrapError::InvalidWrappedMint,
+    )]
+    pub wrapped_mint: InterfaceAccount<'info, Mint>,
+
+    // wrapped mint state, the mint must have been created with the scaled ui amount extension
+    #[account(
+        seeds = [WRAPPED_MINT_STATE_SEED, &wrapped_mint.key().as_ref()],
+        bump,
+        constraint = (wrapped_mint_state.authority_mode == MintAuthorityMode::Program) @ TokenizationWrapError::InvalidMintAuthorityMode,
+        constraint = wrapped_mint_state.mint_config.scaled_ui_amount @ TokenizationWrapError::InvalidMintConfig,
+    )]
+    pub wrapped_mint_state: Account<'info, WrappedMintState>,
+
+    // corporate actions role check
+    #[account(
+        seeds=[WRAPPED_ROLE_SEED,&wrapped_mint.key().as_ref(),&[RoleKind::CorporateActions.as_u8()],corporate_actions.key().as_ref()],
+        bump,
+        constraint = corporate_actions_role.has_role(&corporate_actions.key(), &[RoleKind::CorporateActions]) @ TokenizationWrapError::Unauthorized,
+    )]
+    pub corporate_actions_role: Account<'info, RoleState>,
+
+    /// CHECK: PDA holding the wrapped mint authorities, no data
+    #[account(
+        seeds = [WRAPPED_MINT_AUTHORITY_SEED, &wrapped_mint.key().as_ref()],
+        bump,
+    )]
+    pub wrapped_mint_authority: UncheckedAccount<'info>,
+
+    pub token_program: Program<'info, Token2022>,
+}
+
+#[derive(Accounts)]
+pub struct SetMultiplierBound<'info> {
+    // wrapped mint owner
+    #[account(
+        address = wrapped_mint_state.wrapped_mint_owner @TokenizationWrapError::NotOwner,
+    )]
+    pub wrapped_mint_owner: Signer<'info>,
+
+    // The wrapped mint
+    #[account(
+        constraint = (wrapped_mint_state.wrapped_mint == wrapped_mint.key()) @ TokenizationWrapError::InvalidWrappedMint,
+    )]
+    pub wrapped_mint: InterfaceAccount<'info, Mint>,
+
+    // wrapped mint state
+    #[account(
+        mut,
+        seeds = [WRAPPED_MINT_STATE_SEED, &wrapped_mint.key().as_ref()],
+        bump,
+        constraint = wrapped_mint_state.mint_config.scaled_ui_amount @ TokenizationWrapError::InvalidMintConfig,
+    )]
+    pub wrapped_mint_state: Account<'info, WrappedMintState>,
+}
+
+#[event]
+pub struct MultiplierUpdatedEvent {
+    pub wrapped_mint: Pubkey,
+    // multiplier in effect when the update was made
+    pub old_multiplier: f64,
+    pub new_multiplier: f64,
+    // unix timestamp the new multiplier takes effect
+    pub effective_timestamp: i64,
+    pub corporate_actions: Pubkey,
+}
+
+#[event]
+pub struct MultiplierBoundUpdatedEvent {
+    pub wrapped_mint: Pubkey,
+    pub old_max_change: f64,
+    pub new_max_change: f64,
+}
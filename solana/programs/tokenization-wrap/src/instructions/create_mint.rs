@@ -1,21 +1,39 @@
 use crate::constants::*;
 use crate::errors::TokenizationWrapError;
+use crate::instructions::authority::MintAuthorityMode;
+use crate::instructions::config::ProgramConfig;
+use crate::instructions::metadata::{validate_additional_metadata, MetadataEntry};
+use crate::instructions::rate_limit::{RateLimitConfig, RateLimitWindow};
+use crate::instructions::underlying::underlying_risk_profile;
+use crate::utils::top_up_rent;
 use anchor_lang::prelude::*;
 use anchor_lang::solana_program;
 use anchor_lang::solana_program::program::{invoke, invoke_signed};
 use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token_interface::spl_pod::bytemuck::pod_bytes_of;
 use anchor_spl::token_interface::{token_metadata_initialize, TokenMetadataInitialize};
-use anchor_spl::token_interface::{Mint, Token2022, TokenAccount};
+use anchor_spl::token_interface::{Mint, Token2022, TokenAccount, TokenInterface};
+use spl_token_metadata_interface::state::Field;
 use spl_token_2022_v9::{
     extension::{
-        confidential_transfer, default_account_state, metadata_pointer, pausable, scaled_ui_amount,
-        transfer_hook, ExtensionType,
+        confidential_transfer::{self, ConfidentialTransferMint},
+        default_account_state::{self, DefaultAccountState},
+        interest_bearing_mint::{self, InterestBearingConfig},
+        metadata_pointer,
+        mint_close_authority::MintCloseAuthority,
+        pausable::{self, PausableConfig},
+        permanent_delegate::PermanentDelegate,
+        scaled_ui_amount::{self, ScaledUiAmountConfig},
+        transfer_fee::{self, TransferFeeConfig},
+        transfer_hook::{self, TransferHook},
+        BaseStateWithExtensions, ExtensionType, StateWithExtensions,
     },
     instruction::{
         initialize_mint2, initialize_mint_close_authority, initialize_permanent_delegate,
     },
     pod::PodMint,
-    state::AccountState,
+    solana_zk_sdk::encryption::pod::elgamal::PodElGamalPubkey,
+    state::{AccountState, Mint as MintState},
 };
 
 pub fn create_mint(
@@ -24,11 +42,16 @@ pub fn create_mint(
     name: String,
     symbol: String,
     uri: String,
+    use_program_authority: bool,
+    mint_config: CreateMintConfig,
+    additional_metadata: Vec<MetadataEntry>,
 ) -> Result<()> {
     // Validate inputs
     require!(name.len() <= 64, TokenizationWrapError::NameTooLong);
     require!(symbol.len() <= 32, TokenizationWrapError::SymbolTooLong);
     require!(uri.len() <= 512, TokenizationWrapError::UriTooLong);
+    mint_config.validate()?;
+    validate_additional_metadata(&additional_metadata)?;
 
     let wrapped_mint_state = &mut ctx.accounts.wrapped_mint_state;
     let wrapped_mint_state_key = wrapped_mint_state.key();
@@ -37,14 +60,32 @@ pub fn create_mint(
     let unwrapped_mint_vault = &ctx.accounts.unwrapped_mint_vault;
     let unwrapped_mint_vault_key = unwrapped_mint_vault.key();
 
+    // underlying extension policy of the program config, every risk the unwrapped mint
+    // carries must be allowed, the allowed ones are recorded in the wrapped mint state
+    let allowed_underlying_risks = ctx.accounts.program_config.allowed_underlying_risks;
+    let underlying_risk_flags = underlying_risk_profile(&unwrapped_mint.to_account_info())?.risk_flags;
+    require!(
+        underlying_risk_flags & !allowed_underlying_risks == 0,
+        TokenizationWrapError::UnderlyingRiskNotAllowed
+    );
+
     let wrapped_mint_owner = &ctx.accounts.wrapped_mint_owner;
     let wrapped_mint_owner_key = wrapped_mint_owner.key();
+    let wrapped_mint_authority = &ctx.accounts.wrapped_mint_authority;
     let rent = &ctx.accounts.rent;
     let wrapped_mint = &ctx.accounts.wrapped_mint;
     let wrapped_mint_key = wrapped_mint.key();
     let token_program = &ctx.accounts.token_program;
     let system_program = &ctx.accounts.system_program;
 
+    // extension authorities are held either by the owner key or by the wrapped mint authority PDA
+    let (authority_mode, extension_authority) = if use_program_authority {
+        (MintAuthorityMode::Program, wrapped_mint_authority.to_account_info())
+    } else {
+        (MintAuthorityMode::Owner, wrapped_mint_owner.to_account_info())
+    };
+    let extension_authority_key = extension_authority.key();
+
     // wrapped mint seeds
     let wrapped_mint_bump = ctx.bumps.wrapped_mint;
     let wrapped_mint_seeds: &[&[&[u8]]] = &[&[
@@ -55,17 +96,8 @@ pub fn create_mint(
     ]];
 
     // Initialize Token-2022 mint with extensions
-    // Calculate required extensions
-    let mut extension_types = Vec::new();
-    extension_types.push(ExtensionType::MetadataPointer);
-    extension_types.push(ExtensionType::PermanentDelegate);
-    extension_types.push(ExtensionType::TransferHook);
-    extension_types.push(ExtensionType::DefaultAccountState);
-    extension_types.push(ExtensionType::Pausable);
-    extension_types.push(ExtensionType::MintCloseAuthority);
-    extension_types.push(ExtensionType::ScaledUiAmount);
-    extension_types.push(ExtensionType::ConfidentialTransferMint);
-    // extension_types.push(ExtensionType::TokenMetadata); // ExtensionType::TokenMetadata => unreachable!(),
+    // Calculate required extensions, metadata pointer is always included
+    let extension_types = mint_config.extension_types();
 
     let space = ExtensionType::try_calculate_account_len::<PodMint>(&extension_types)
         .map_err(|_| TokenizationWrapError::InvalidAccountData)?;
@@ -88,7 +120,7 @@ pub fn create_mint(
 
     // Initialize extensions before mint initialization
 
-    // 1. Metadata Pointer Extension
+    // 1. Metadata Pointer Extension, the owner keeps this authority in either mode
     invoke(
         &metadata_pointer::instruction::initialize(
             &spl_token_2022_v9::ID,
@@ -100,79 +132,128 @@ pub fn create_mint(
     )?;
 
     // 2. Permanent Delegate Extension
-    invoke(
-        &initialize_permanent_delegate(
-            &spl_token_2022_v9::ID,
-            &wrapped_mint_key,
-            &wrapped_mint_owner_key,
-        )?,
-        &[wrapped_mint.to_account_info()],
-    )?;
+    if mint_config.permanent_delegate {
+        invoke(
+            &initialize_permanent_delegate(
+                &spl_token_2022_v9::ID,
+                &wrapped_mint_key,
+                &extension_authority_key,
+            )?,
+            &[wrapped_mint.to_account_info()],
+        )?;
+    }
 
     // 3. Transfer Hook Extension
-    invoke(
-        &transfer_hook::instruction::initialize(
-            &spl_token_2022_v9::ID,
-            &wrapped_mint_key,
-            Some(wrapped_mint_owner_key),
-            None,
-        )?,
-        &[wrapped_mint.to_account_info()],
-    )?;
+    if mint_config.transfer_hook {
+        invoke(
+            &transfer_hook::instruction::initialize(
+                &spl_token_2022_v9::ID,
+                &wrapped_mint_key,
+                Some(extension_authority_key),
+                None,
+            )?,
+            &[wrapped_mint.to_account_info()],
+        )?;
+    }
 
     // 4. Default Account State Extension
-    invoke(
-        &default_account_state::instruction::initialize_default_account_state(
-            &spl_token_2022_v9::ID,
-            &wrapped_mint_key,
-            &AccountState::Initialized, // default is initialized
-        )?,
-        &[wrapped_mint.to_account_info()],
-    )?;
+    if mint_config.default_account_state {
+        let default_state = if mint_config.default_frozen {
+            AccountState::Frozen
+        } else {
+            AccountState::Initialized
+        };
+        invoke(
+            &default_account_state::instruction::initialize_default_account_state(
+                &spl_token_2022_v9::ID,
+                &wrapped_mint_key,
+                &default_state,
+            )?,
+            &[wrapped_mint.to_account_info()],
+        )?;
+    }
 
     // 5. Pausable Extension
-    invoke(
-        &pausable::instruction::initialize(
-            &spl_token_2022_v9::ID,
-            &wrapped_mint_key,
-            &wrapped_mint_owner_key,
-        )?,
-        &[wrapped_mint.to_account_info()],
-    )?;
+    if mint_config.pausable {
+        invoke(
+            &pausable::instruction::initialize(
+                &spl_token_2022_v9::ID,
+                &wrapped_mint_key,
+                &extension_authority_key,
+            )?,
+            &[wrapped_mint.to_account_info()],
+        )?;
+    }
 
-    // 6 Mint Close Authority
-    invoke(
-        &initialize_mint_close_authority(
-            &spl_token_2022_v9::ID,
-            &wrapped_mint_key,
-            Some(&wrapped_mint_owner_key),
-        )?,
-        &[wrapped_mint.to_account_info()],
-    )?;
+    // 6 Mint Close Authority, the owner keeps this authority in either mode
+    if mint_config.mint_close_authority {
+        invoke(
+            &initialize_mint_close_authority(
+                &spl_token_2022_v9::ID,
+                &wrapped_mint_key,
+                Some(&wrapped_mint_owner_key),
+            )?,
+            &[wrapped_mint.to_account_info()],
+        )?;
+    }
 
     // 7. Scaled UI Amount Config Extension
-    invoke(
-        &scaled_ui_amount::instruction::initialize(
-            &spl_token_2022_v9::ID,
-            &wrapped_mint_key,
-            Some(wrapped_mint_owner_key),
-            1.0, // default is 1
-        )?,
-        &[wrapped_mint.to_account_info()],
-    )?;
+    if mint_config.scaled_ui_amount {
+        invoke(
+            &scaled_ui_amount::instruction::initialize(
+                &spl_token_2022_v9::ID,
+                &wrapped_mint_key,
+                Some(extension_authority_key),
+                mint_config.scaled_ui_multiplier,
+            )?,
+            &[wrapped_mint.to_account_info()],
+        )?;
+    }
 
     // 8. Confidential Transfer Extension
     // Enables private transactions and specifies an auditor that can decrypt transaction amounts for compliance
-    invoke(
-        &confidential_transfer::instruction::initialize_mint(
-            &spl_token_2022_v9::ID,
-            &wrapped_mint_key,
-            Some(wrapped_mint_owner_key),
-            false, // false , same as xStock's implementation
-            None,  // None, same as xStock's implementation
-        )?,
-        &[wrapped_mint.to_account_info()],
-    )?;
+    if mint_config.confidential_transfer {
+        invoke(
+            &confidential_transfer::instruction::initialize_mint(
+                &spl_token_2022_v9::ID,
+                &wrapped_mint_key,
+                Some(extension_authority_key),
+                mint_config.confidential_auto_approve,
+                mint_config
+                    .confidential_auditor_elgamal_pubkey
+                    .map(PodElGamalPubkey::from),
+            )?,
+            &[wrapped_mint.to_account_info()],
+        )?;
+    }
+
+    // Interest Bearing Extension
+    if mint_config.interest_bearing {
+        invoke(
+            &interest_bearing_mint::instruction::initialize(
+                &spl_token_2022_v9::ID,
+                &wrapped_mint_key,
+                Some(extension_authority_key),
+                mint_config.interest_rate,
+            )?,
+            &[wrapped_mint.to_account_info()],
+        )?;
+    }
+
+    // Transfer Fee Extension, withheld fees can be withdrawn by the extension authority
+    if mint_config.transfer_fee {
+        invoke(
+            &transfer_fee::instruction::initialize_transfer_fee_config(
+                &spl_token_2022_v9::ID,
+                &wrapped_mint_key,
+                Some(&extension_authority_key),
+                Some(&extension_authority_key),
+                mint_config.transfer_fee_basis_points,
+                mint_config.transfer_fee_maximum_fee,
+            )?,
+            &[wrapped_mint.to_account_info()],
+        )?;
+    }
 
     // 9. Initialize the mint itself
     invoke(
@@ -180,7 +261,7 @@ pub fn create_mint(
             &spl_token_2022_v9::ID,
             &wrapped_mint_key,
             &wrapped_mint_key,
-            Some(&wrapped_mint_owner_key), // freeze authority
+            Some(&extension_authority_key), // freeze authority
             unwrapped_mint.decimals,
         )?,
         &[wrapped_mint.to_account_info(), rent.to_account_info()],
@@ -192,7 +273,7 @@ pub fn create_mint(
         mint: wrapped_mint.to_account_info(),
         metadata: wrapped_mint.to_account_info(), // metadata stored in mint account itself
         mint_authority: wrapped_mint.to_account_info(),
-        update_authority: wrapped_mint_owner.to_account_info(),
+        update_authority: extension_authority.clone(),
     };
     let cpi_ctx = CpiContext::new_with_signer(
         token_program.to_account_info(),
@@ -202,25 +283,35 @@ pub fn create_mint(
 
     token_metadata_initialize(cpi_ctx, name.clone(), symbol.clone(), uri.clone())?;
 
-    // wrapped mint lamports truly need
-    let wrapped_mint_lamports_needed = Rent::get()?.minimum_balance(wrapped_mint.data_len());
-    let wrapped_mint_lamports_truly = wrapped_mint.lamports();
-    if wrapped_mint_lamports_needed > wrapped_mint_lamports_truly {
-        let extra_lamports = wrapped_mint_lamports_needed - wrapped_mint_lamports_truly;
-        invoke(
-            &solana_program::system_instruction::transfer(
-                &wrapped_mint_owner_key,
+    // 11. Additional metadata, each field reallocates the mint account and is
+    // signed by the extension authority, the owner key or the wrapped mint authority PDA
+    let wrapped_mint_authority_bump = ctx.bumps.wrapped_mint_authority;
+    let wrapped_mint_authority_seeds: &[&[&[u8]]] = &[&[
+        WRAPPED_MINT_AUTHORITY_SEED,
+        wrapped_mint_key.as_ref(),
+        &[wrapped_mint_authority_bump],
+    ]];
+    for entry in additional_metadata.iter() {
+        invoke_signed(
+            &spl_token_metadata_interface::instruction::update_field(
+                &spl_token_2022_v9::ID,
                 &wrapped_mint_key,
-                extra_lamports,
+                &extension_authority_key,
+                Field::Key(entry.key.clone()),
+                entry.value.clone(),
             ),
-            &[
-                wrapped_mint_owner.to_account_info(),
-                wrapped_mint.to_account_info(),
-                system_program.to_account_info(),
-            ],
+            &[wrapped_mint.to_account_info(), extension_authority.clone()],
+            wrapped_mint_authority_seeds,
         )?;
     }
 
+    // wrapped mint lamports truly need
+    top_up_rent(
+        &wrapped_mint_owner.to_account_info(),
+        &wrapped_mint.to_account_info(),
+        &system_program.to_account_info(),
+    )?;
+
     // Store backpointer data
     wrapped_mint_state.unwrapped_mint = unwrapped_mint_key;
     wrapped_mint_state.unwrapped_mint_vault = unwrapped_mint_vault_key;
@@ -228,6 +319,19 @@ pub fn create_mint(
     wrapped_mint_state.salt = salt;
     wrapped_mint_state.wrapped_mint_owner = wrapped_mint_owner_key;
     wrapped_mint_state.wrapped_mint_pending_owner = pubkey!("11111111111111111111111111111111");
+    wrapped_mint_state.authority_mode = authority_mode;
+    wrapped_mint_state.paused = false;
+    wrapped_mint_state.access_list_enabled = false;
+    wrapped_mint_state.underlying_risk_flags = underlying_risk_flags;
+    wrapped_mint_state.mint_config = mint_config.clone();
+    wrapped_mint_state.transfer_hook_program = None;
+    wrapped_mint_state.pending_transfer_hook_program = None;
+    wrapped_mint_state.pending_transfer_hook_effective_timestamp = 0;
+    wrapped_mint_state.transfer_hook_timelock = 0;
+    wrapped_mint_state.rate_limit = RateLimitConfig::default();
+    wrapped_mint_state.wrap_window = RateLimitWindow::default();
+    wrapped_mint_state.unwrap_window = RateLimitWindow::default();
+    wrapped_mint_state.version = WRAPPED_MINT_STATE_VERSION;
 
     emit!(CreateMintEvent {
         wrapped_mint_owner: wrapped_mint_owner_key,
@@ -235,6 +339,10 @@ pub fn create_mint(
         wrapped_mint: wrapped_mint_key,
         unwrapped_mint_vault: unwrapped_mint_vault_key,
         wrapped_mint_state: wrapped_mint_state_key,
+        authority_mode: authority_mode,
+        underlying_risk_flags: underlying_risk_flags,
+        mint_config: mint_config,
+        additional_metadata: additional_metadata,
     });
 
     msg!("warp mint owner : {}", wrapped_mint_owner_key);
@@ -253,9 +361,9 @@ pub struct CreateMint<'info> {
     #[account(mut)]
     pub wrapped_mint_owner: Signer<'info>,
 
-    // unwrapped mint to be wrapped
+    // unwrapped mint to be wrapped, either a SPL Token or a Token-2022 mint
     #[account(
-        constraint = (unwrapped_mint.to_account_info().owner == &spl_token_2022_v9::ID) @ TokenizationWrapError::InvalidUnwrappedMint
+        constraint = (unwrapped_mint.to_account_info().owner == unwrapped_token_program.key) @ TokenizationWrapError::InvalidUnwrappedMint
     )]
     pub unwrapped_mint: InterfaceAccount<'info, Mint>,
 
@@ -278,16 +386,33 @@ pub struct CreateMint<'info> {
     )]
     pub wrapped_mint_state: Account<'info, WrappedMintState>,
 
+    /// CHECK: PDA that holds the extension authorities when use_program_authority is set, no data
+    #[account(
+        seeds = [WRAPPED_MINT_AUTHORITY_SEED, &wrapped_mint.key().as_ref()],
+        bump,
+    )]
+    pub wrapped_mint_authority: UncheckedAccount<'info>,
+
+    // underlying risk policy, set by the program config authority
+    #[account(
+        seeds = [PROGRAM_CONFIG_SEED],
+        bump,
+    )]
+    pub program_config: Account<'info, ProgramConfig>,
+
     /// The wrapped mint vault account holding unwrapped tokens
     #[account(
         init_if_needed,
         payer = wrapped_mint_owner,
         associated_token::mint = unwrapped_mint,
         associated_token::authority = wrapped_mint, // wrapped mint is authority, holding unwrapped tokens
-        associated_token::token_program = token_program,
+        associated_token::token_program = unwrapped_token_program,
     )]
     pub unwrapped_mint_vault: InterfaceAccount<'info, TokenAccount>,
 
+    // token program of the unwrapped mint, SPL Token or Token-2022
+    pub unwrapped_token_program: Interface<'info, TokenInterface>,
+    // token program of the wrapped mint, always Token-2022
     pub token_program: Program<'info, Token2022>,
     pub associated_token_program: Program<'info, AssociatedToken>,
     pub rent: Sysvar<'info, Rent>,
@@ -304,6 +429,189 @@ pub struct WrappedMintState {
     pub wrapped_mint_pending_owner: Pubkey,
     pub unwrapped_mint: Pubkey,
     pub unwrapped_mint_vault: Pubkey,
+    pub authority_mode: MintAuthorityMode,
+    pub paused: bool,
+    pub access_list_enabled: bool,
+    // UNDERLYING_RISK_* flags accepted at create_mint
+    pub underlying_risk_flags: u32,
+    // extension profile chosen at create_mint
+    pub mint_config: CreateMintConfig,
+    // program the wrapped mint's transfer hook currently points at
+    pub transfer_hook_program: Option<Pubkey>,
+    // hook change staged by set_transfer_hook while the timelock runs
+    pub pending_transfer_hook_program: Option<Pubkey>,
+    pub pending_transfer_hook_effective_timestamp: i64,
+    // seconds a transfer hook change waits before it can be applied, 0 applies it at once
+    pub transfer_hook_timelock: i64,
+    // wrap / unwrap rate limits and the circuit breaker
+    pub rate_limit: RateLimitConfig,
+    pub wrap_window: RateLimitWindow,
+    pub unwrap_window: RateLimitWindow,
+    // WRAPPED_MINT_STATE_VERSION, bumped by migrate_mint_state
+    pub version: u8,
+}
+
+impl WrappedMintState {
+    // transfer_ownership is waiting for accept_ownership, the owner's authorities are parked
+    pub fn ownership_transfer_pending(&self) -> bool {
+        self.wrapped_mint_pending_owner != pubkey!("11111111111111111111111111111111")
+    }
+}
+
+/// Extensions of the wrapped mint and their initial parameters. The metadata pointer and
+/// token metadata are always included, every other extension is optional and can't be
+/// added after create_mint, instructions that need a missing one fail with InvalidMintConfig.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, InitSpace)]
+pub struct CreateMintConfig {
+    // needed by force_transfer and force_unwrap
+    pub permanent_delegate: bool,
+    // needed by attach_transfer_hook, set_transfer_hook and set_transfer_hook_timelock
+    pub transfer_hook: bool,
+    // needed by pause_mint and resume_mint
+    pub pausable: bool,
+    pub mint_close_authority: bool,
+    // new token accounts start frozen when default_frozen is set, e.g. KYC gated mints.
+    // wrap mints into the user's own account, so it fails until approve_holder thaws it
+    pub default_account_state: bool,
+    pub default_frozen: bool,
+    pub scaled_ui_amount: bool,
+    pub scaled_ui_multiplier: f64,
+    // largest factor update_multiplier may scale the multiplier by at once
+    pub scaled_ui_max_change: f64,
+    pub confidential_transfer: bool,
+    pub confidential_auto_approve: bool,
+    pub confidential_auditor_elgamal_pubkey: Option<[u8; 32]>,
+    pub interest_bearing: bool,
+    // basis points
+    pub interest_rate: i16,
+    pub transfer_fee: bool,
+    pub transfer_fee_basis_points: u16,
+    pub transfer_fee_maximum_fee: u64,
+}
+
+impl CreateMintConfig {
+    pub fn validate(&self) -> Result<()> {
+        // Token-2022 rejects a mint with both interest bearing and scaled ui amount,
+        // and a confidential transfer fee mint needs the confidential transfer fee extension
+        require!(
+            !(self.interest_bearing && self.scaled_ui_amount),
+            TokenizationWrapError::InvalidMintConfig
+        );
+        require!(
+            !(self.transfer_fee && self.confidential_transfer),
+            TokenizationWrapError::InvalidMintConfig
+        );
+        require!(
+            !self.default_frozen || self.default_account_state,
+            TokenizationWrapError::InvalidMintConfig
+        );
+        if self.scaled_ui_amount {
+            require!(
+                self.scaled_ui_multiplier.is_finite() && self.scaled_ui_multiplier > 0.0,
+                TokenizationWrapError::InvalidMintConfig
+            );
+            require!(
+                self.scaled_ui_max_change.is_finite() && self.scaled_ui_max_change >= 1.0,
+                TokenizationWrapError::InvalidMintConfig
+            );
+        }
+        if self.transfer_fee {
+            require!(
+                self.transfer_fee_basis_points <= transfer_fee::MAX_FEE_BASIS_POINTS,
+                TokenizationWrapError::InvalidMintConfig
+            );
+        }
+        return Ok(());
+    }
+
+    pub fn extension_types(&self) -> Vec<ExtensionType> {
+        let mut extension_types = Vec::new();
+        extension_types.push(ExtensionType::MetadataPointer);
+        if self.permanent_delegate {
+            extension_types.push(ExtensionType::PermanentDelegate);
+        }
+        if self.transfer_hook {
+            extension_types.push(ExtensionType::TransferHook);
+        }
+        if self.default_account_state {
+            extension_types.push(ExtensionType::DefaultAccountState);
+        }
+        if self.pausable {
+            extension_types.push(ExtensionType::Pausable);
+        }
+        if self.mint_close_authority {
+            extension_types.push(ExtensionType::MintCloseAuthority);
+        }
+        if self.scaled_ui_amount {
+            extension_types.push(ExtensionType::ScaledUiAmount);
+        }
+        if self.confidential_transfer {
+            extension_types.push(ExtensionType::ConfidentialTransferMint);
+        }
+        if self.interest_bearing {
+            extension_types.push(ExtensionType::InterestBearingConfig);
+        }
+        if self.transfer_fee {
+            extension_types.push(ExtensionType::TransferFeeConfig);
+        }
+        // extension_types.push(ExtensionType::TokenMetadata); // ExtensionType::TokenMetadata => unreachable!(),
+        return extension_types;
+    }
+
+    /// Reads the extension profile back from an existing wrapped mint, for mints created
+    /// before the profile was recorded. The multiplier bound isn't stored on the mint and
+    /// starts at 1.0, set_multiplier_bound raises it.
+    pub fn from_mint(wrapped_mint: &AccountInfo) -> Result<Self> {
+        let wrapped_mint_data = wrapped_mint.try_borrow_data()?;
+        let mint_state = StateWithExtensions::<MintState>::unpack(&wrapped_mint_data)
+            .map_err(|_| TokenizationWrapError::InvalidWrappedMint)?;
+
+        let default_account_state = mint_state.get_extension::<DefaultAccountState>().ok();
+        let scaled_ui_amount = mint_state.get_extension::<ScaledUiAmountConfig>().ok();
+        let confidential_transfer = mint_state.get_extension::<ConfidentialTransferMint>().ok();
+        let interest_bearing = mint_state.get_extension::<InterestBearingConfig>().ok();
+        let transfer_fee = mint_state.get_extension::<TransferFeeConfig>().ok();
+
+        let confidential_auditor_elgamal_pubkey = confidential_transfer
+            .and_then(|extension| Option::<PodElGamalPubkey>::from(extension.auditor_elgamal_pubkey))
+            .map(|auditor| {
+                let mut auditor_bytes = [0u8; 32];
+                auditor_bytes.copy_from_slice(pod_bytes_of(&auditor));
+                auditor_bytes
+            });
+
+        return Ok(CreateMintConfig {
+            permanent_delegate: mint_state.get_extension::<PermanentDelegate>().is_ok(),
+            transfer_hook: mint_state.get_extension::<TransferHook>().is_ok(),
+            pausable: mint_state.get_extension::<PausableConfig>().is_ok(),
+            mint_close_authority: mint_state.get_extension::<MintCloseAuthority>().is_ok(),
+            default_account_state: default_account_state.is_some(),
+            default_frozen: default_account_state
+                .map(|extension| extension.state == AccountState::Frozen as u8)
+                .unwrap_or(false),
+            scaled_ui_amount: scaled_ui_amount.is_some(),
+            scaled_ui_multiplier: scaled_ui_amount
+                .map(|extension| f64::from(extension.multiplier))
+                .unwrap_or(1.0),
+            scaled_ui_max_change: 1.0,
+            confidential_transfer: confidential_transfer.is_some(),
+            confidential_auto_approve: confidential_transfer
+                .map(|extension| bool::from(extension.auto_approve_new_accounts))
+                .unwrap_or(false),
+            confidential_auditor_elgamal_pubkey: confidential_auditor_elgamal_pubkey,
+            interest_bearing: interest_bearing.is_some(),
+            interest_rate: interest_bearing
+                .map(|extension| i16::from(extension.current_rate))
+                .unwrap_or(0),
+            transfer_fee: transfer_fee.is_some(),
+            transfer_fee_basis_points: transfer_fee
+                .map(|extension| u16::from(extension.newer_transfer_fee.transfer_fee_basis_points))
+                .unwrap_or(0),
+            transfer_fee_maximum_fee: transfer_fee
+                .map(|extension| u64::from(extension.newer_transfer_fee.maximum_fee))
+                .unwrap_or(0),
+        });
+    }
 }
 
 #[event]
@@ -313,4 +621,8 @@ pub struct CreateMintEvent {
     pub wrapped_mint: Pubkey,
     pub unwrapped_mint_vault: Pubkey,
     pub wrapped_mint_state: Pubkey,
+    pub authority_mode: MintAuthorityMode,
+    pub underlying_risk_flags: u32,
+    pub mint_config: CreateMintConfig,
+    pub additional_metadata: Vec<MetadataEntry>,
 }
@@ -0,0 +1,186 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::*;
+use crate::errors::TokenizationWrapError;
+use crate::instructions::authority::MintAuthorityMode;
+use crate::instructions::create_mint::WrappedMintState;
+use crate::instructions::role::{RoleKind, RoleState};
+use anchor_lang::solana_program::program::invoke_signed;
+use anchor_spl::token_interface::{Mint, Token2022, TokenAccount};
+use spl_token_2022_v9::{
+    instruction::{freeze_account, thaw_account},
+    onchain::invoke_transfer_checked,
+};
+
+/// Moves wrapped tokens between holders with the permanent delegate, for regulator
+/// mandated seizures and lost wallet recovery. A frozen source account is thawed for
+/// the transfer and frozen again afterwards. `remaining_accounts` carry the extra
+/// accounts of the wrapped mint's transfer hook, if any.
+pub fn force_transfer<'info>(
+    ctx: Context<'_, '_, 'info, 'info, ForceTransfer<'info>>,
+    amount: u64,
+    reason_code: u16,
+    case_reference: [u8; 32],
+) -> Result<()> {
+    require!(amount > 0, TokenizationWrapError::InsufficientBalance);
+
+    let wrapped_mint = &ctx.accounts.wrapped_mint;
+    let wrapped_mint_key = wrapped_mint.key();
+    let wrapped_mint_authority = &ctx.accounts.wrapped_mint_authority;
+    let from_token_account = &ctx.accounts.from_token_account;
+    let to_token_account = &ctx.accounts.to_token_account;
+    let recoverer_key = ctx.accounts.recoverer.key();
+
+    require!(
+        from_token_account.key() != to_token_account.key(),
+        TokenizationWrapError::InvalidAccountData
+    );
+    require!(
+        from_token_account.amount >= amount,
+        TokenizationWrapError::InsufficientBalance
+    );
+
+    // wrapped mint authority seeds
+    let wrapped_mint_authority_bump = ctx.bumps.wrapped_mint_authority;
+    let wrapped_mint_authority_seeds: &[&[&[u8]]] = &[&[
+        WRAPPED_MINT_AUTHORITY_SEED,
+        wrapped_mint_key.as_ref(),
+        &[wrapped_mint_authority_bump],
+    ]];
+
+    // accounts to thaw and refreeze the source account
+    let freeze_accounts = [
+        from_token_account.to_account_info(),
+        wrapped_mint.to_account_info(),
+        wrapped_mint_authority.to_account_info(),
+    ];
+
+    let source_frozen = from_token_account.is_frozen();
+    if source_frozen {
+        invoke_signed(
+            &thaw_account(
+                &spl_token_2022_v9::ID,
+                &from_token_account.key(),
+                &wrapped_mint_key,
+                &wrapped_mint_authority.key(),
+                &[],
+            )?,
+            &freeze_accounts,
+            wrapped_mint_authority_seeds,
+        )?;
+    }
+
+    invoke_transfer_checked(
+        &spl_token_2022_v9::ID,
+        from_token_account.to_account_info(),
+        wrapped_mint.to_account_info(),
+        to_token_account.to_account_info(),
+        wrapped_mint_authority.to_account_info(),
+        ctx.remaining_accounts,
+        amount,
+        wrapped_mint.decimals,
+        wrapped_mint_authority_seeds,
+    )?;
+
+    if source_frozen {
+        invoke_signed(
+            &freeze_account(
+                &spl_token_2022_v9::ID,
+                &from_token_account.key(),
+                &wrapped_mint_key,
+                &wrapped_mint_authority.key(),
+                &[],
+            )?,
+            &freeze_accounts,
+            wrapped_mint_authority_seeds,
+        )?;
+    }
+
+    emit!(ForceTransferEvent {
+        wrapped_mint: wrapped_mint_key,
+        from: from_token_account.key(),
+        from_owner: from_token_account.owner,
+        to: to_token_account.key(),
+        to_owner: to_token_account.owner,
+        amount: amount,
+        reason_code: reason_code,
+        case_reference: case_reference,
+        recoverer: recoverer_key,
+    });
+
+    msg!("wrapped mint: {:?}", wrapped_mint_key);
+    msg!("force transfer from: {:?}", from_token_account.key());
+    msg!("force transfer to: {:?}", to_token_account.key());
+    msg!("amount: {}", amount);
+    msg!("reason code: {}", reason_code);
+
+    return Ok(());
+}
+
+#[derive(Accounts)]
+pub struct ForceTransfer<'info> {
+    // recoverer
+    pub recoverer: Signer<'info>,
+
+    // The wrapped mint
+    #[account(
+        constraint = (wrapped_mint_state.wrapped_mint == wrapped_mint.key()) @ TokenizationWrapError::InvalidWrappedMint,
+    )]
+    pub wrapped_mint: InterfaceAccount<'info, Mint>,
+
+    // wrapped mint state, the permanent delegate must be the wrapped mint authority PDA
+    #[account(
+        seeds = [WRAPPED_MINT_STATE_SEED, &wrapped_mint.key().as_ref()],
+        bump,
+        constraint = wrapped_mint_state.mint_config.permanent_delegate @ TokenizationWrapError::InvalidMintConfig,
+        constraint = (wrapped_mint_state.authority_mode == MintAuthorityMode::Program) @ TokenizationWrapError::InvalidMintAuthorityMode,
+    )]
+    pub wrapped_mint_state: Account<'info, WrappedMintState>,
+
+    // recoverer role check
+    #[account(
+        seeds=[WRAPPED_ROLE_SEED,&wrapped_mint.key().as_ref(),&[RoleKind::Recoverer.as_u8()],recoverer.key().as_ref()],
+        bump,
+        constraint = recoverer_role.has_role(&recoverer.key(), &[RoleKind::Recoverer]) @ TokenizationWrapError::Unauthorized,
+    )]
+    pub recoverer_role: Account<'info, RoleState>,
+
+    // holder's wrapped token account (source)
+    #[account(
+        mut,
+        token::mint = wrapped_mint,
+        token::token_program = token_program,
+    )]
+    pub from_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    // wrapped token account (destination)
+    #[account(
+        mut,
+        token::mint = wrapped_mint,
+        token::token_program = token_program,
+    )]
+    pub to_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// CHECK: PDA holding the wrapped mint authorities, no data
+    #[account(
+        seeds = [WRAPPED_MINT_AUTHORITY_SEED, &wrapped_mint.key().as_ref()],
+        bump,
+    )]
+    pub wrapped_mint_authority: UncheckedAccount<'info>,
+
+    pub token_program: Program<'info, Token2022>,
+}
+
+#[event]
+pub struct ForceTransferEvent {
+    pub wrapped_mint: Pubkey,
+    pub from: Pubkey,
+    pub from_owner: Pubkey,
+    pub to: Pubkey,
+    pub to_owner: Pubkey,
+    pub amount: u64,
+    pub reason_code: u16,
+    // hash of the off-chain case reference
+    pub case_reference: [u8; 32],
+    pub recoverer: Pubkey,
+}
@@ -0,0 +1,289 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::*;
+use crate::errors::TokenizationWrapError;
+use crate::instructions::access_list::check_access_lists;
+use crate::instructions::authority::MintAuthorityMode;
+use crate::instructions::create_mint::WrappedMintState;
+use crate::instructions::role::{RoleKind, RoleState};
+use anchor_lang::solana_program::program::invoke_signed;
+use anchor_spl::token_interface::{Mint, Token2022, TokenAccount, TokenInterface};
+use spl_token_2022_v9::{
+    instruction::{burn_checked, freeze_account, thaw_account},
+    onchain::invoke_transfer_checked,
+};
+
+/// Redeems a holder's position: burns `amount` wrapped tokens from the holder with the
+/// permanent delegate and releases the same amount of underlying from the vault to
+/// `destination_token_account`, so the supply stays equal to the vault balance. The
+/// destination owner has to pass the access and block lists like any unwrap.
+/// `remaining_accounts` carry the extra accounts of the unwrapped mint's transfer hook, if any.
+///
+/// The rate limits and the circuit breaker don't apply on purpose: force_unwrap is the
+/// salvager's recovery path, e.g. while the breaker has paused the mint, and isn't tied
+/// to a wrapper role whose limits it could use up.
+pub fn force_unwrap<'info>(
+    ctx: Context<'_, '_, 'info, 'info, ForceUnwrap<'info>>,
+    amount: u64,
+) -> Result<()> {
+    require!(amount > 0, TokenizationWrapError::InsufficientBalance);
+
+    let authority_key = ctx.accounts.authority.key();
+    let wrapped_mint_state = &ctx.accounts.wrapped_mint_state;
+
+    // the wrapped mint owner, or a Salvager
+    let is_owner = authority_key == wrapped_mint_state.wrapped_mint_owner;
+    let is_salvager = ctx
+        .accounts
+        .salvager_role
+        .as_ref()
+        .map(|salvager_role| salvager_role.has_role(&authority_key, &[RoleKind::Salvager]))
+        .unwrap_or(false);
+    require!(is_owner || is_salvager, TokenizationWrapError::Unauthorized);
+
+    // access / block list of the destination owner, the underlying leaves the vault to them
+    check_access_lists(
+        wrapped_mint_state,
+        &ctx.accounts.destination_access_list,
+        &ctx.accounts.destination_block_list,
+    )?;
+
+    let unwrapped_mint = &ctx.accounts.unwrapped_mint;
+    let unwrapped_mint_key = unwrapped_mint.key();
+    let wrapped_mint = &ctx.accounts.wrapped_mint;
+    let wrapped_mint_key = wrapped_mint.key();
+    let wrapped_mint_authority = &ctx.accounts.wrapped_mint_authority;
+    let holder_token_account = &ctx.accounts.holder_token_account;
+    let holder_key = holder_token_account.owner;
+    let destination_token_account = &ctx.accounts.destination_token_account;
+    let unwrapped_mint_vault = &ctx.accounts.unwrapped_mint_vault;
+    let unwrapped_token_program = &ctx.accounts.unwrapped_token_program;
+
+    require!(
+        holder_token_account.amount >= amount,
+        TokenizationWrapError::InsufficientBalance
+    );
+
+    // wrapped mint authority seeds
+    let wrapped_mint_authority_bump = ctx.bumps.wrapped_mint_authority;
+    let wrapped_mint_authority_seeds: &[&[&[u8]]] = &[&[
+        WRAPPED_MINT_AUTHORITY_SEED,
+        wrapped_mint_key.as_ref(),
+        &[wrapped_mint_authority_bump],
+    ]];
+
+    // accounts to thaw and refreeze the holder account
+    let freeze_accounts = [
+        holder_token_account.to_account_info(),
+        wrapped_mint.to_account_info(),
+        wrapped_mint_authority.to_account_info(),
+    ];
+
+    let holder_frozen = holder_token_account.is_frozen();
+    if holder_frozen {
+        invoke_signed(
+            &thaw_account(
+                &spl_token_2022_v9::ID,
+                &holder_token_account.key(),
+                &wrapped_mint_key,
+                &wrapped_mint_authority.key(),
+                &[],
+            )?,
+            &freeze_accounts,
+            wrapped_mint_authority_seeds,
+        )?;
+    }
+
+    // Burn wrapped tokens with the permanent delegate
+    invoke_signed(
+        &burn_checked(
+            &spl_token_2022_v9::ID,
+            &holder_token_account.key(),
+            &wrapped_mint_key,
+            &wrapped_mint_authority.key(),
+            &[],
+            amount,
+            wrapped_mint.decimals,
+        )?,
+        &[
+            holder_token_account.to_account_info(),
+            wrapped_mint.to_account_info(),
+            wrapped_mint_authority.to_account_info(),
+        ],
+        wrapped_mint_authority_seeds,
+    )?;
+
+    if holder_frozen {
+        invoke_signed(
+            &freeze_account(
+                &spl_token_2022_v9::ID,
+                &holder_token_account.key(),
+                &wrapped_mint_key,
+                &wrapped_mint_authority.key(),
+                &[],
+            )?,
+            &freeze_accounts,
+            wrapped_mint_authority_seeds,
+        )?;
+    }
+
+    // destination balance before the transfer, an underlying with the TransferFee
+    // extension credits the destination with less than amount
+    let destination_balance_before = destination_token_account.amount;
+
+    // Transfer unwrapped tokens from vault to the destination
+    // wrapped mint seeds
+    let wrapped_mint_bump = ctx.bumps.wrapped_mint;
+    let wrapped_mint_seeds: &[&[&[u8]]] = &[&[
+        WRAPPED_MINT_SEED,
+        unwrapped_mint_key.as_ref(),
+        &wrapped_mint_state.salt,
+        &[wrapped_mint_bump],
+    ]];
+
+    invoke_transfer_checked(
+        unwrapped_token_program.key,
+        unwrapped_mint_vault.to_account_info(),
+        unwrapped_mint.to_account_info(),
+        destination_token_account.to_account_info(),
+        wrapped_mint.to_account_info(),
+        ctx.remaining_accounts,
+        amount,
+        unwrapped_mint.decimals,
+        wrapped_mint_seeds,
+    )?;
+
+    ctx.accounts.destination_token_account.reload()?;
+    let net_amount = ctx.accounts.destination_token_account.amount
+        .checked_sub(destination_balance_before)
+        .ok_or(TokenizationWrapError::InsufficientBalance)?;
+    // the destination can't be credited more than was sent
+    let fee = amount
+        .checked_sub(net_amount)
+        .ok_or(TokenizationWrapError::InvalidAccountData)?;
+
+    emit!(ForceUnwrapEvent {
+        wrapped_mint: wrapped_mint_key,
+        unwrapped_mint: unwrapped_mint_key,
+        holder: holder_key,
+        holder_token_account: ctx.accounts.holder_token_account.key(),
+        destination_token_account: ctx.accounts.destination_token_account.key(),
+        amount: amount,
+        fee: fee,
+        net_amount: net_amount,
+        authority: authority_key,
+    });
+
+    msg!("wrapped mint: {:?}", wrapped_mint_key);
+    msg!("force unwrap holder: {:?}", holder_key);
+    msg!("destination: {:?}", ctx.accounts.destination_token_account.key());
+    msg!("amount: {}", amount);
+    msg!("Transfer fee: {}", fee);
+
+    return Ok(());
+}
+
+#[derive(Accounts)]
+pub struct ForceUnwrap<'info> {
+    // wrapped mint owner or salvager
+    pub authority: Signer<'info>,
+
+    /// The unwrapped mint
+    #[account(
+        constraint = (wrapped_mint_state.unwrapped_mint == unwrapped_mint.key()) @ TokenizationWrapError::InvalidUnwrappedMint
+    )]
+    pub unwrapped_mint: InterfaceAccount<'info, Mint>,
+
+    // The wrapped mint
+    #[account(
+        mut,
+        seeds=[WRAPPED_MINT_SEED,&unwrapped_mint.key().as_ref(),&wrapped_mint_state.salt],
+        bump,
+        constraint = (wrapped_mint_state.wrapped_mint == wrapped_mint.key()) @ TokenizationWrapError::InvalidWrappedMint,
+    )]
+    pub wrapped_mint: InterfaceAccount<'info, Mint>,
+
+    // wrapped mint state, the permanent delegate must be the wrapped mint authority PDA
+    #[account(
+        seeds = [WRAPPED_MINT_STATE_SEED, &wrapped_mint.key().as_ref()],
+        bump,
+        constraint = wrapped_mint_state.mint_config.permanent_delegate @ TokenizationWrapError::InvalidMintConfig,
+        constraint = (wrapped_mint_state.authority_mode == MintAuthorityMode::Program) @ TokenizationWrapError::InvalidMintAuthorityMode,
+    )]
+    pub wrapped_mint_state: Account<'info, WrappedMintState>,
+
+    // salvager role check, not needed when the owner signs
+    #[account(
+        seeds=[WRAPPED_ROLE_SEED,&wrapped_mint.key().as_ref(),&[RoleKind::Salvager.as_u8()],authority.key().as_ref()],
+        bump,
+    )]
+    pub salvager_role: Option<Account<'info, RoleState>>,
+
+    // holder's wrapped token account
+    #[account(
+        mut,
+        token::mint = wrapped_mint,
+        token::token_program = token_program,
+    )]
+    pub holder_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    // unwrapped token account receiving the underlying
+    #[account(
+        mut,
+        token::mint = unwrapped_mint,
+        token::token_program = unwrapped_token_program,
+    )]
+    pub destination_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// CHECK: destination owner's access list PDA, only checked for existence
+    #[account(
+        seeds=[ACCESS_LIST_SEED,&wrapped_mint.key().as_ref(),destination_token_account.owner.as_ref()],
+        bump,
+    )]
+    pub destination_access_list: UncheckedAccount<'info>,
+
+    /// CHECK: destination owner's block list PDA, only checked for existence
+    #[account(
+        seeds=[BLOCK_LIST_SEED,&wrapped_mint.key().as_ref(),destination_token_account.owner.as_ref()],
+        bump,
+    )]
+    pub destination_block_list: UncheckedAccount<'info>,
+
+    /// The wrapped mint vault account holding unwrapped tokens
+    #[account(
+        mut,
+        associated_token::mint = unwrapped_mint,
+        associated_token::authority = wrapped_mint, // wrapped mint is authority, holding unwrapped tokens
+        associated_token::token_program = unwrapped_token_program,
+        constraint = (wrapped_mint_state.unwrapped_mint_vault == unwrapped_mint_vault.key()) @ TokenizationWrapError::InvalidUnwrappedMintVault,
+    )]
+    pub unwrapped_mint_vault: InterfaceAccount<'info, TokenAccount>,
+
+    /// CHECK: PDA holding the wrapped mint authorities, no data
+    #[account(
+        seeds = [WRAPPED_MINT_AUTHORITY_SEED, &wrapped_mint.key().as_ref()],
+        bump,
+    )]
+    pub wrapped_mint_authority: UncheckedAccount<'info>,
+
+    // token program of the unwrapped mint, SPL Token or Token-2022
+    pub unwrapped_token_program: Interface<'info, TokenInterface>,
+    // token program of the wrapped mint, always Token-2022
+    pub token_program: Program<'info, Token2022>,
+}
+
+#[event]
+pub struct ForceUnwrapEvent {
+    pub wrapped_mint: Pubkey,
+    pub unwrapped_mint: Pubkey,
+    pub holder: Pubkey,
+    pub holder_token_account: Pubkey,
+    pub destination_token_account: Pubkey,
+    // gross amount burned and transferred out of the vault
+    pub amount: u64,
+    // transfer fee withheld by the unwrapped mint
+    pub fee: u64,
+    // amount received by the destination
+    pub net_amount: u64,
+    pub authority: Pubkey,
+}
@@ -0,0 +1,205 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::*;
+use crate::errors::TokenizationWrapError;
+use crate::instructions::authority::MintAuthorityMode;
+use crate::instructions::create_mint::WrappedMintState;
+use crate::instructions::role::{RoleKind, RoleState};
+use crate::utils::{close_pda_account, create_pda_account, is_program_account};
+use anchor_lang::solana_program::program::invoke_signed;
+use anchor_spl::token_interface::{Mint, Token2022};
+use spl_token_2022_v9::{
+    extension::StateWithExtensions,
+    instruction::{freeze_account, thaw_account},
+    state::{Account as TokenAccountState, AccountState},
+};
+
+/// Freezes the wrapped token accounts passed through `remaining_accounts` and puts them
+/// under a freeze hold, so `approve_holder` can't thaw them. `remaining_accounts` carry
+/// each token account followed by its freeze hold PDA, the freezer pays the hold's rent.
+pub fn freeze_accounts<'info>(ctx: Context<'_, '_, 'info, 'info, FreezeAccounts<'info>>) -> Result<()> {
+    return set_accounts_frozen(ctx, true);
+}
+
+/// Thaws the wrapped token accounts passed through `remaining_accounts` and releases their
+/// freeze holds, passed the same way as for `freeze_accounts`. The rent goes back to the freezer.
+pub fn thaw_accounts<'info>(ctx: Context<'_, '_, 'info, 'info, FreezeAccounts<'info>>) -> Result<()> {
+    return set_accounts_frozen(ctx, false);
+}
+
+// accounts already in the target state are only put under or released from the hold
+fn set_accounts_frozen<'info>(
+    ctx: Context<'_, '_, 'info, 'info, FreezeAccounts<'info>>,
+    frozen: bool,
+) -> Result<()> {
+    require!(
+        !ctx.remaining_accounts.is_empty() && ctx.remaining_accounts.len().is_multiple_of(2),
+        TokenizationWrapError::InvalidRemainingAccounts
+    );
+
+    let wrapped_mint = &ctx.accounts.wrapped_mint;
+    let wrapped_mint_key = wrapped_mint.key();
+    let wrapped_mint_authority = &ctx.accounts.wrapped_mint_authority;
+    let freezer = &ctx.accounts.freezer;
+    let freezer_key = freezer.key();
+
+    // wrapped mint authority seeds
+    let wrapped_mint_authority_bump = ctx.bumps.wrapped_mint_authority;
+    let wrapped_mint_authority_seeds: &[&[&[u8]]] = &[&[
+        WRAPPED_MINT_AUTHORITY_SEED,
+        wrapped_mint_key.as_ref(),
+        &[wrapped_mint_authority_bump],
+    ]];
+
+    for accounts in ctx.remaining_accounts.chunks(2) {
+        let (token_account, freeze_hold) = (&accounts[0], &accounts[1]);
+        require!(
+            token_account.owner == &spl_token_2022_v9::ID,
+            TokenizationWrapError::InvalidRemainingAccounts
+        );
+        let (holder, is_frozen) = {
+            let token_account_data = token_account.try_borrow_data()?;
+            let token_account_state =
+                StateWithExtensions::<TokenAccountState>::unpack(&token_account_data)
+                    .map_err(|_| TokenizationWrapError::InvalidRemainingAccounts)?;
+            require!(
+                token_account_state.base.mint == wrapped_mint_key,
+                TokenizationWrapError::InvalidRemainingAccounts
+            );
+            (
+                token_account_state.base.owner,
+                token_account_state.base.state == AccountState::Frozen,
+            )
+        };
+
+        let (freeze_hold_key, freeze_hold_bump) = Pubkey::find_program_address(
+            &[FREEZE_HOLD_SEED, token_account.key.as_ref()],
+            &crate::ID,
+        );
+        require_keys_eq!(
+            freeze_hold.key(),
+            freeze_hold_key,
+            TokenizationWrapError::InvalidRemainingAccounts
+        );
+        if frozen && !is_program_account(freeze_hold) {
+            // also covers accounts that are only frozen by the default account state
+            create_pda_account(
+                &freezer.to_account_info(),
+                freeze_hold,
+                &ctx.accounts.system_program.to_account_info(),
+                ANCHOR_DISCRIMINATOR_SIZE + FreezeHoldState::INIT_SPACE,
+                &[&[FREEZE_HOLD_SEED, token_account.key.as_ref(), &[freeze_hold_bump]]],
+            )?;
+            let freeze_hold_state = FreezeHoldState {
+                wrapped_mint: wrapped_mint_key,
+                token_account: token_account.key(),
+                freezer: freezer_key,
+            };
+            freeze_hold_state.try_serialize(&mut &mut freeze_hold.try_borrow_mut_data()?[..])?;
+        } else if !frozen && is_program_account(freeze_hold) {
+            close_pda_account(freeze_hold, &freezer.to_account_info())?;
+        }
+
+        if is_frozen == frozen {
+            continue;
+        }
+
+        let instruction = if frozen {
+            freeze_account(
+                &spl_token_2022_v9::ID,
+                token_account.key,
+                &wrapped_mint_key,
+                &wrapped_mint_authority.key(),
+                &[],
+            )?
+        } else {
+            thaw_account(
+                &spl_token_2022_v9::ID,
+                token_account.key,
+                &wrapped_mint_key,
+                &wrapped_mint_authority.key(),
+                &[],
+            )?
+        };
+        invoke_signed(
+            &instruction,
+            &[
+                token_account.clone(),
+                wrapped_mint.to_account_info(),
+                wrapped_mint_authority.to_account_info(),
+            ],
+            wrapped_mint_authority_seeds,
+        )?;
+
+        emit!(FreezeEvent {
+            wrapped_mint: wrapped_mint_key,
+            token_account: token_account.key(),
+            holder: holder,
+            frozen: frozen,
+            freezer: freezer_key,
+        });
+
+        msg!("token account: {:?}", token_account.key());
+        msg!("frozen: {:?}", frozen);
+    }
+
+    return Ok(());
+}
+
+#[derive(Accounts)]
+pub struct FreezeAccounts<'info> {
+    // freezer, pays for and receives the rent of the freeze holds
+    #[account(mut)]
+    pub freezer: Signer<'info>,
+
+    // The wrapped mint
+    #[account(
+        constraint = (wrapped_mint_state.wrapped_mint == wrapped_mint.key()) @ TokenizationWrapError::InvalidWrappedMint,
+    )]
+    pub wrapped_mint: InterfaceAccount<'info, Mint>,
+
+    // wrapped mint state
+    #[account(
+        seeds = [WRAPPED_MINT_STATE_SEED, &wrapped_mint.key().as_ref()],
+        bump,
+        constraint = (wrapped_mint_state.authority_mode == MintAuthorityMode::Program) @ TokenizationWrapError::InvalidMintAuthorityMode,
+    )]
+    pub wrapped_mint_state: Account<'info, WrappedMintState>,
+
+    // freezer role check
+    #[account(
+        seeds=[WRAPPED_ROLE_SEED,&wrapped_mint.key().as_ref(),&[RoleKind::Freezer.as_u8()],freezer.key().as_ref()],
+        bump,
+        constraint = freezer_role.has_role(&freezer.key(), &[RoleKind::Freezer]) @ TokenizationWrapError::Unauthorized,
+    )]
+    pub freezer_role: Account<'info, RoleState>,
+
+    /// CHECK: PDA holding the wrapped mint authorities, no data
+    #[account(
+        seeds = [WRAPPED_MINT_AUTHORITY_SEED, &wrapped_mint.key().as_ref()],
+        bump,
+    )]
+    pub wrapped_mint_authority: UncheckedAccount<'info>,
+
+    pub token_program: Program<'info, Token2022>,
+    pub system_program: Program<'info, System>,
+}
+
+// one account per token account a freezer froze, seeds = [FREEZE_HOLD_SEED, token account]
+#[account]
+#[derive(InitSpace)]
+pub struct FreezeHoldState {
+    pub wrapped_mint: Pubkey,
+    pub token_account: Pubkey,
+    pub freezer: Pubkey,
+}
+
+#[event]
+pub struct FreezeEvent {
+    pub wrapped_mint: Pubkey,
+    pub token_account: Pubkey,
+    pub holder: Pubkey,
+    // true when frozen, false when thawed
+    pub frozen: bool,
+    pub freezer: Pubkey,
+}
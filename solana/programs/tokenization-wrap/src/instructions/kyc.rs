@@ -0,0 +1,278 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::*;
+use crate::errors::TokenizationWrapError;
+use crate::instructions::authority::MintAuthorityMode;
+use crate::instructions::create_mint::WrappedMintState;
+use crate::instructions::role::{RoleKind, RoleState};
+use crate::utils::is_program_account;
+use anchor_lang::solana_program::program::invoke_signed;
+use anchor_spl::token_interface::{Mint, Token2022, TokenAccount};
+use spl_token_2022_v9::{
+    extension::default_account_state,
+    instruction::{freeze_account, thaw_account},
+    state::AccountState,
+};
+
+/// Switches new wrapped token accounts between starting Frozen (KYC mode) and Initialized,
+/// signed by the freeze authority, the owner key or the wrapped mint authority PDA.
+pub fn set_default_account_state(ctx: Context<SetDefaultAccountState>, frozen: bool) -> Result<()> {
+    let wrapped_mint = &ctx.accounts.wrapped_mint;
+    let wrapped_mint_key = wrapped_mint.key();
+    let wrapped_mint_owner = &ctx.accounts.wrapped_mint_owner;
+    let wrapped_mint_authority = &ctx.accounts.wrapped_mint_authority;
+
+    // wrapped mint authority seeds
+    let wrapped_mint_authority_bump = ctx.bumps.wrapped_mint_authority;
+    let wrapped_mint_authority_seeds: &[&[&[u8]]] = &[&[
+        WRAPPED_MINT_AUTHORITY_SEED,
+        wrapped_mint_key.as_ref(),
+        &[wrapped_mint_authority_bump],
+    ]];
+
+    // freeze authority is the owner key or the wrapped mint authority PDA
+    let (freeze_authority, signer_seeds) = match ctx.accounts.wrapped_mint_state.authority_mode {
+        MintAuthorityMode::Owner => (wrapped_mint_owner.to_account_info(), &[][..]),
+        MintAuthorityMode::Program => (
+            wrapped_mint_authority.to_account_info(),
+            wrapped_mint_authority_seeds,
+        ),
+    };
+
+    let default_state = if frozen {
+        AccountState::Frozen
+    } else {
+        AccountState::Initialized
+    };
+
+    invoke_signed(
+        &default_account_state::instruction::update_default_account_state(
+            &spl_token_2022_v9::ID,
+            &wrapped_mint_key,
+            &freeze_authority.key(),
+            &[],
+            &default_state,
+        )?,
+        &[wrapped_mint.to_account_info(), freeze_authority.clone()],
+        signer_seeds,
+    )?;
+
+    let wrapped_mint_state = &mut ctx.accounts.wrapped_mint_state;
+    wrapped_mint_state.mint_config.default_frozen = frozen;
+
+    emit!(DefaultAccountStateEvent {
+        wrapped_mint: wrapped_mint_key,
+        frozen: frozen,
+    });
+
+    msg!("wrapped mint: {:?}", wrapped_mint_key);
+    msg!("default frozen: {:?}", frozen);
+
+    return Ok(());
+}
+
+/// Thaws a wrapped token account of a KYC approved holder. Accounts a freezer put under a
+/// freeze hold stay frozen until the freezer thaws them.
+pub fn approve_holder(ctx: Context<ManageHolder>) -> Result<()> {
+    require!(
+        !is_program_account(&ctx.accounts.freeze_hold),
+        TokenizationWrapError::FreezeHold
+    );
+
+    let wrapped_mint = &ctx.accounts.wrapped_mint;
+    let wrapped_mint_key = wrapped_mint.key();
+    let wrapped_mint_authority = &ctx.accounts.wrapped_mint_authority;
+    let holder_token_account = &ctx.accounts.holder_token_account;
+    let kyc_approver_key = ctx.accounts.kyc_approver.key();
+
+    // wrapped mint authority seeds
+    let wrapped_mint_authority_bump = ctx.bumps.wrapped_mint_authority;
+    let wrapped_mint_authority_seeds: &[&[&[u8]]] = &[&[
+        WRAPPED_MINT_AUTHORITY_SEED,
+        wrapped_mint_key.as_ref(),
+        &[wrapped_mint_authority_bump],
+    ]];
+
+    invoke_signed(
+        &thaw_account(
+            &spl_token_2022_v9::ID,
+            &holder_token_account.key(),
+            &wrapped_mint_key,
+            &wrapped_mint_authority.key(),
+            &[],
+        )?,
+        &[
+            holder_token_account.to_account_info(),
+            wrapped_mint.to_account_info(),
+            wrapped_mint_authority.to_account_info(),
+        ],
+        wrapped_mint_authority_seeds,
+    )?;
+
+    emit!(HolderApprovedEvent {
+        wrapped_mint: wrapped_mint_key,
+        holder: holder_token_account.owner,
+        token_account: holder_token_account.key(),
+        kyc_approver: kyc_approver_key,
+    });
+
+    msg!("wrapped mint: {:?}", wrapped_mint_key);
+    msg!("holder approved: {:?}", holder_token_account.owner);
+    msg!("token account: {:?}", holder_token_account.key());
+
+    return Ok(());
+}
+
+/// Freezes the wrapped token account of a holder whose KYC approval was revoked.
+pub fn revoke_holder(ctx: Context<ManageHolder>) -> Result<()> {
+    let wrapped_mint = &ctx.accounts.wrapped_mint;
+    let wrapped_mint_key = wrapped_mint.key();
+    let wrapped_mint_authority = &ctx.accounts.wrapped_mint_authority;
+    let holder_token_account = &ctx.accounts.holder_token_account;
+    let kyc_approver_key = ctx.accounts.kyc_approver.key();
+
+    // wrapped mint authority seeds
+    let wrapped_mint_authority_bump = ctx.bumps.wrapped_mint_authority;
+    let wrapped_mint_authority_seeds: &[&[&[u8]]] = &[&[
+        WRAPPED_MINT_AUTHORITY_SEED,
+        wrapped_mint_key.as_ref(),
+        &[wrapped_mint_authority_bump],
+    ]];
+
+    invoke_signed(
+        &freeze_account(
+            &spl_token_2022_v9::ID,
+            &holder_token_account.key(),
+            &wrapped_mint_key,
+            &wrapped_mint_authority.key(),
+            &[],
+        )?,
+        &[
+            holder_token_account.to_account_info(),
+            wrapped_mint.to_account_info(),
+            wrapped_mint_authority.to_account_info(),
+        ],
+        wrapped_mint_authority_seeds,
+    )?;
+
+    emit!(HolderRevokedEvent {
+        wrapped_mint: wrapped_mint_key,
+        holder: holder_token_account.owner,
+        token_account: holder_token_account.key(),
+        kyc_approver: kyc_approver_key,
+    });
+
+    msg!("wrapped mint: {:?}", wrapped_mint_key);
+    msg!("holder revoked: {:?}", holder_token_account.owner);
+    msg!("token account: {:?}", holder_token_account.key());
+
+    return Ok(());
+}
+
+#[derive(Accounts)]
+pub struct SetDefaultAccountState<'info> {
+    // wrapped mint owner
+    #[account(
+        address = wrapped_mint_state.wrapped_mint_owner @TokenizationWrapError::NotOwner,
+    )]
+    pub wrapped_mint_owner: Signer<'info>,
+
+    // The wrapped mint
+    #[account(
+        mut,
+        constraint = (wrapped_mint_state.wrapped_mint == wrapped_mint.key()) @ TokenizationWrapError::InvalidWrappedMint,
+    )]
+    pub wrapped_mint: InterfaceAccount<'info, Mint>,
+
+    // wrapped mint state, the mint must have been created with the default account state extension
+    #[account(
+        mut,
+        seeds = [WRAPPED_MINT_STATE_SEED, &wrapped_mint.key().as_ref()],
+        bump,
+        constraint = wrapped_mint_state.mint_config.default_account_state @ TokenizationWrapError::InvalidMintConfig,
+    )]
+    pub wrapped_mint_state: Account<'info, WrappedMintState>,
+
+    /// CHECK: PDA holding the wrapped mint authorities, no data
+    #[account(
+        seeds = [WRAPPED_MINT_AUTHORITY_SEED, &wrapped_mint.key().as_ref()],
+        bump,
+    )]
+    pub wrapped_mint_authority: UncheckedAccount<'info>,
+
+    pub token_program: Program<'info, Token2022>,
+}
+
+#[derive(Accounts)]
+pub struct ManageHolder<'info> {
+    // kyc approver
+    pub kyc_approver: Signer<'info>,
+
+    // The wrapped mint
+    #[account(
+        constraint = (wrapped_mint_state.wrapped_mint == wrapped_mint.key()) @ TokenizationWrapError::InvalidWrappedMint,
+    )]
+    pub wrapped_mint: InterfaceAccount<'info, Mint>,
+
+    // wrapped mint state
+    #[account(
+        seeds = [WRAPPED_MINT_STATE_SEED, &wrapped_mint.key().as_ref()],
+        bump,
+        constraint = (wrapped_mint_state.authority_mode == MintAuthorityMode::Program) @ TokenizationWrapError::InvalidMintAuthorityMode,
+    )]
+    pub wrapped_mint_state: Account<'info, WrappedMintState>,
+
+    // kyc approver role check
+    #[account(
+        seeds=[WRAPPED_ROLE_SEED,&wrapped_mint.key().as_ref(),&[RoleKind::KycApprover.as_u8()],kyc_approver.key().as_ref()],
+        bump,
+        constraint = kyc_approver_role.has_role(&kyc_approver.key(), &[RoleKind::KycApprover]) @ TokenizationWrapError::Unauthorized,
+    )]
+    pub kyc_approver_role: Account<'info, RoleState>,
+
+    // holder's wrapped token account
+    #[account(
+        mut,
+        token::mint = wrapped_mint,
+        token::token_program = token_program,
+    )]
+    pub holder_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// CHECK: freeze hold of the holder's token account, only checked for existence
+    #[account(
+        seeds = [FREEZE_HOLD_SEED, holder_token_account.key().as_ref()],
+        bump,
+    )]
+    pub freeze_hold: UncheckedAccount<'info>,
+
+    /// CHECK: PDA holding the wrapped mint authorities, no data
+    #[account(
+        seeds = [WRAPPED_MINT_AUTHORITY_SEED, &wrapped_mint.key().as_ref()],
+        bump,
+    )]
+    pub wrapped_mint_authority: UncheckedAccount<'info>,
+
+    pub token_program: Program<'info, Token2022>,
+}
+
+#[event]
+pub struct DefaultAccountStateEvent {
+    pub wrapped_mint: Pubkey,
+    pub frozen: bool,
+}
+
+#[event]
+pub struct HolderApprovedEvent {
+    pub wrapped_mint: Pubkey,
+    pub holder: Pubkey,
+    pub token_account: Pubkey,
+    pub kyc_approver: Pubkey,
+}
+
+#[event]
+pub struct HolderRevokedEvent {
+    pub wrapped_mint: Pubkey,
+    pub holder: Pubkey,
+    pub token_account: Pubkey,
+    pub kyc_approver: Pubkey,
+}
@@ -2,28 +2,95 @@ use anchor_lang::prelude::*;
 
 use crate::constants::*;
 use crate::errors::TokenizationWrapError;
+use crate::instructions::authority::{rotate_mint_authorities, MintAuthorityKind};
 use crate::instructions::create_mint::WrappedMintState;
-use anchor_spl::token_interface::Mint;
+use anchor_spl::token_interface::{Mint, Token2022};
 
+/// Starts a two-step ownership transfer. The Token-2022 authorities the owner key holds,
+/// all of them in `MintAuthorityMode::Owner` and the metadata pointer and mint close
+/// authorities in `MintAuthorityMode::Program`, are parked in the wrapped mint authority PDA right away,
+/// so `accept_ownership` can hand them to the new owner without the old key. While the
+/// transfer is pending those powers can't be used with the owner key, transfer to the
+/// current owner and accept to call it off.
 pub fn transfer_ownership(ctx: Context<TransferOwnerShip>, new_owner: Pubkey) -> Result<()> {
+    let wrapped_mint = &ctx.accounts.wrapped_mint;
+    let wrapped_mint_key = wrapped_mint.key();
+    let wrapped_mint_owner = &ctx.accounts.wrapped_mint_owner;
+    let wrapped_mint_authority_key = ctx.accounts.wrapped_mint_authority.key();
+    let token_program = &ctx.accounts.token_program;
+
+    let parked_authorities = rotate_mint_authorities(
+        &wrapped_mint.to_account_info(),
+        &wrapped_mint_owner.to_account_info(),
+        &wrapped_mint_authority_key,
+        &token_program.to_account_info(),
+        &[],
+        |_| true,
+    )?;
+
     let wrapped_mint_state = &mut ctx.accounts.wrapped_mint_state;
     wrapped_mint_state.wrapped_mint_pending_owner = new_owner;
 
+    emit!(OwnershipTransferStartedEvent {
+        wrapped_mint: wrapped_mint_key,
+        owner: wrapped_mint_owner.key(),
+        pending_owner: new_owner,
+        parked_authorities: parked_authorities.clone(),
+    });
+
     msg!(
         "Pending owner: {}",
         wrapped_mint_state.wrapped_mint_pending_owner
     );
+    msg!("Parked authorities: {:?}", parked_authorities);
 
     return Ok(());
 }
 
+/// Accepts a pending ownership transfer and hands the authorities parked by
+/// `transfer_ownership` from the wrapped mint authority PDA to the new owner, so a
+/// custody key rotation never leaves the old key in control of the mint and doesn't
+/// need the old key to complete.
 pub fn accept_ownership(ctx: Context<AcceptOwnerShip>) -> Result<()> {
-    let wrapped_mint_state = &mut ctx.accounts.wrapped_mint_state;
+    let wrapped_mint = &ctx.accounts.wrapped_mint;
+    let wrapped_mint_key = wrapped_mint.key();
+    let wrapped_mint_authority = &ctx.accounts.wrapped_mint_authority;
     let wrapped_mint_pending_owner_key = ctx.accounts.wrapped_mint_pending_owner.key();
+    let token_program = &ctx.accounts.token_program;
+
+    // wrapped mint authority seeds
+    let wrapped_mint_authority_bump = ctx.bumps.wrapped_mint_authority;
+    let wrapped_mint_authority_seeds: &[&[&[u8]]] = &[&[
+        WRAPPED_MINT_AUTHORITY_SEED,
+        wrapped_mint_key.as_ref(),
+        &[wrapped_mint_authority_bump],
+    ]];
+
+    // in program mode the PDA keeps the authorities it uses for the role-gated instructions
+    let authority_mode = ctx.accounts.wrapped_mint_state.authority_mode;
+    let rotated_authorities = rotate_mint_authorities(
+        &wrapped_mint.to_account_info(),
+        &wrapped_mint_authority.to_account_info(),
+        &wrapped_mint_pending_owner_key,
+        &token_program.to_account_info(),
+        wrapped_mint_authority_seeds,
+        |authority_kind| authority_mode.owner_holds(authority_kind),
+    )?;
+
+    let wrapped_mint_state = &mut ctx.accounts.wrapped_mint_state;
+    let wrapped_mint_owner_key = wrapped_mint_state.wrapped_mint_owner;
     wrapped_mint_state.wrapped_mint_owner = wrapped_mint_pending_owner_key;
     wrapped_mint_state.wrapped_mint_pending_owner = pubkey!("11111111111111111111111111111111");
 
+    emit!(RotateMintAuthoritiesEvent {
+        wrapped_mint: wrapped_mint_key,
+        previous_owner: wrapped_mint_owner_key,
+        new_owner: wrapped_mint_pending_owner_key,
+        rotated_authorities: rotated_authorities.clone(),
+    });
+
     msg!("New owner: {}", wrapped_mint_state.wrapped_mint_owner);
+    msg!("Rotated authorities: {:?}", rotated_authorities);
 
     return Ok(());
 }
@@ -39,6 +106,7 @@ pub struct TransferOwnerShip<'info> {
 
     // unwrapped mint to be wrapped
     #[account(
+        mut,
         constraint = (wrapped_mint_state.wrapped_mint == wrapped_mint.key()) @ TokenizationWrapError::InvalidWrappedMint,
     )]
     pub wrapped_mint: InterfaceAccount<'info, Mint>,
@@ -50,6 +118,15 @@ pub struct TransferOwnerShip<'info> {
         bump,
     )]
     pub wrapped_mint_state: Account<'info, WrappedMintState>,
+
+    /// CHECK: PDA holding the parked authorities until the transfer is accepted, no data
+    #[account(
+        seeds = [WRAPPED_MINT_AUTHORITY_SEED, &wrapped_mint.key().as_ref()],
+        bump,
+    )]
+    pub wrapped_mint_authority: UncheckedAccount<'info>,
+
+    pub token_program: Program<'info, Token2022>,
 }
 
 #[derive(Accounts)]
@@ -62,6 +139,7 @@ pub struct AcceptOwnerShip<'info> {
 
     // unwrapped mint to be wrapped
     #[account(
+        mut,
         constraint = (wrapped_mint_state.wrapped_mint == wrapped_mint.key()) @ TokenizationWrapError::InvalidWrappedMint,
     )]
     pub wrapped_mint: InterfaceAccount<'info, Mint>,
@@ -73,4 +151,30 @@ pub struct AcceptOwnerShip<'info> {
         bump,
     )]
     pub wrapped_mint_state: Account<'info, WrappedMintState>,
+
+    /// CHECK: PDA holding the parked authorities, no data
+    #[account(
+        seeds = [WRAPPED_MINT_AUTHORITY_SEED, &wrapped_mint.key().as_ref()],
+        bump,
+    )]
+    pub wrapped_mint_authority: UncheckedAccount<'info>,
+
+    pub token_program: Program<'info, Token2022>,
+}
+
+#[event]
+pub struct OwnershipTransferStartedEvent {
+    pub wrapped_mint: Pubkey,
+    pub owner: Pubkey,
+    pub pending_owner: Pubkey,
+    // authorities moved from the owner key to the wrapped mint authority PDA
+    pub parked_authorities: Vec<MintAuthorityKind>,
+}
+
+#[event]
+pub struct RotateMintAuthoritiesEvent {
+    pub wrapped_mint: Pubkey,
+    pub previous_owner: Pubkey,
+    pub new_owner: Pubkey,
+    pub rotated_authorities: Vec<MintAuthorityKind>,
 }
@@ -0,0 +1,297 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::*;
+use crate::errors::TokenizationWrapError;
+use crate::instructions::authority::MintAuthorityMode;
+use crate::instructions::create_mint::WrappedMintState;
+use crate::instructions::role::{RoleKind, RoleState};
+use crate::utils::top_up_rent;
+use anchor_lang::solana_program::program::invoke_signed;
+use anchor_spl::token_interface::spl_pod::optional_keys::OptionalNonZeroPubkey;
+use anchor_spl::token_interface::{Mint, Token2022};
+use spl_token_metadata_interface::{instruction as metadata_instruction, state::Field};
+
+/// Sets a token metadata field of the wrapped mint. `name`, `symbol` and `uri` update the
+/// base fields, any other key is stored as an additional key/value pair. The metadata
+/// manager pays for the extra rent when the mint account grows.
+pub fn update_metadata_field(ctx: Context<UpdateMetadata>, field: String, value: String) -> Result<()> {
+    let wrapped_mint = &ctx.accounts.wrapped_mint;
+    let wrapped_mint_key = wrapped_mint.key();
+    let wrapped_mint_authority = &ctx.accounts.wrapped_mint_authority;
+    let metadata_manager = &ctx.accounts.metadata_manager;
+    validate_metadata_field(&field, &value)?;
+
+    // wrapped mint authority seeds
+    let wrapped_mint_authority_bump = ctx.bumps.wrapped_mint_authority;
+    let wrapped_mint_authority_seeds: &[&[&[u8]]] = &[&[
+        WRAPPED_MINT_AUTHORITY_SEED,
+        wrapped_mint_key.as_ref(),
+        &[wrapped_mint_authority_bump],
+    ]];
+
+    let metadata_field = match field.as_str() {
+        "name" => Field::Name,
+        "symbol" => Field::Symbol,
+        "uri" => Field::Uri,
+        _ => Field::Key(field.clone()),
+    };
+
+    invoke_signed(
+        &metadata_instruction::update_field(
+            &spl_token_2022_v9::ID,
+            &wrapped_mint_key,
+            &wrapped_mint_authority.key(),
+            metadata_field,
+            value.clone(),
+        ),
+        &[
+            wrapped_mint.to_account_info(),
+            wrapped_mint_authority.to_account_info(),
+        ],
+        wrapped_mint_authority_seeds,
+    )?;
+
+    // the mint account grows with the metadata
+    top_up_rent(
+        &metadata_manager.to_account_info(),
+        &wrapped_mint.to_account_info(),
+        &ctx.accounts.system_program.to_account_info(),
+    )?;
+
+    emit!(MetadataFieldUpdatedEvent {
+        wrapped_mint: wrapped_mint_key,
+        field: field.clone(),
+        value: value.clone(),
+        metadata_manager: metadata_manager.key(),
+    });
+
+    msg!("wrapped mint: {:?}", wrapped_mint_key);
+    msg!("metadata field: {}", field);
+    msg!("metadata value: {}", value);
+
+    return Ok(());
+}
+
+/// Removes an additional key/value pair from the wrapped mint token metadata. With
+/// `idempotent` a missing key is not an error.
+pub fn remove_metadata_key(ctx: Context<UpdateMetadata>, key: String, idempotent: bool) -> Result<()> {
+    let wrapped_mint = &ctx.accounts.wrapped_mint;
+    let wrapped_mint_key = wrapped_mint.key();
+    let wrapped_mint_authority = &ctx.accounts.wrapped_mint_authority;
+
+    // wrapped mint authority seeds
+    let wrapped_mint_authority_bump = ctx.bumps.wrapped_mint_authority;
+    let wrapped_mint_authority_seeds: &[&[&[u8]]] = &[&[
+        WRAPPED_MINT_AUTHORITY_SEED,
+        wrapped_mint_key.as_ref(),
+        &[wrapped_mint_authority_bump],
+    ]];
+
+    invoke_signed(
+        &metadata_instruction::remove_key(
+            &spl_token_2022_v9::ID,
+            &wrapped_mint_key,
+            &wrapped_mint_authority.key(),
+            key.clone(),
+            idempotent,
+        ),
+        &[
+            wrapped_mint.to_account_info(),
+            wrapped_mint_authority.to_account_info(),
+        ],
+        wrapped_mint_authority_seeds,
+    )?;
+
+    emit!(MetadataKeyRemovedEvent {
+        wrapped_mint: wrapped_mint_key,
+        key: key.clone(),
+        metadata_manager: ctx.accounts.metadata_manager.key(),
+    });
+
+    msg!("wrapped mint: {:?}", wrapped_mint_key);
+    msg!("metadata key removed: {}", key);
+
+    return Ok(());
+}
+
+/// Hands the token metadata update authority from the wrapped mint authority PDA to
+/// `new_authority`. `None` makes the metadata immutable. Either way the metadata manager
+/// role can no longer update the metadata afterwards.
+pub fn update_metadata_authority(
+    ctx: Context<UpdateMetadata>,
+    new_authority: Option<Pubkey>,
+) -> Result<()> {
+    let wrapped_mint = &ctx.accounts.wrapped_mint;
+    let wrapped_mint_key = wrapped_mint.key();
+    let wrapped_mint_authority = &ctx.accounts.wrapped_mint_authority;
+
+    // wrapped mint authority seeds
+    let wrapped_mint_authority_bump = ctx.bumps.wrapped_mint_authority;
+    let wrapped_mint_authority_seeds: &[&[&[u8]]] = &[&[
+        WRAPPED_MINT_AUTHORITY_SEED,
+        wrapped_mint_key.as_ref(),
+        &[wrapped_mint_authority_bump],
+    ]];
+
+    let new_update_authority = match new_authority {
+        Some(new_authority) => {
+            require!(
+                new_authority != Pubkey::default(),
+                TokenizationWrapError::InvalidAccountData
+            );
+            OptionalNonZeroPubkey(new_authority)
+        }
+        None => OptionalNonZeroPubkey::default(),
+    };
+
+    invoke_signed(
+        &metadata_instruction::update_authority(
+            &spl_token_2022_v9::ID,
+            &wrapped_mint_key,
+            &wrapped_mint_authority.key(),
+            new_update_authority,
+        ),
+        &[
+            wrapped_mint.to_account_info(),
+            wrapped_mint_authority.to_account_info(),
+        ],
+        wrapped_mint_authority_seeds,
+    )?;
+
+    emit!(MetadataAuthorityUpdatedEvent {
+        wrapped_mint: wrapped_mint_key,
+        old_authority: wrapped_mint_authority.key(),
+        new_authority: new_authority,
+        metadata_manager: ctx.accounts.metadata_manager.key(),
+    });
+
+    msg!("wrapped mint: {:?}", wrapped_mint_key);
+    msg!("metadata update authority: {:?}", new_authority);
+
+    return Ok(());
+}
+
+/// Checks a token metadata field against the length limits of create_mint.
+pub fn validate_metadata_field(field: &str, value: &str) -> Result<()> {
+    match field {
+        "name" => require!(value.len() <= 64, TokenizationWrapError::NameTooLong),
+        "symbol" => require!(value.len() <= 32, TokenizationWrapError::SymbolTooLong),
+        "uri" => require!(value.len() <= 512, TokenizationWrapError::UriTooLong),
+        key => {
+            require!(!key.is_empty(), TokenizationWrapError::InvalidMetadataKey);
+            require!(
+                key.len() <= MAX_METADATA_KEY_LEN,
+                TokenizationWrapError::MetadataKeyTooLong
+            );
+            require!(
+                value.len() <= MAX_METADATA_VALUE_LEN,
+                TokenizationWrapError::MetadataValueTooLong
+            );
+        }
+    }
+
+    return Ok(());
+}
+
+/// Checks the additional key/value pairs passed to create_mint: at most
+/// `MAX_ADDITIONAL_METADATA` unique keys, none of them a base field, with at most
+/// `MAX_ADDITIONAL_METADATA_SIZE` key and value bytes in total.
+pub fn validate_additional_metadata(additional_metadata: &[MetadataEntry]) -> Result<()> {
+    require!(
+        additional_metadata.len() <= MAX_ADDITIONAL_METADATA,
+        TokenizationWrapError::TooManyMetadataFields
+    );
+    let metadata_size: usize = additional_metadata
+        .iter()
+        .map(|entry| entry.key.len() + entry.value.len())
+        .sum();
+    require!(
+        metadata_size <= MAX_ADDITIONAL_METADATA_SIZE,
+        TokenizationWrapError::AdditionalMetadataTooLarge
+    );
+    for (index, entry) in additional_metadata.iter().enumerate() {
+        require!(
+            !matches!(entry.key.as_str(), "name" | "symbol" | "uri"),
+            TokenizationWrapError::InvalidMetadataKey
+        );
+        require!(
+            additional_metadata[..index].iter().all(|other| other.key != entry.key),
+            TokenizationWrapError::InvalidMetadataKey
+        );
+        validate_metadata_field(&entry.key, &entry.value)?;
+    }
+
+    return Ok(());
+}
+
+#[derive(Accounts)]
+pub struct UpdateMetadata<'info> {
+    // metadata manager, pays the rent when the metadata grows
+    #[account(mut)]
+    pub metadata_manager: Signer<'info>,
+
+    // The wrapped mint, holding the token metadata
+    #[account(
+        mut,
+        constraint = (wrapped_mint_state.wrapped_mint == wrapped_mint.key()) @ TokenizationWrapError::InvalidWrappedMint,
+    )]
+    pub wrapped_mint: InterfaceAccount<'info, Mint>,
+
+    // wrapped mint state, the metadata update authority must be the wrapped mint authority PDA
+    #[account(
+        seeds = [WRAPPED_MINT_STATE_SEED, &wrapped_mint.key().as_ref()],
+        bump,
+        constraint = (wrapped_mint_state.authority_mode == MintAuthorityMode::Program) @ TokenizationWrapError::InvalidMintAuthorityMode,
+    )]
+    pub wrapped_mint_state: Account<'info, WrappedMintState>,
+
+    // metadata manager role check
+    #[account(
+        seeds=[WRAPPED_ROLE_SEED,&wrapped_mint.key().as_ref(),&[RoleKind::MetadataManager.as_u8()],metadata_manager.key().as_ref()],
+        bump,
+        constraint = metadata_manager_role.has_role(&metadata_manager.key(), &[RoleKind::MetadataManager]) @ TokenizationWrapError::Unauthorized,
+    )]
+    pub metadata_manager_role: Account<'info, RoleState>,
+
+    /// CHECK: PDA holding the wrapped mint authorities, no data
+    #[account(
+        seeds = [WRAPPED_MINT_AUTHORITY_SEED, &wrapped_mint.key().as_ref()],
+        bump,
+    )]
+    pub wrapped_mint_authority: UncheckedAccount<'info>,
+
+    pub token_program: Program<'info, Token2022>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Additional token metadata key/value pair, e.g. ISIN, issuer legal entity, jurisdiction
+/// or the hash of the offering document.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq)]
+pub struct MetadataEntry {
+    pub key: String,
+    pub value: String,
+}
+
+#[event]
+pub struct MetadataFieldUpdatedEvent {
+    pub wrapped_mint: Pubkey,
+    pub field: String,
+    pub value: String,
+    pub metadata_manager: Pubkey,
+}
+
+#[event]
+pub struct MetadataKeyRemovedEvent {
+    pub wrapped_mint: Pubkey,
+    pub key: String,
+    pub metadata_manager: Pubkey,
+}
+
+#[event]
+pub struct MetadataAuthorityUpdatedEvent {
+    pub wrapped_mint: Pubkey,
+    pub old_authority: Pubkey,
+    // None when the metadata was made immutable
+    pub new_authority: Option<Pubkey>,
+    pub metadata_manager: Pubkey,
+}
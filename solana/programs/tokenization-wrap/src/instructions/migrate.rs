@@ -0,0 +1,249 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::*;
+use crate::errors::TokenizationWrapError;
+use crate::instructions::authority::MintAuthorityMode;
+use crate::instructions::create_mint::{CreateMintConfig, WrappedMintState};
+use crate::instructions::rate_limit::{RateLimitConfig, RateLimitWindow};
+use crate::instructions::role::{RoleKind, RoleState};
+use crate::instructions::underlying::underlying_risk_profile;
+use crate::utils::top_up_rent;
+use anchor_spl::token_interface::Mint;
+use spl_token_2022_v9::{
+    extension::{transfer_hook::TransferHook, BaseStateWithExtensions, StateWithExtensions},
+    state::Mint as MintState,
+};
+
+/// Grows a wrapped mint state created before the authority mode, extension profile, hook
+/// and rate limit fields existed to the current layout. Permissionless, the new fields are
+/// read back from the wrapped and unwrapped mints or start at their defaults: owner
+/// authority mode, not paused, access list off and no rate limits.
+pub fn migrate_mint_state(ctx: Context<MigrateMintState>) -> Result<()> {
+    let wrapped_mint_state = &ctx.accounts.wrapped_mint_state;
+    let wrapped_mint = &ctx.accounts.wrapped_mint;
+    let wrapped_mint_key = wrapped_mint.key();
+    let unwrapped_mint = &ctx.accounts.unwrapped_mint;
+    let payer = &ctx.accounts.payer;
+
+    require!(
+        wrapped_mint_state.owner == &crate::ID,
+        TokenizationWrapError::InvalidAccountData
+    );
+    require!(
+        wrapped_mint_state.data_len() == ANCHOR_DISCRIMINATOR_SIZE + LegacyWrappedMintState::INIT_SPACE,
+        TokenizationWrapError::AlreadyMigrated
+    );
+
+    let legacy_state = {
+        let wrapped_mint_state_data = wrapped_mint_state.try_borrow_data()?;
+        require!(
+            wrapped_mint_state_data[..ANCHOR_DISCRIMINATOR_SIZE] == *WrappedMintState::DISCRIMINATOR,
+            TokenizationWrapError::InvalidAccountData
+        );
+        LegacyWrappedMintState::deserialize(&mut &wrapped_mint_state_data[ANCHOR_DISCRIMINATOR_SIZE..])?
+    };
+    require!(
+        legacy_state.wrapped_mint == wrapped_mint_key,
+        TokenizationWrapError::InvalidWrappedMint
+    );
+    require!(
+        legacy_state.unwrapped_mint == unwrapped_mint.key(),
+        TokenizationWrapError::InvalidUnwrappedMint
+    );
+
+    // legacy mints were created with every extension, read back which ones and their parameters
+    let mint_config = CreateMintConfig::from_mint(&wrapped_mint.to_account_info())?;
+    let transfer_hook_program = {
+        let wrapped_mint_info = wrapped_mint.to_account_info();
+        let wrapped_mint_data = wrapped_mint_info.try_borrow_data()?;
+        let mint_state = StateWithExtensions::<MintState>::unpack(&wrapped_mint_data)
+            .map_err(|_| TokenizationWrapError::InvalidWrappedMint)?;
+        mint_state
+            .get_extension::<TransferHook>()
+            .ok()
+            .and_then(|extension| Option::<Pubkey>::from(extension.program_id))
+    };
+    let underlying_risk_flags = underlying_risk_profile(&unwrapped_mint.to_account_info())?.risk_flags;
+
+    let migrated_state = WrappedMintState {
+        salt: legacy_state.salt,
+        wrapped_mint: legacy_state.wrapped_mint,
+        wrapped_mint_owner: legacy_state.wrapped_mint_owner,
+        wrapped_mint_pending_owner: legacy_state.wrapped_mint_pending_owner,
+        unwrapped_mint: legacy_state.unwrapped_mint,
+        unwrapped_mint_vault: legacy_state.unwrapped_mint_vault,
+        authority_mode: MintAuthorityMode::Owner,
+        paused: false,
+        access_list_enabled: false,
+        underlying_risk_flags: underlying_risk_flags,
+        mint_config: mint_config.clone(),
+        transfer_hook_program: transfer_hook_program,
+        pending_transfer_hook_program: None,
+        pending_transfer_hook_effective_timestamp: 0,
+        transfer_hook_timelock: 0,
+        rate_limit: RateLimitConfig::default(),
+        wrap_window: RateLimitWindow::default(),
+        unwrap_window: RateLimitWindow::default(),
+        version: WRAPPED_MINT_STATE_VERSION,
+    };
+
+    // grow to the current layout, the payer covers the extra rent
+    wrapped_mint_state.realloc(ANCHOR_DISCRIMINATOR_SIZE + WrappedMintState::INIT_SPACE, true)?;
+    top_up_rent(
+        &payer.to_account_info(),
+        &wrapped_mint_state.to_account_info(),
+        &ctx.accounts.system_program.to_account_info(),
+    )?;
+    let mut wrapped_mint_state_data = wrapped_mint_state.try_borrow_mut_data()?;
+    migrated_state.try_serialize(&mut &mut wrapped_mint_state_data[..])?;
+
+    emit!(MintStateMigratedEvent {
+        wrapped_mint: wrapped_mint_key,
+        version: WRAPPED_MINT_STATE_VERSION,
+        underlying_risk_flags: underlying_risk_flags,
+        mint_config: mint_config,
+    });
+
+    msg!("wrapped mint: {}", wrapped_mint_key);
+    msg!("wrapped mint state version: {}", WRAPPED_MINT_STATE_VERSION);
+
+    return Ok(());
+}
+
+/// Grows a role account created before the allowance, rate limit and validity fields
+/// existed to the current layout, for any role. Permissionless, the role keeps behaving
+/// as before: unlimited allowance, no rate limits and always valid. Until then the role
+/// can't be loaded by any instruction, a holder can migrate in the same transaction as
+/// the instruction using the role, e.g. an emergency guardian's pause.
+pub fn migrate_role(ctx: Context<MigrateRole>) -> Result<()> {
+    let user_role = &ctx.accounts.user_role;
+    let payer = &ctx.accounts.payer;
+
+    require!(
+        user_role.owner == &crate::ID,
+        TokenizationWrapError::InvalidAccountData
+    );
+    require!(
+        user_role.data_len() == ANCHOR_DISCRIMINATOR_SIZE + LegacyRoleState::INIT_SPACE,
+        TokenizationWrapError::AlreadyMigrated
+    );
+
+    let legacy_role = {
+        let user_role_data = user_role.try_borrow_data()?;
+        require!(
+            user_role_data[..ANCHOR_DISCRIMINATOR_SIZE] == *RoleState::DISCRIMINATOR,
+            TokenizationWrapError::InvalidAccountData
+        );
+        LegacyRoleState::deserialize(&mut &user_role_data[ANCHOR_DISCRIMINATOR_SIZE..])?
+    };
+
+    // the role account must be the PDA of the role it records
+    let (user_role_key, _) = Pubkey::find_program_address(
+        &[
+            WRAPPED_ROLE_SEED,
+            legacy_role.wrapped_mint.as_ref(),
+            &[legacy_role.role.as_u8()],
+            legacy_role.user.as_ref(),
+        ],
+        &crate::ID,
+    );
+    require_keys_eq!(
+        user_role.key(),
+        user_role_key,
+        TokenizationWrapError::InvalidRoleAccount
+    );
+
+    let migrated_role = RoleState::new(legacy_role.wrapped_mint, legacy_role.user, legacy_role.role);
+
+    // grow to the current layout, the payer covers the extra rent
+    user_role.realloc(ANCHOR_DISCRIMINATOR_SIZE + RoleState::INIT_SPACE, true)?;
+    top_up_rent(
+        &payer.to_account_info(),
+        &user_role.to_account_info(),
+        &ctx.accounts.system_program.to_account_info(),
+    )?;
+    let mut user_role_data = user_role.try_borrow_mut_data()?;
+    migrated_role.try_serialize(&mut &mut user_role_data[..])?;
+
+    emit!(RoleMigratedEvent {
+        wrapped_mint: legacy_role.wrapped_mint,
+        user: legacy_role.user,
+        role: legacy_role.role,
+    });
+
+    msg!("wrapped mint: {:?}", legacy_role.wrapped_mint);
+    msg!("role account: {:?}", user_role.key());
+    msg!("user: {:?}", legacy_role.user);
+    msg!("user role: {:?}", legacy_role.role);
+
+    return Ok(());
+}
+
+#[derive(Accounts)]
+pub struct MigrateMintState<'info> {
+    // pays the rent of the grown account, anyone
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub wrapped_mint: InterfaceAccount<'info, Mint>,
+
+    // unwrapped mint recorded in the wrapped mint state, its risk flags are recorded
+    pub unwrapped_mint: InterfaceAccount<'info, Mint>,
+
+    /// CHECK: wrapped mint state in the legacy layout, checked and deserialized by hand
+    #[account(
+        mut,
+        seeds = [WRAPPED_MINT_STATE_SEED, &wrapped_mint.key().as_ref()],
+        bump,
+    )]
+    pub wrapped_mint_state: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct MigrateRole<'info> {
+    // pays the rent of the grown account, anyone
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// CHECK: role account in the legacy layout, its PDA is checked against the role it records
+    #[account(mut)]
+    pub user_role: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Wrapped mint state layout before `WRAPPED_MINT_STATE_VERSION` 1.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, InitSpace)]
+pub struct LegacyWrappedMintState {
+    pub salt: [u8; 32],
+    pub wrapped_mint: Pubkey,
+    pub wrapped_mint_owner: Pubkey,
+    pub wrapped_mint_pending_owner: Pubkey,
+    pub unwrapped_mint: Pubkey,
+    pub unwrapped_mint_vault: Pubkey,
+}
+
+/// Role account layout before allowances, rate limits and validity windows.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, InitSpace)]
+pub struct LegacyRoleState {
+    pub wrapped_mint: Pubkey,
+    pub user: Pubkey,
+    pub role: RoleKind,
+}
+
+#[event]
+pub struct MintStateMigratedEvent {
+    pub wrapped_mint: Pubkey,
+    pub version: u8,
+    pub underlying_risk_flags: u32,
+    pub mint_config: CreateMintConfig,
+}
+
+#[event]
+pub struct RoleMigratedEvent {
+    pub wrapped_mint: Pubkey,
+    pub user: Pubkey,
+    pub role: RoleKind,
+}
@@ -1,7 +1,22 @@
+pub mod access_list;
+pub mod authority;
 pub mod close_vault;
+pub mod confidential;
+pub mod config;
+pub mod corporate_actions;
 pub mod create_mint;
+pub mod force_transfer;
+pub mod force_unwrap;
+pub mod freeze;
+pub mod kyc;
 pub mod manage;
+pub mod metadata;
+pub mod migrate;
+pub mod pause;
+pub mod rate_limit;
 pub mod role;
+pub mod transfer_hook;
+pub mod underlying;
 pub mod unwrap;
 pub mod wrap;
 pub mod recover_mint;
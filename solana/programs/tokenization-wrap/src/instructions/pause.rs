@@ -0,0 +1,106 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::*;
+use crate::errors::TokenizationWrapError;
+use crate::instructions::create_mint::WrappedMintState;
+use crate::instructions::role::{RoleKind, RoleState};
+use anchor_spl::token_interface::Mint;
+
+/// Halts wrap and unwrap for the wrapped mint. Holder transfers are not affected,
+/// the Token-2022 pausable extension covers that case.
+pub fn pause(ctx: Context<Pause>) -> Result<()> {
+    let wrapped_mint_state = &mut ctx.accounts.wrapped_mint_state;
+    wrapped_mint_state.paused = true;
+
+    emit!(PausedEvent {
+        wrapped_mint: ctx.accounts.wrapped_mint.key(),
+        pauser: ctx.accounts.pauser.key(),
+        role: ctx.accounts.pauser_role.role,
+    });
+
+    msg!("wrapped mint: {:?}", ctx.accounts.wrapped_mint.key());
+    msg!("paused by: {:?}", ctx.accounts.pauser.key());
+
+    return Ok(());
+}
+
+pub fn unpause(ctx: Context<Unpause>) -> Result<()> {
+    let wrapped_mint_state = &mut ctx.accounts.wrapped_mint_state;
+    wrapped_mint_state.paused = false;
+
+    emit!(UnpausedEvent {
+        wrapped_mint: ctx.accounts.wrapped_mint.key(),
+        wrapped_mint_owner: ctx.accounts.wrapped_mint_owner.key(),
+    });
+
+    msg!("wrapped mint: {:?}", ctx.accounts.wrapped_mint.key());
+    msg!("unpaused by: {:?}", ctx.accounts.wrapped_mint_owner.key());
+
+    return Ok(());
+}
+
+#[derive(Accounts)]
+pub struct Pause<'info> {
+    // pauser or emergency guardian
+    pub pauser: Signer<'info>,
+
+    // The wrapped mint
+    #[account(
+        constraint = (wrapped_mint_state.wrapped_mint == wrapped_mint.key()) @ TokenizationWrapError::InvalidWrappedMint,
+    )]
+    pub wrapped_mint: InterfaceAccount<'info, Mint>,
+
+    // wrapped mint state
+    #[account(
+        mut,
+        seeds = [WRAPPED_MINT_STATE_SEED, &wrapped_mint.key().as_ref()],
+        bump,
+        constraint = !wrapped_mint_state.paused @ TokenizationWrapError::Paused,
+    )]
+    pub wrapped_mint_state: Account<'info, WrappedMintState>,
+
+    // pauser role check, either the Pauser or the EmergencyGuardian role account
+    #[account(
+        seeds=[WRAPPED_ROLE_SEED,&wrapped_mint.key().as_ref(),&[pauser_role.role.as_u8()],pauser.key().as_ref()],
+        bump,
+        constraint = pauser_role.has_role(&pauser.key(), &[RoleKind::Pauser, RoleKind::EmergencyGuardian]) @ TokenizationWrapError::Unauthorized,
+    )]
+    pub pauser_role: Account<'info, RoleState>,
+}
+
+#[derive(Accounts)]
+pub struct Unpause<'info> {
+    // wrapped mint owner
+    #[account(
+        address = wrapped_mint_state.wrapped_mint_owner @TokenizationWrapError::NotOwner,
+    )]
+    pub wrapped_mint_owner: Signer<'info>,
+
+    // The wrapped mint
+    #[account(
+        constraint = (wrapped_mint_state.wrapped_mint == wrapped_mint.key()) @ TokenizationWrapError::InvalidWrappedMint,
+    )]
+    pub wrapped_mint: InterfaceAccount<'info, Mint>,
+
+    // wrapped mint state
+    #[account(
+        mut,
+        seeds = [WRAPPED_MINT_STATE_SEED, &wrapped_mint.key().as_ref()],
+        bump,
+        constraint = wrapped_mint_state.paused @ TokenizationWrapError::NotPaused,
+    )]
+    pub wrapped_mint_state: Account<'info, WrappedMintState>,
+}
+
+#[event]
+pub struct PausedEvent {
+    pub wrapped_mint: Pubkey,
+    pub pauser: Pubkey,
+    pub role: RoleKind,
+}
+
+#[event]
+pub struct UnpausedEvent {
+    pub wrapped_mint: Pubkey,
+    pub wrapped_mint_owner: Pubkey,
+}
@@ -0,0 +1,282 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::*;
+use crate::errors::TokenizationWrapError;
+use crate::instructions::create_mint::WrappedMintState;
+use crate::instructions::role::{RoleKind, RoleState};
+use anchor_lang::solana_program::program::set_return_data;
+use anchor_spl::token_interface::Mint;
+
+/// Sets the wrap / unwrap rate limits of the wrapped mint and the unwrap volume that trips
+/// the circuit breaker. A zero `window_seconds` turns every rate limit off.
+pub fn set_rate_limits(ctx: Context<SetRateLimits>, rate_limit: RateLimitConfig) -> Result<()> {
+    require!(
+        rate_limit.window_seconds >= 0,
+        TokenizationWrapError::InvalidRateLimit
+    );
+
+    let wrapped_mint_state = &mut ctx.accounts.wrapped_mint_state;
+    wrapped_mint_state.rate_limit = rate_limit.clone();
+
+    emit!(RateLimitsUpdatedEvent {
+        wrapped_mint: wrapped_mint_state.wrapped_mint,
+        rate_limit: rate_limit,
+    });
+
+    msg!("wrapped mint: {:?}", wrapped_mint_state.wrapped_mint);
+    msg!("rate limit: {:?}", wrapped_mint_state.rate_limit);
+
+    return Ok(());
+}
+
+/// Sets the wrap / unwrap limits of one role holder over the mint's rate limit window,
+/// 0 means no limit. Role accounts that predate rate limits must be grown with
+/// `migrate_role` first.
+pub fn set_role_rate_limits(
+    ctx: Context<SetRoleRateLimits>,
+    user: Pubkey,
+    role: u8,
+    max_wrap_amount: u64,
+    max_unwrap_amount: u64,
+) -> Result<()> {
+    let user_role = &mut ctx.accounts.user_role;
+    require!(
+        user_role.is_holder(&user, &[RoleKind::from_u8(role)?]),
+        TokenizationWrapError::InvalidRole
+    );
+    user_role.max_wrap_amount = max_wrap_amount;
+    user_role.max_unwrap_amount = max_unwrap_amount;
+
+    emit!(RoleRateLimitsUpdatedEvent {
+        wrapped_mint: user_role.wrapped_mint,
+        user: user,
+        role: user_role.role,
+        max_wrap_amount: max_wrap_amount,
+        max_unwrap_amount: max_unwrap_amount,
+    });
+
+    msg!("wrapped mint: {:?}", user_role.wrapped_mint);
+    msg!("user: {:?}", user);
+    msg!("max wrap amount: {}", max_wrap_amount);
+    msg!("max unwrap amount: {}", max_unwrap_amount);
+
+    return Ok(());
+}
+
+/// Records a wrap against the mint and the role holder rate limits.
+pub fn check_wrap_rate_limits(
+    wrapped_mint_state: &mut WrappedMintState,
+    user_role: &mut RoleState,
+    amount: u64,
+) -> Result<()> {
+    let window_seconds = wrapped_mint_state.rate_limit.window_seconds;
+    if window_seconds == 0 {
+        return Ok(());
+    }
+    let now = Clock::get()?.unix_timestamp;
+
+    let max_wrap_amount = wrapped_mint_state.rate_limit.max_wrap_amount;
+    wrapped_mint_state
+        .wrap_window
+        .record(now, window_seconds, amount, max_wrap_amount)?;
+    let max_wrap_amount = user_role.max_wrap_amount;
+    user_role
+        .wrap_window
+        .record(now, window_seconds, amount, max_wrap_amount)?;
+
+    return Ok(());
+}
+
+/// Records an unwrap against the mint and the role holder rate limits. Returns true when
+/// the unwrap outflow crossed the circuit breaker threshold instead: the wrapped mint is
+/// paused and the unwrap must not go through, failing would roll the pause back. The
+/// return data is set to `CIRCUIT_BREAKER_TRIPPED` so callers can tell it from an unwrap.
+/// Callers check the access lists and the balance first, so only burnable outflow counts.
+pub fn check_unwrap_rate_limits(
+    wrapped_mint_state: &mut WrappedMintState,
+    user_role: &mut RoleState,
+    amount: u64,
+) -> Result<bool> {
+    let window_seconds = wrapped_mint_state.rate_limit.window_seconds;
+    if window_seconds == 0 {
+        return Ok(false);
+    }
+    let now = Clock::get()?.unix_timestamp;
+
+    let threshold = wrapped_mint_state.rate_limit.circuit_breaker_unwrap_amount;
+    let window_amount = wrapped_mint_state
+        .unwrap_window
+        .amount_with(now, window_seconds, amount);
+    if threshold != 0 && window_amount > threshold {
+        wrapped_mint_state.paused = true;
+
+        emit!(CircuitBreakerTrippedEvent {
+            wrapped_mint: wrapped_mint_state.wrapped_mint,
+            user: user_role.user,
+            amount: amount,
+            window_amount: window_amount,
+            threshold: threshold,
+        });
+
+        set_return_data(CIRCUIT_BREAKER_TRIPPED.as_bytes());
+
+        msg!("wrapped mint: {:?}", wrapped_mint_state.wrapped_mint);
+        msg!("{}, unwrap window amount: {}", CIRCUIT_BREAKER_TRIPPED, window_amount);
+
+        return Ok(true);
+    }
+
+    let max_unwrap_amount = wrapped_mint_state.rate_limit.max_unwrap_amount;
+    wrapped_mint_state
+        .unwrap_window
+        .record(now, window_seconds, amount, max_unwrap_amount)?;
+    let max_unwrap_amount = user_role.max_unwrap_amount;
+    user_role
+        .unwrap_window
+        .record(now, window_seconds, amount, max_unwrap_amount)?;
+
+    return Ok(false);
+}
+
+/// Rate limits of a wrapped mint, amounts are per `window_seconds` and 0 means no limit.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, Default, PartialEq, InitSpace)]
+pub struct RateLimitConfig {
+    pub window_seconds: i64,
+    pub max_wrap_amount: u64,
+    pub max_unwrap_amount: u64,
+    // unwrap volume that pauses wrap and unwrap instead of going through
+    pub circuit_breaker_unwrap_amount: u64,
+}
+
+/// Sliding window counter. The previous window's amount is weighted by how much of it
+/// still overlaps the last `window_seconds`, which approximates a true rolling sum with
+/// two counters.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, InitSpace)]
+pub struct RateLimitWindow {
+    pub window_start: i64,
+    pub current_amount: u64,
+    pub previous_amount: u64,
+}
+
+impl RateLimitWindow {
+    // move the window forward to the one containing `now`
+    fn roll(&mut self, now: i64, window_seconds: i64) {
+        let elapsed = now.saturating_sub(self.window_start);
+        if elapsed < window_seconds {
+            return;
+        }
+        self.previous_amount = if elapsed < window_seconds.saturating_mul(2) {
+            self.current_amount
+        } else {
+            0
+        };
+        self.current_amount = 0;
+        self.window_start = now - elapsed % window_seconds;
+    }
+
+    // amount over the last `window_seconds`, after `roll`
+    fn rolling_amount(&self, now: i64, window_seconds: i64) -> u64 {
+        let elapsed = now.saturating_sub(self.window_start).clamp(0, window_seconds);
+        let previous_weight = (window_seconds - elapsed) as u128;
+        let previous = self.previous_amount as u128 * previous_weight / window_seconds as u128;
+        return (previous as u64).saturating_add(self.current_amount);
+    }
+
+    /// Rolling amount including `amount`, without recording it.
+    pub fn amount_with(&mut self, now: i64, window_seconds: i64, amount: u64) -> u64 {
+        self.roll(now, window_seconds);
+        return self.rolling_amount(now, window_seconds).saturating_add(amount);
+    }
+
+    /// Records `amount` if the rolling amount stays within `limit`, 0 means no limit.
+    pub fn record(&mut self, now: i64, window_seconds: i64, amount: u64, limit: u64) -> Result<()> {
+        let total = self.amount_with(now, window_seconds, amount);
+        require!(
+            limit == 0 || total <= limit,
+            TokenizationWrapError::RateLimitExceeded
+        );
+        self.current_amount = self.current_amount.saturating_add(amount);
+        return Ok(());
+    }
+}
+
+#[derive(Accounts)]
+pub struct SetRateLimits<'info> {
+    // wrapped mint owner
+    #[account(
+        address = wrapped_mint_state.wrapped_mint_owner @TokenizationWrapError::NotOwner,
+    )]
+    pub wrapped_mint_owner: Signer<'info>,
+
+    // The wrapped mint
+    #[account(
+        constraint = (wrapped_mint_state.wrapped_mint == wrapped_mint.key()) @ TokenizationWrapError::InvalidWrappedMint,
+    )]
+    pub wrapped_mint: InterfaceAccount<'info, Mint>,
+
+    // wrapped mint state
+    #[account(
+        mut,
+        seeds = [WRAPPED_MINT_STATE_SEED, &wrapped_mint.key().as_ref()],
+        bump,
+    )]
+    pub wrapped_mint_state: Account<'info, WrappedMintState>,
+}
+
+#[derive(Accounts)]
+#[instruction(user: Pubkey,role: u8)]
+pub struct SetRoleRateLimits<'info> {
+    // wrapped mint owner
+    #[account(
+        address = wrapped_mint_state.wrapped_mint_owner @TokenizationWrapError::NotOwner,
+    )]
+    pub wrapped_mint_owner: Signer<'info>,
+
+    // The wrapped mint
+    #[account(
+        constraint = (wrapped_mint_state.wrapped_mint == wrapped_mint.key()) @ TokenizationWrapError::InvalidWrappedMint,
+    )]
+    pub wrapped_mint: InterfaceAccount<'info, Mint>,
+
+    // wrapped mint state
+    #[account(
+        seeds = [WRAPPED_MINT_STATE_SEED, &wrapped_mint.key().as_ref()],
+        bump,
+    )]
+    pub wrapped_mint_state: Account<'info, WrappedMintState>,
+
+    // user role
+    #[account(
+        mut,
+        seeds=[WRAPPED_ROLE_SEED,&wrapped_mint.key().as_ref(),&[role],&user.as_ref()],
+        bump,
+    )]
+    pub user_role: Account<'info, RoleState>,
+}
+
+#[event]
+pub struct RateLimitsUpdatedEvent {
+    pub wrapped_mint: Pubkey,
+    pub rate_limit: RateLimitConfig,
+}
+
+#[event]
+pub struct RoleRateLimitsUpdatedEvent {
+    pub wrapped_mint: Pubkey,
+    pub user: Pubkey,
+    pub role: RoleKind,
+    // 0 means no limit
+    pub max_wrap_amount: u64,
+    pub max_unwrap_amount: u64,
+}
+
+#[event]
+pub struct CircuitBreakerTrippedEvent {
+    pub wrapped_mint: Pubkey,
+    pub user: Pubkey,
+    // unwrap that was refused
+    pub amount: u64,
+    // unwrap volume over the window including the refused unwrap
+    pub window_amount: u64,
+    pub threshold: u64,
+}
@@ -4,7 +4,7 @@ use crate::instructions::create_mint::WrappedMintState;
 use anchor_lang::prelude::*;
 use anchor_spl::associated_token::AssociatedToken;
 use anchor_spl::token_2022::Token2022;
-use anchor_spl::token_interface::{Mint, TokenAccount, MintTo, mint_to};
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface, MintTo, mint_to};
 
 
 pub fn recover_mint(ctx: Context<RecoverMint>) -> Result<()> {
@@ -116,11 +116,14 @@ pub struct RecoverMint<'info> {
         mut,
         associated_token::mint = unwrapped_mint,
         associated_token::authority = wrapped_mint, // wrapped mint is authority, holding unwrapped tokens
-        associated_token::token_program = token_program,
+        associated_token::token_program = unwrapped_token_program,
         constraint = (wrapped_mint_state.unwrapped_mint_vault == unwrapped_mint_vault.key()) @ TokenizationWrapError::InvalidUnwrappedMintVault,
     )]
     pub unwrapped_mint_vault: InterfaceAccount<'info, TokenAccount>,
 
+    // token program of the unwrapped mint, SPL Token or Token-2022
+    pub unwrapped_token_program: Interface<'info, TokenInterface>,
+    // token program of the wrapped mint, always Token-2022
     pub token_program: Program<'info, Token2022>,
     pub associated_token_program: Program<'info, AssociatedToken>,
     pub system_program: Program<'info, System>,
@@ -2,15 +2,24 @@ use anchor_lang::prelude::*;
 
 use crate::constants::*;
 use crate::errors::TokenizationWrapError;
+use crate::instructions::authority::MintAuthorityMode;
 use crate::instructions::create_mint::WrappedMintState;
-use anchor_spl::token_interface::Mint;
+use crate::instructions::rate_limit::RateLimitWindow;
+use crate::utils::{close_pda_account, create_pda_account, is_program_account};
+use anchor_lang::solana_program::program::invoke_signed;
+use anchor_spl::token_interface::{Mint, Token2022};
+use spl_token_2022_v9::extension::pausable;
 
 pub fn add_role(ctx: Context<AddRole>, user: Pubkey, role: u8) -> Result<()> {
     let user_role = &mut ctx.accounts.user_role;
     let wrapped_mint = &ctx.accounts.wrapped_mint;
-    user_role.wrapped_mint = wrapped_mint.key();
-    user_role.user = user;
-    user_role.role = RoleKind::from_u8(role)?;
+    user_role.set_inner(RoleState::new(wrapped_mint.key(), user, RoleKind::from_u8(role)?));
+
+    emit!(RoleGrantedEvent {
+        wrapped_mint: wrapped_mint.key(),
+        user: user,
+        role: user_role.role,
+    });
 
     msg!("wrapped mint: {:?}", wrapped_mint.key());
     msg!("role account: {:?}", user_role.key());
@@ -24,6 +33,12 @@ pub fn remove_role(ctx: Context<RemoveRole>, user: Pubkey, role: u8) -> Result<(
     let user_role = &ctx.accounts.user_role;
     let wrapped_mint = &ctx.accounts.wrapped_mint;
 
+    emit!(RoleRevokedEvent {
+        wrapped_mint: wrapped_mint.key(),
+        user: user,
+        role: user_role.role,
+    });
+
     msg!("wrapped mint: {:?}", wrapped_mint.key());
     msg!("role account: {:?}", user_role.key());
     msg!("user: {:?}", user);
@@ -32,6 +47,327 @@ pub fn remove_role(ctx: Context<RemoveRole>, user: Pubkey, role: u8) -> Result<(
     return Ok(());
 }
 
+/// Grants `roles` in one instruction. The matching role PDAs are passed through
+/// `remaining_accounts` in the same order as `roles`, roles already granted are skipped.
+pub fn add_roles<'info>(
+    ctx: Context<'_, '_, 'info, 'info, ManageRoles<'info>>,
+    roles: Vec<RoleAssignment>,
+) -> Result<()> {
+    let wrapped_mint_key = ctx.accounts.wrapped_mint.key();
+    require!(
+        roles.len() == ctx.remaining_accounts.len(),
+        TokenizationWrapError::InvalidRemainingAccounts
+    );
+
+    for (assignment, user_role) in roles.iter().zip(ctx.remaining_accounts.iter()) {
+        let role = RoleKind::from_u8(assignment.role)?;
+        let (user_role_key, user_role_bump) = Pubkey::find_program_address(
+            &[
+                WRAPPED_ROLE_SEED,
+                wrapped_mint_key.as_ref(),
+                &[assignment.role],
+                assignment.user.as_ref(),
+            ],
+            &crate::ID,
+        );
+        require_keys_eq!(
+            user_role.key(),
+            user_role_key,
+            TokenizationWrapError::InvalidRoleAccount
+        );
+        // already granted
+        if is_program_account(user_role) {
+            continue;
+        }
+
+        create_pda_account(
+            &ctx.accounts.wrapped_mint_owner.to_account_info(),
+            user_role,
+            &ctx.accounts.system_program.to_account_info(),
+            ANCHOR_DISCRIMINATOR_SIZE + RoleState::INIT_SPACE,
+            &[&[
+                WRAPPED_ROLE_SEED,
+                wrapped_mint_key.as_ref(),
+                &[assignment.role],
+                assignment.user.as_ref(),
+                &[user_role_bump],
+            ]],
+        )?;
+        RoleState::new(wrapped_mint_key, assignment.user, role)
+            .try_serialize(&mut &mut user_role.try_borrow_mut_data()?[..])?;
+
+        emit!(RoleGrantedEvent {
+            wrapped_mint: wrapped_mint_key,
+            user: assignment.user,
+            role: role,
+        });
+        msg!("role granted: {:?} {:?}", assignment.user, role);
+    }
+
+    return Ok(());
+}
+
+/// Revokes `roles` in one instruction, the role PDAs are passed the same way as for
+/// `add_roles`. Roles not granted are skipped, the rent goes back to the owner.
+pub fn remove_roles<'info>(
+    ctx: Context<'_, '_, 'info, 'info, ManageRoles<'info>>,
+    roles: Vec<RoleAssignment>,
+) -> Result<()> {
+    let wrapped_mint_key = ctx.accounts.wrapped_mint.key();
+    require!(
+        roles.len() == ctx.remaining_accounts.len(),
+        TokenizationWrapError::InvalidRemainingAccounts
+    );
+
+    for (assignment, user_role) in roles.iter().zip(ctx.remaining_accounts.iter()) {
+        let role = RoleKind::from_u8(assignment.role)?;
+        let (user_role_key, _) = Pubkey::find_program_address(
+            &[
+                WRAPPED_ROLE_SEED,
+                wrapped_mint_key.as_ref(),
+                &[assignment.role],
+                assignment.user.as_ref(),
+            ],
+            &crate::ID,
+        );
+        require_keys_eq!(
+            user_role.key(),
+            user_role_key,
+            TokenizationWrapError::InvalidRoleAccount
+        );
+        // not granted
+        if !is_program_account(user_role) {
+            continue;
+        }
+
+        close_pda_account(user_role, &ctx.accounts.wrapped_mint_owner.to_account_info())?;
+
+        emit!(RoleRevokedEvent {
+            wrapped_mint: wrapped_mint_key,
+            user: assignment.user,
+            role: role,
+        });
+        msg!("role revoked: {:?} {:?}", assignment.user, role);
+    }
+
+    return Ok(());
+}
+
+/// Moves a role from `old_user` to `new_user` in one instruction, so a rotation never
+/// leaves a gap or two holders. The new holder takes over the allowance, the outstanding
+/// amount, rate limits and validity window, its totals and rate limit windows start fresh.
+pub fn replace_role_holder(
+    ctx: Context<ReplaceRoleHolder>,
+    old_user: Pubkey,
+    new_user: Pubkey,
+    role: u8,
+) -> Result<()> {
+    require_keys_neq!(old_user, new_user, TokenizationWrapError::InvalidAccountData);
+    let wrapped_mint_key = ctx.accounts.wrapped_mint.key();
+    let old_user_role = &ctx.accounts.old_user_role;
+
+    let mut new_role_state = RoleState::new(wrapped_mint_key, new_user, RoleKind::from_u8(role)?);
+    new_role_state.allowance = old_user_role.allowance;
+    new_role_state.outstanding = old_user_role.outstanding;
+    new_role_state.max_wrap_amount = old_user_role.max_wrap_amount;
+    new_role_state.max_unwrap_amount = old_user_role.max_unwrap_amount;
+    new_role_state.valid_from = old_user_role.valid_from;
+    new_role_state.valid_until = old_user_role.valid_until;
+    ctx.accounts.new_user_role.set_inner(new_role_state);
+
+    let role = ctx.accounts.new_user_role.role;
+    emit!(RoleRevokedEvent {
+        wrapped_mint: wrapped_mint_key,
+        user: old_user,
+        role: role,
+    });
+    emit!(RoleGrantedEvent {
+        wrapped_mint: wrapped_mint_key,
+        user: new_user,
+        role: role,
+    });
+
+    msg!("wrapped mint: {:?}", wrapped_mint_key);
+    msg!("user role: {:?}", role);
+    msg!("old user: {:?}", old_user);
+    msg!("new user: {:?}", new_user);
+
+    return Ok(());
+}
+
+/// Sets the cap on the wrapped amount a role holder may have outstanding. A cap below the
+/// outstanding amount blocks wraps until enough is unwrapped. Role accounts that predate
+/// allowances must be grown with `migrate_role` first.
+pub fn set_allowance(ctx: Context<SetAllowance>, user: Pubkey, role: u8, allowance: u64) -> Result<()> {
+    let user_role = &mut ctx.accounts.user_role;
+    require!(
+        user_role.is_holder(&user, &[RoleKind::from_u8(role)?]),
+        TokenizationWrapError::InvalidRole
+    );
+    let old_allowance = user_role.allowance;
+    user_role.allowance = allowance;
+
+    emit!(AllowanceUpdatedEvent {
+        wrapped_mint: user_role.wrapped_mint,
+        user: user,
+        role: user_role.role,
+        old_allowance: old_allowance,
+        new_allowance: allowance,
+    });
+
+    msg!("wrapped mint: {:?}", user_role.wrapped_mint);
+    msg!("user: {:?}", user);
+    msg!("allowance: {}", allowance);
+
+    return Ok(());
+}
+
+/// Sets the window a role is valid in, checked against the Clock sysvar by every role
+/// check. 0 leaves that end open, so `valid_until` 0 never expires. Extending an expired
+/// role revives it as long as the account was not closed yet. Role accounts that predate
+/// validity windows must be grown with `migrate_role` first.
+pub fn set_role_validity(
+    ctx: Context<SetRoleValidity>,
+    user: Pubkey,
+    role: u8,
+    valid_from: i64,
+    valid_until: i64,
+) -> Result<()> {
+    require!(
+        valid_from >= 0 && valid_until >= 0 && (valid_until == 0 || valid_until > valid_from),
+        TokenizationWrapError::InvalidRoleValidity
+    );
+
+    let user_role = &mut ctx.accounts.user_role;
+    require!(
+        user_role.is_holder(&user, &[RoleKind::from_u8(role)?]),
+        TokenizationWrapError::InvalidRole
+    );
+    user_role.valid_from = valid_from;
+    user_role.valid_until = valid_until;
+
+    emit!(RoleValidityUpdatedEvent {
+        wrapped_mint: user_role.wrapped_mint,
+        user: user,
+        role: user_role.role,
+        valid_from: valid_from,
+        valid_until: valid_until,
+    });
+
+    msg!("wrapped mint: {:?}", user_role.wrapped_mint);
+    msg!("user: {:?}", user);
+    msg!("valid from: {}", valid_from);
+    msg!("valid until: {}", valid_until);
+
+    return Ok(());
+}
+
+/// Closes a role account past its `valid_until`, anyone may call it. The rent goes back
+/// to the wrapped mint owner who paid for the account.
+pub fn close_expired_role(ctx: Context<CloseExpiredRole>, user: Pubkey, role: u8) -> Result<()> {
+    let user_role = &ctx.accounts.user_role;
+    let now = Clock::get()?.unix_timestamp;
+    require!(
+        user_role.valid_until != 0 && now >= user_role.valid_until,
+        TokenizationWrapError::RoleNotExpired
+    );
+
+    emit!(ExpiredRoleClosedEvent {
+        wrapped_mint: user_role.wrapped_mint,
+        user: user,
+        role: user_role.role,
+        valid_until: user_role.valid_until,
+    });
+
+    msg!("wrapped mint: {:?}", user_role.wrapped_mint);
+    msg!("role account: {:?}", user_role.key());
+    msg!("user: {:?}", user);
+    msg!("user role: {:?}", role);
+
+    return Ok(());
+}
+
+/// Pauses every transfer, mint and burn of the wrapped mint through the Token-2022
+/// pausable extension, signed by the wrapped mint authority PDA.
+pub fn pause_mint(ctx: Context<PauseMint>) -> Result<()> {
+    let wrapped_mint = &ctx.accounts.wrapped_mint;
+    let wrapped_mint_key = wrapped_mint.key();
+    let wrapped_mint_authority = &ctx.accounts.wrapped_mint_authority;
+    let pauser_key = ctx.accounts.pauser.key();
+
+    // wrapped mint authority seeds
+    let wrapped_mint_authority_bump = ctx.bumps.wrapped_mint_authority;
+    let wrapped_mint_authority_seeds: &[&[&[u8]]] = &[&[
+        WRAPPED_MINT_AUTHORITY_SEED,
+        wrapped_mint_key.as_ref(),
+        &[wrapped_mint_authority_bump],
+    ]];
+
+    invoke_signed(
+        &pausable::instruction::pause(
+            &spl_token_2022_v9::ID,
+            &wrapped_mint_key,
+            &wrapped_mint_authority.key(),
+            &[],
+        )?,
+        &[
+            wrapped_mint.to_account_info(),
+            wrapped_mint_authority.to_account_info(),
+        ],
+        wrapped_mint_authority_seeds,
+    )?;
+
+    emit!(MintPausedEvent {
+        wrapped_mint: wrapped_mint_key,
+        pauser: pauser_key,
+        role: ctx.accounts.pauser_role.role,
+    });
+
+    msg!("wrapped mint: {:?}", wrapped_mint_key);
+    msg!("mint paused by: {:?}", pauser_key);
+
+    return Ok(());
+}
+
+pub fn resume_mint(ctx: Context<ResumeMint>) -> Result<()> {
+    let wrapped_mint = &ctx.accounts.wrapped_mint;
+    let wrapped_mint_key = wrapped_mint.key();
+    let wrapped_mint_authority = &ctx.accounts.wrapped_mint_authority;
+    let pauser_key = ctx.accounts.pauser.key();
+
+    // wrapped mint authority seeds
+    let wrapped_mint_authority_bump = ctx.bumps.wrapped_mint_authority;
+    let wrapped_mint_authority_seeds: &[&[&[u8]]] = &[&[
+        WRAPPED_MINT_AUTHORITY_SEED,
+        wrapped_mint_key.as_ref(),
+        &[wrapped_mint_authority_bump],
+    ]];
+
+    invoke_signed(
+        &pausable::instruction::resume(
+            &spl_token_2022_v9::ID,
+            &wrapped_mint_key,
+            &wrapped_mint_authority.key(),
+            &[],
+        )?,
+        &[
+            wrapped_mint.to_account_info(),
+            wrapped_mint_authority.to_account_info(),
+        ],
+        wrapped_mint_authority_seeds,
+    )?;
+
+    emit!(MintResumedEvent {
+        wrapped_mint: wrapped_mint_key,
+        pauser: pauser_key,
+    });
+
+    msg!("wrapped mint: {:?}", wrapped_mint_key);
+    msg!("mint resumed by: {:?}", pauser_key);
+
+    return Ok(());
+}
+
 #[derive(Accounts)]
 #[instruction(user: Pubkey,role: u8)]
 pub struct AddRole<'info> {
@@ -102,6 +438,251 @@ pub struct RemoveRole<'info> {
     pub user_role: Account<'info, RoleState>,
 }
 
+#[derive(Accounts)]
+pub struct ManageRoles<'info> {
+    // wrapped mint owner, pays for and receives the rent of the role accounts
+    #[account(
+        mut,
+        address = wrapped_mint_state.wrapped_mint_owner @TokenizationWrapError::NotOwner,
+    )]
+    pub wrapped_mint_owner: Signer<'info>,
+
+    // The wrapped mint
+    #[account(
+        constraint = (wrapped_mint_state.wrapped_mint == wrapped_mint.key()) @ TokenizationWrapError::InvalidWrappedMint,
+    )]
+    pub wrapped_mint: InterfaceAccount<'info, Mint>,
+
+    // wrapped mint state
+    #[account(
+        seeds = [WRAPPED_MINT_STATE_SEED, &wrapped_mint.key().as_ref()],
+        bump,
+    )]
+    pub wrapped_mint_state: Account<'info, WrappedMintState>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(old_user: Pubkey,new_user: Pubkey,role: u8)]
+pub struct ReplaceRoleHolder<'info> {
+    // wrapped mint owner
+    #[account(
+        mut,
+        address = wrapped_mint_state.wrapped_mint_owner @TokenizationWrapError::NotOwner,
+    )]
+    pub wrapped_mint_owner: Signer<'info>,
+
+    // The wrapped mint
+    #[account(
+        constraint = (wrapped_mint_state.wrapped_mint == wrapped_mint.key()) @ TokenizationWrapError::InvalidWrappedMint,
+    )]
+    pub wrapped_mint: InterfaceAccount<'info, Mint>,
+
+    // wrapped mint state
+    #[account(
+        seeds = [WRAPPED_MINT_STATE_SEED, &wrapped_mint.key().as_ref()],
+        bump,
+    )]
+    pub wrapped_mint_state: Account<'info, WrappedMintState>,
+
+    // role of the old holder
+    #[account(
+        mut,
+        seeds=[WRAPPED_ROLE_SEED,&wrapped_mint.key().as_ref(),&[role],&old_user.as_ref()],
+        bump,
+        close = wrapped_mint_owner,
+    )]
+    pub old_user_role: Account<'info, RoleState>,
+
+    // role of the new holder
+    #[account(
+        init,
+        seeds=[WRAPPED_ROLE_SEED,&wrapped_mint.key().as_ref(),&[role],&new_user.as_ref()],
+        bump,
+        payer = wrapped_mint_owner,
+        space = ANCHOR_DISCRIMINATOR_SIZE + RoleState::INIT_SPACE,
+    )]
+    pub new_user_role: Account<'info, RoleState>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(user: Pubkey,role: u8)]
+pub struct SetAllowance<'info> {
+    // wrapped mint owner
+    #[account(
+        address = wrapped_mint_state.wrapped_mint_owner @TokenizationWrapError::NotOwner,
+    )]
+    pub wrapped_mint_owner: Signer<'info>,
+
+    // The wrapped mint
+    #[account(
+        constraint = (wrapped_mint_state.wrapped_mint == wrapped_mint.key()) @ TokenizationWrapError::InvalidWrappedMint,
+    )]
+    pub wrapped_mint: InterfaceAccount<'info, Mint>,
+
+    // wrapped mint state
+    #[account(
+        seeds = [WRAPPED_MINT_STATE_SEED, &wrapped_mint.key().as_ref()],
+        bump,
+    )]
+    pub wrapped_mint_state: Account<'info, WrappedMintState>,
+
+    // user role
+    #[account(
+        mut,
+        seeds=[WRAPPED_ROLE_SEED,&wrapped_mint.key().as_ref(),&[role],&user.as_ref()],
+        bump,
+    )]
+    pub user_role: Account<'info, RoleState>,
+}
+
+#[derive(Accounts)]
+#[instruction(user: Pubkey,role: u8)]
+pub struct SetRoleValidity<'info> {
+    // wrapped mint owner
+    #[account(
+        address = wrapped_mint_state.wrapped_mint_owner @TokenizationWrapError::NotOwner,
+    )]
+    pub wrapped_mint_owner: Signer<'info>,
+
+    // The wrapped mint
+    #[account(
+        constraint = (wrapped_mint_state.wrapped_mint == wrapped_mint.key()) @ TokenizationWrapError::InvalidWrappedMint,
+    )]
+    pub wrapped_mint: InterfaceAccount<'info, Mint>,
+
+    // wrapped mint state
+    #[account(
+        seeds = [WRAPPED_MINT_STATE_SEED, &wrapped_mint.key().as_ref()],
+        bump,
+    )]
+    pub wrapped_mint_state: Account<'info, WrappedMintState>,
+
+    // user role
+    #[account(
+        mut,
+        seeds=[WRAPPED_ROLE_SEED,&wrapped_mint.key().as_ref(),&[role],&user.as_ref()],
+        bump,
+    )]
+    pub user_role: Account<'info, RoleState>,
+}
+
+#[derive(Accounts)]
+#[instruction(user: Pubkey,role: u8)]
+pub struct CloseExpiredRole<'info> {
+    /// CHECK: wrapped mint owner, receives the rent, doesn't need to sign
+    #[account(
+        mut,
+        address = wrapped_mint_state.wrapped_mint_owner @TokenizationWrapError::NotOwner,
+    )]
+    pub wrapped_mint_owner: UncheckedAccount<'info>,
+
+    // The wrapped mint
+    #[account(
+        constraint = (wrapped_mint_state.wrapped_mint == wrapped_mint.key()) @ TokenizationWrapError::InvalidWrappedMint,
+    )]
+    pub wrapped_mint: InterfaceAccount<'info, Mint>,
+
+    // wrapped mint state
+    #[account(
+        seeds = [WRAPPED_MINT_STATE_SEED, &wrapped_mint.key().as_ref()],
+        bump,
+    )]
+    pub wrapped_mint_state: Account<'info, WrappedMintState>,
+
+    // expired user role
+    #[account(
+        mut,
+        seeds=[WRAPPED_ROLE_SEED,&wrapped_mint.key().as_ref(),&[role],&user.as_ref()],
+        bump,
+        close = wrapped_mint_owner,
+    )]
+    pub user_role: Account<'info, RoleState>,
+}
+
+#[derive(Accounts)]
+pub struct PauseMint<'info> {
+    // pauser or emergency guardian
+    pub pauser: Signer<'info>,
+
+    // The wrapped mint
+    #[account(
+        mut,
+        constraint = (wrapped_mint_state.wrapped_mint == wrapped_mint.key()) @ TokenizationWrapError::InvalidWrappedMint,
+    )]
+    pub wrapped_mint: InterfaceAccount<'info, Mint>,
+
+    // wrapped mint state
+    #[account(
+        seeds = [WRAPPED_MINT_STATE_SEED, &wrapped_mint.key().as_ref()],
+        bump,
+        constraint = wrapped_mint_state.mint_config.pausable @ TokenizationWrapError::InvalidMintConfig,
+        constraint = (wrapped_mint_state.authority_mode == MintAuthorityMode::Program) @ TokenizationWrapError::InvalidMintAuthorityMode,
+    )]
+    pub wrapped_mint_state: Account<'info, WrappedMintState>,
+
+    // pauser role check, either the Pauser or the EmergencyGuardian role account
+    #[account(
+        seeds=[WRAPPED_ROLE_SEED,&wrapped_mint.key().as_ref(),&[pauser_role.role.as_u8()],pauser.key().as_ref()],
+        bump,
+        constraint = pauser_role.has_role(&pauser.key(), &[RoleKind::Pauser, RoleKind::EmergencyGuardian]) @ TokenizationWrapError::Unauthorized,
+    )]
+    pub pauser_role: Account<'info, RoleState>,
+
+    /// CHECK: PDA holding the wrapped mint authorities, no data
+    #[account(
+        seeds = [WRAPPED_MINT_AUTHORITY_SEED, &wrapped_mint.key().as_ref()],
+        bump,
+    )]
+    pub wrapped_mint_authority: UncheckedAccount<'info>,
+
+    pub token_program: Program<'info, Token2022>,
+}
+
+#[derive(Accounts)]
+pub struct ResumeMint<'info> {
+    // pauser
+    pub pauser: Signer<'info>,
+
+    // The wrapped mint
+    #[account(
+        mut,
+        constraint = (wrapped_mint_state.wrapped_mint == wrapped_mint.key()) @ TokenizationWrapError::InvalidWrappedMint,
+    )]
+    pub wrapped_mint: InterfaceAccount<'info, Mint>,
+
+    // wrapped mint state
+    #[account(
+        seeds = [WRAPPED_MINT_STATE_SEED, &wrapped_mint.key().as_ref()],
+        bump,
+        constraint = wrapped_mint_state.mint_config.pausable @ TokenizationWrapError::InvalidMintConfig,
+        constraint = (wrapped_mint_state.authority_mode == MintAuthorityMode::Program) @ TokenizationWrapError::InvalidMintAuthorityMode,
+    )]
+    pub wrapped_mint_state: Account<'info, WrappedMintState>,
+
+    // pauser role check
+    #[account(
+        seeds=[WRAPPED_ROLE_SEED,&wrapped_mint.key().as_ref(),&[RoleKind::Pauser.as_u8()],pauser.key().as_ref()],
+        bump,
+        constraint = pauser_role.has_role(&pauser.key(), &[RoleKind::Pauser]) @ TokenizationWrapError::Unauthorized,
+    )]
+    pub pauser_role: Account<'info, RoleState>,
+
+    /// CHECK: PDA holding the wrapped mint authorities, no data
+    #[account(
+        seeds = [WRAPPED_MINT_AUTHORITY_SEED, &wrapped_mint.key().as_ref()],
+        bump,
+    )]
+    pub wrapped_mint_authority: UncheckedAccount<'info>,
+
+    pub token_program: Program<'info, Token2022>,
+}
+
+/// A role granted to one user on one wrapped mint. Accounts created before the allowance,
+/// rate limit and validity fields only hold the mint, user and role and don't load until
+/// `migrate_role` grows them.
 #[account]
 #[derive(InitSpace)]
 pub struct RoleState {
@@ -109,16 +690,107 @@ pub struct RoleState {
     pub user: Pubkey,
     #[max_len(32)]
     pub role: RoleKind,
+    // cap on the wrapped amount the holder may have outstanding, UNLIMITED_ALLOWANCE is no cap
+    pub allowance: u64,
+    // wrapped amount minted by the holder's wraps and not yet burned by its unwraps
+    pub outstanding: u64,
+    // cumulative amounts minted by wrap and burned by unwrap
+    pub total_wrapped: u64,
+    pub total_unwrapped: u64,
+    // wrap / unwrap limits over the mint's rate limit window, 0 means no limit
+    pub max_wrap_amount: u64,
+    pub max_unwrap_amount: u64,
+    pub wrap_window: RateLimitWindow,
+    pub unwrap_window: RateLimitWindow,
+    // unix timestamps the role is valid from / until, 0 leaves that end open
+    pub valid_from: i64,
+    pub valid_until: i64,
+}
+
+impl RoleState {
+    // a fresh grant, unlimited and always valid
+    pub fn new(wrapped_mint: Pubkey, user: Pubkey, role: RoleKind) -> Self {
+        RoleState {
+            wrapped_mint: wrapped_mint,
+            user: user,
+            role: role,
+            allowance: UNLIMITED_ALLOWANCE,
+            outstanding: 0,
+            total_wrapped: 0,
+            total_unwrapped: 0,
+            max_wrap_amount: 0,
+            max_unwrap_amount: 0,
+            wrap_window: RateLimitWindow::default(),
+            unwrap_window: RateLimitWindow::default(),
+            valid_from: 0,
+            valid_until: 0,
+        }
+    }
+
+    // whether this role account grants `user` one of `roles` right now
+    pub fn has_role(&self, user: &Pubkey, roles: &[RoleKind]) -> bool {
+        self.is_holder(user, roles)
+            && Clock::get().is_ok_and(|clock| self.is_valid_at(clock.unix_timestamp))
+    }
+
+    // whether this role account is for `user` and one of `roles`, valid or not
+    pub fn is_holder(&self, user: &Pubkey, roles: &[RoleKind]) -> bool {
+        self.user == *user && roles.contains(&self.role)
+    }
+
+    pub fn is_valid_at(&self, now: i64) -> bool {
+        now >= self.valid_from && (self.valid_until == 0 || now < self.valid_until)
+    }
+
+    pub fn record_wrap(&mut self, amount: u64) -> Result<()> {
+        let outstanding = self.outstanding.saturating_add(amount);
+        if self.allowance != UNLIMITED_ALLOWANCE {
+            require!(outstanding <= self.allowance, TokenizationWrapError::AllowanceExceeded);
+        }
+        self.outstanding = outstanding;
+        self.total_wrapped = self.total_wrapped.saturating_add(amount);
+        return Ok(());
+    }
+
+    pub fn record_unwrap(&mut self, amount: u64) {
+        // the cap stays put, unwrapping tokens the holder didn't wrap frees nothing beyond 0
+        self.outstanding = self.outstanding.saturating_sub(amount);
+        self.total_unwrapped = self.total_unwrapped.saturating_add(amount);
+    }
+}
+
+/// A user and role pair of `add_roles` / `remove_roles`.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq)]
+pub struct RoleAssignment {
+    pub user: Pubkey,
+    pub role: u8,
 }
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug, InitSpace)]
 #[repr(u8)]
 pub enum RoleKind {
-    Default = 0, // default role, no special permissions
-    Wrapper = 1,
+    Default = 0,            // default role, no special permissions
+    Wrapper = 1,            // wrap and unwrap
+    Pauser = 2,             // pause, pause_mint / resume_mint
+    EmergencyGuardian = 3,  // pause and pause_mint only
+    AccessListManager = 4,  // access list and block list
+    KycApprover = 5,        // approve_holder / revoke_holder
+    Freezer = 6,            // freeze_accounts / thaw_accounts
+    Recoverer = 7,          // force_transfer
+    Salvager = 8,           // force_unwrap
+    MetadataManager = 9,    // token metadata updates
+    CorporateActions = 10,  // update_multiplier
+    Compliance = 11,        // approve_confidential_account
+    WrapOnly = 12,          // wrap, e.g. market makers
+    UnwrapOnly = 13,        // unwrap, e.g. redemption agents
 }
 
 impl RoleKind {
+    // roles allowed to wrap
+    pub const WRAP_ROLES: &[RoleKind] = &[RoleKind::Wrapper, RoleKind::WrapOnly];
+    // roles allowed to unwrap
+    pub const UNWRAP_ROLES: &[RoleKind] = &[RoleKind::Wrapper, RoleKind::UnwrapOnly];
+
     pub fn as_u8(self) -> u8 {
         self as u8
     }
@@ -127,7 +799,72 @@ impl RoleKind {
         match value {
             // 0 => Ok(RoleKind::Default),
             1 => Ok(RoleKind::Wrapper),
+            2 => Ok(RoleKind::Pauser),
+            3 => Ok(RoleKind::EmergencyGuardian),
+            4 => Ok(RoleKind::AccessListManager),
+            5 => Ok(RoleKind::KycApprover),
+            6 => Ok(RoleKind::Freezer),
+            7 => Ok(RoleKind::Recoverer),
+            8 => Ok(RoleKind::Salvager),
+            9 => Ok(RoleKind::MetadataManager),
+            10 => Ok(RoleKind::CorporateActions),
+            11 => Ok(RoleKind::Compliance),
+            12 => Ok(RoleKind::WrapOnly),
+            13 => Ok(RoleKind::UnwrapOnly),
             _ => err!(TokenizationWrapError::InvalidRole),
         }
     }
-}
\ No newline at end of file
+}
+
+#[event]
+pub struct RoleGrantedEvent {
+    pub wrapped_mint: Pubkey,
+    pub user: Pubkey,
+    pub role: RoleKind,
+}
+
+#[event]
+pub struct RoleRevokedEvent {
+    pub wrapped_mint: Pubkey,
+    pub user: Pubkey,
+    pub role: RoleKind,
+}
+
+#[event]
+pub struct AllowanceUpdatedEvent {
+    pub wrapped_mint: Pubkey,
+    pub user: Pubkey,
+    pub role: RoleKind,
+    pub old_allowance: u64,
+    pub new_allowance: u64,
+}
+
+#[event]
+pub struct RoleValidityUpdatedEvent {
+    pub wrapped_mint: Pubkey,
+    pub user: Pubkey,
+    pub role: RoleKind,
+    pub valid_from: i64,
+    pub valid_until: i64,
+}
+
+#[event]
+pub struct ExpiredRoleClosedEvent {
+    pub wrapped_mint: Pubkey,
+    pub user: Pubkey,
+    pub role: RoleKind,
+    pub valid_until: i64,
+}
+
+#[event]
+pub struct MintPausedEvent {
+    pub wrapped_mint: Pubkey,
+    pub pauser: Pubkey,
+    pub role: RoleKind,
+}
+
+#[event]
+pub struct MintResumedEvent {
+    pub wrapped_mint: Pubkey,
+    pub pauser: Pubkey,
+}
@@ -0,0 +1,366 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::*;
+use crate::errors::TokenizationWrapError;
+use crate::instructions::authority::MintAuthorityMode;
+use crate::instructions::create_mint::WrappedMintState;
+use anchor_lang::solana_program::program::invoke_signed;
+use anchor_spl::token_interface::{Mint, Token2022};
+use spl_token_2022_v9::extension::transfer_hook;
+use tokenization_hook::cpi::accounts::InitializeExtraAccountMetaList;
+use tokenization_hook::program::TokenizationHook;
+
+/// Points the wrapped mint's transfer hook at the tokenization hook program and
+/// initializes its extra account meta list, so the block list is enforced on every
+/// holder to holder transfer and not only on wrap/unwrap.
+pub fn attach_transfer_hook(ctx: Context<AttachTransferHook>) -> Result<()> {
+    let wrapped_mint = &ctx.accounts.wrapped_mint;
+    let wrapped_mint_key = wrapped_mint.key();
+    let wrapped_mint_state = &ctx.accounts.wrapped_mint_state;
+    let wrapped_mint_owner = &ctx.accounts.wrapped_mint_owner;
+    let wrapped_mint_authority = &ctx.accounts.wrapped_mint_authority;
+    let extra_account_meta_list = &ctx.accounts.extra_account_meta_list;
+    let tokenization_hook_program = &ctx.accounts.tokenization_hook_program;
+    let tokenization_hook_program_key = tokenization_hook_program.key();
+
+    // with a timelock the hook can only be swapped through set_transfer_hook
+    require!(
+        wrapped_mint_state.transfer_hook_timelock == 0
+            || wrapped_mint_state.transfer_hook_program == Some(tokenization_hook_program_key),
+        TokenizationWrapError::TransferHookTimelocked
+    );
+
+    // wrapped mint authority seeds
+    let wrapped_mint_authority_bump = ctx.bumps.wrapped_mint_authority;
+    let wrapped_mint_authority_seeds: &[&[&[u8]]] = &[&[
+        WRAPPED_MINT_AUTHORITY_SEED,
+        wrapped_mint_key.as_ref(),
+        &[wrapped_mint_authority_bump],
+    ]];
+
+    // transfer hook authority is the owner key or the wrapped mint authority PDA
+    let (transfer_hook_authority, signer_seeds) = match wrapped_mint_state.authority_mode {
+        MintAuthorityMode::Owner => (wrapped_mint_owner.to_account_info(), &[][..]),
+        MintAuthorityMode::Program => (
+            wrapped_mint_authority.to_account_info(),
+            wrapped_mint_authority_seeds,
+        ),
+    };
+
+    invoke_signed(
+        &transfer_hook::instruction::update(
+            &spl_token_2022_v9::ID,
+            &wrapped_mint_key,
+            &transfer_hook_authority.key(),
+            &[],
+            Some(tokenization_hook_program_key),
+        )?,
+        &[
+            wrapped_mint.to_account_info(),
+            transfer_hook_authority.clone(),
+        ],
+        signer_seeds,
+    )?;
+
+    // the list survives detaching the hook, only create it the first time
+    if extra_account_meta_list.data_is_empty() {
+        tokenization_hook::cpi::initialize_extra_account_meta_list(CpiContext::new_with_signer(
+            tokenization_hook_program.to_account_info(),
+            InitializeExtraAccountMetaList {
+                payer: wrapped_mint_owner.to_account_info(),
+                extra_account_meta_list: extra_account_meta_list.to_account_info(),
+                mint: wrapped_mint.to_account_info(),
+                authority: transfer_hook_authority.clone(),
+                system_program: ctx.accounts.system_program.to_account_info(),
+            },
+            signer_seeds,
+        ))?;
+    }
+
+    let wrapped_mint_state = &mut ctx.accounts.wrapped_mint_state;
+    wrapped_mint_state.transfer_hook_program = Some(tokenization_hook_program_key);
+
+    emit!(AttachTransferHookEvent {
+        wrapped_mint: wrapped_mint_key,
+        transfer_hook_program: tokenization_hook_program_key,
+        extra_account_meta_list: extra_account_meta_list.key(),
+    });
+
+    msg!("wrapped mint: {:?}", wrapped_mint_key);
+    msg!("transfer hook program: {:?}", tokenization_hook_program_key);
+    msg!("extra account meta list: {:?}", extra_account_meta_list.key());
+
+    return Ok(());
+}
+
+/// Points the wrapped mint's transfer hook at `program_id`, `None` detaches it. Without a
+/// timelock the change applies at once, otherwise it is staged and applied with
+/// `apply_transfer_hook` once the timelock has passed. The extra account meta list of a
+/// third party hook program is left to that program.
+pub fn set_transfer_hook(ctx: Context<SetTransferHook>, program_id: Option<Pubkey>) -> Result<()> {
+    let wrapped_mint_key = ctx.accounts.wrapped_mint.key();
+    let transfer_hook_timelock = ctx.accounts.wrapped_mint_state.transfer_hook_timelock;
+
+    if transfer_hook_timelock == 0 {
+        return update_transfer_hook(ctx, program_id);
+    }
+
+    let effective_timestamp = Clock::get()?
+        .unix_timestamp
+        .checked_add(transfer_hook_timelock)
+        .ok_or(TokenizationWrapError::InvalidTimelock)?;
+
+    let wrapped_mint_state = &mut ctx.accounts.wrapped_mint_state;
+    wrapped_mint_state.pending_transfer_hook_program = program_id;
+    wrapped_mint_state.pending_transfer_hook_effective_timestamp = effective_timestamp;
+
+    emit!(TransferHookScheduledEvent {
+        wrapped_mint: wrapped_mint_key,
+        transfer_hook_program: program_id,
+        effective_timestamp: effective_timestamp,
+    });
+
+    msg!("wrapped mint: {:?}", wrapped_mint_key);
+    msg!("pending transfer hook program: {:?}", program_id);
+    msg!("effective timestamp: {}", effective_timestamp);
+
+    return Ok(());
+}
+
+/// Applies the transfer hook change staged by `set_transfer_hook` once its timelock has passed.
+pub fn apply_transfer_hook(ctx: Context<SetTransferHook>) -> Result<()> {
+    let wrapped_mint_state = &mut ctx.accounts.wrapped_mint_state;
+    require!(
+        wrapped_mint_state.pending_transfer_hook_effective_timestamp != 0,
+        TokenizationWrapError::NoPendingTransferHook
+    );
+    require!(
+        Clock::get()?.unix_timestamp >= wrapped_mint_state.pending_transfer_hook_effective_timestamp,
+        TokenizationWrapError::TransferHookTimelocked
+    );
+
+    let program_id = wrapped_mint_state.pending_transfer_hook_program;
+    wrapped_mint_state.pending_transfer_hook_program = None;
+    wrapped_mint_state.pending_transfer_hook_effective_timestamp = 0;
+
+    return update_transfer_hook(ctx, program_id);
+}
+
+/// Sets the seconds a transfer hook change waits before it can be applied. The timelock
+/// can only grow, otherwise the owner could drop it to swap the hook at once, and is capped
+/// at `MAX_TRANSFER_HOOK_TIMELOCK` so the hook can't be locked forever. Only mints in
+/// program authority mode take a timelock, in owner mode the owner holds the transfer hook
+/// authority and could update the extension directly.
+pub fn set_transfer_hook_timelock(ctx: Context<SetTransferHookTimelock>, timelock: i64) -> Result<()> {
+    let wrapped_mint_state = &mut ctx.accounts.wrapped_mint_state;
+    require!(
+        timelock == 0 || wrapped_mint_state.authority_mode == MintAuthorityMode::Program,
+        TokenizationWrapError::InvalidMintAuthorityMode
+    );
+    require!(
+        timelock >= wrapped_mint_state.transfer_hook_timelock && timelock <= MAX_TRANSFER_HOOK_TIMELOCK,
+        TokenizationWrapError::InvalidTimelock
+    );
+    let old_timelock = wrapped_mint_state.transfer_hook_timelock;
+    wrapped_mint_state.transfer_hook_timelock = timelock;
+
+    emit!(TransferHookTimelockUpdatedEvent {
+        wrapped_mint: wrapped_mint_state.wrapped_mint,
+        old_timelock: old_timelock,
+        new_timelock: timelock,
+    });
+
+    msg!("wrapped mint: {:?}", wrapped_mint_state.wrapped_mint);
+    msg!("transfer hook timelock: {}", timelock);
+
+    return Ok(());
+}
+
+// signed by the transfer hook authority, the owner key or the wrapped mint authority PDA
+fn update_transfer_hook(ctx: Context<SetTransferHook>, program_id: Option<Pubkey>) -> Result<()> {
+    let wrapped_mint = &ctx.accounts.wrapped_mint;
+    let wrapped_mint_key = wrapped_mint.key();
+    let wrapped_mint_owner = &ctx.accounts.wrapped_mint_owner;
+    let wrapped_mint_authority = &ctx.accounts.wrapped_mint_authority;
+
+    // wrapped mint authority seeds
+    let wrapped_mint_authority_bump = ctx.bumps.wrapped_mint_authority;
+    let wrapped_mint_authority_seeds: &[&[&[u8]]] = &[&[
+        WRAPPED_MINT_AUTHORITY_SEED,
+        wrapped_mint_key.as_ref(),
+        &[wrapped_mint_authority_bump],
+    ]];
+
+    // transfer hook authority is the owner key or the wrapped mint authority PDA
+    let (transfer_hook_authority, signer_seeds) = match ctx.accounts.wrapped_mint_state.authority_mode {
+        MintAuthorityMode::Owner => (wrapped_mint_owner.to_account_info(), &[][..]),
+        MintAuthorityMode::Program => (
+            wrapped_mint_authority.to_account_info(),
+            wrapped_mint_authority_seeds,
+        ),
+    };
+
+    invoke_signed(
+        &transfer_hook::instruction::update(
+            &spl_token_2022_v9::ID,
+            &wrapped_mint_key,
+            &transfer_hook_authority.key(),
+            &[],
+            program_id,
+        )?,
+        &[
+            wrapped_mint.to_account_info(),
+            transfer_hook_authority.clone(),
+        ],
+        signer_seeds,
+    )?;
+
+    let wrapped_mint_state = &mut ctx.accounts.wrapped_mint_state;
+    let old_program_id = wrapped_mint_state.transfer_hook_program;
+    wrapped_mint_state.transfer_hook_program = program_id;
+
+    emit!(TransferHookUpdatedEvent {
+        wrapped_mint: wrapped_mint_key,
+        old_transfer_hook_program: old_program_id,
+        transfer_hook_program: program_id,
+    });
+
+    msg!("wrapped mint: {:?}", wrapped_mint_key);
+    msg!("transfer hook program: {:?}", program_id);
+
+    return Ok(());
+}
+
+#[derive(Accounts)]
+pub struct AttachTransferHook<'info> {
+    // wrapped mint owner, pays for the extra account meta list
+    #[account(
+        mut,
+        address = wrapped_mint_state.wrapped_mint_owner @TokenizationWrapError::NotOwner,
+    )]
+    pub wrapped_mint_owner: Signer<'info>,
+
+    // The wrapped mint
+    #[account(
+        mut,
+        constraint = (wrapped_mint_state.wrapped_mint == wrapped_mint.key()) @ TokenizationWrapError::InvalidWrappedMint,
+    )]
+    pub wrapped_mint: InterfaceAccount<'info, Mint>,
+
+    // wrapped mint state, the mint must have been created with the transfer hook extension
+    #[account(
+        mut,
+        seeds = [WRAPPED_MINT_STATE_SEED, &wrapped_mint.key().as_ref()],
+        bump,
+        constraint = wrapped_mint_state.mint_config.transfer_hook @ TokenizationWrapError::InvalidMintConfig,
+    )]
+    pub wrapped_mint_state: Account<'info, WrappedMintState>,
+
+    /// CHECK: PDA holding the wrapped mint authorities, no data
+    #[account(
+        seeds = [WRAPPED_MINT_AUTHORITY_SEED, &wrapped_mint.key().as_ref()],
+        bump,
+    )]
+    pub wrapped_mint_authority: UncheckedAccount<'info>,
+
+    /// CHECK: extra account meta list PDA of the tokenization hook program
+    #[account(
+        mut,
+        seeds = [EXTRA_ACCOUNT_METAS_SEED, &wrapped_mint.key().as_ref()],
+        bump,
+        seeds::program = tokenization_hook_program.key(),
+    )]
+    pub extra_account_meta_list: UncheckedAccount<'info>,
+
+    pub tokenization_hook_program: Program<'info, TokenizationHook>,
+    pub token_program: Program<'info, Token2022>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SetTransferHook<'info> {
+    // wrapped mint owner
+    #[account(
+        address = wrapped_mint_state.wrapped_mint_owner @TokenizationWrapError::NotOwner,
+    )]
+    pub wrapped_mint_owner: Signer<'info>,
+
+    // The wrapped mint
+    #[account(
+        mut,
+        constraint = (wrapped_mint_state.wrapped_mint == wrapped_mint.key()) @ TokenizationWrapError::InvalidWrappedMint,
+    )]
+    pub wrapped_mint: InterfaceAccount<'info, Mint>,
+
+    // wrapped mint state, the mint must have been created with the transfer hook extension
+    #[account(
+        mut,
+        seeds = [WRAPPED_MINT_STATE_SEED, &wrapped_mint.key().as_ref()],
+        bump,
+        constraint = wrapped_mint_state.mint_config.transfer_hook @ TokenizationWrapError::InvalidMintConfig,
+    )]
+    pub wrapped_mint_state: Account<'info, WrappedMintState>,
+
+    /// CHECK: PDA holding the wrapped mint authorities, no data
+    #[account(
+        seeds = [WRAPPED_MINT_AUTHORITY_SEED, &wrapped_mint.key().as_ref()],
+        bump,
+    )]
+    pub wrapped_mint_authority: UncheckedAccount<'info>,
+
+    pub token_program: Program<'info, Token2022>,
+}
+
+#[derive(Accounts)]
+pub struct SetTransferHookTimelock<'info> {
+    // wrapped mint owner
+    #[account(
+        address = wrapped_mint_state.wrapped_mint_owner @TokenizationWrapError::NotOwner,
+    )]
+    pub wrapped_mint_owner: Signer<'info>,
+
+    // The wrapped mint
+    #[account(
+        constraint = (wrapped_mint_state.wrapped_mint == wrapped_mint.key()) @ TokenizationWrapError::InvalidWrappedMint,
+    )]
+    pub wrapped_mint: InterfaceAccount<'info, Mint>,
+
+    // wrapped mint state, the mint must have been created with the transfer hook extension
+    #[account(
+        mut,
+        seeds = [WRAPPED_MINT_STATE_SEED, &wrapped_mint.key().as_ref()],
+        bump,
+        constraint = wrapped_mint_state.mint_config.transfer_hook @ TokenizationWrapError::InvalidMintConfig,
+    )]
+    pub wrapped_mint_state: Account<'info, WrappedMintState>,
+}
+
+#[event]
+pub struct AttachTransferHookEvent {
+    pub wrapped_mint: Pubkey,
+    pub transfer_hook_program: Pubkey,
+    pub extra_account_meta_list: Pubkey,
+}
+
+#[event]
+pub struct TransferHookScheduledEvent {
+    pub wrapped_mint: Pubkey,
+    // None when the hook will be detached
+    pub transfer_hook_program: Option<Pubkey>,
+    pub effective_timestamp: i64,
+}
+
+#[event]
+pub struct TransferHookUpdatedEvent {
+    pub wrapped_mint: Pubkey,
+    pub old_transfer_hook_program: Option<Pubkey>,
+    pub transfer_hook_program: Option<Pubkey>,
+}
+
+#[event]
+pub struct TransferHookTimelockUpdatedEvent {
+    pub wrapped_mint: Pubkey,
+    // seconds a transfer hook change waits before it can be applied
+    pub old_timelock: i64,
+    pub new_timelock: i64,
+}
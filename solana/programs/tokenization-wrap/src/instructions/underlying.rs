@@ -0,0 +1,124 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::*;
+use crate::errors::TokenizationWrapError;
+use anchor_spl::token_interface::Mint;
+use spl_token_2022_v9::{
+    extension::{
+        confidential_transfer::ConfidentialTransferMint, default_account_state::DefaultAccountState,
+        interest_bearing_mint::InterestBearingConfig, mint_close_authority::MintCloseAuthority,
+        non_transferable::NonTransferable, pausable::PausableConfig,
+        permanent_delegate::PermanentDelegate, scaled_ui_amount::ScaledUiAmountConfig,
+        transfer_fee::TransferFeeConfig, transfer_hook::TransferHook, BaseStateWithExtensions,
+        StateWithExtensions,
+    },
+    state::{AccountState, Mint as MintState},
+};
+
+/// Read-only, reports the risk profile of an unwrapped mint through the return data.
+pub fn get_underlying_risk_profile(ctx: Context<GetUnderlyingRiskProfile>) -> Result<UnderlyingRiskProfile> {
+    let unwrapped_mint = &ctx.accounts.unwrapped_mint;
+    let risk_profile = underlying_risk_profile(&unwrapped_mint.to_account_info())?;
+
+    msg!("Unwrapped mint: {}", risk_profile.unwrapped_mint);
+    msg!("Token program: {}", risk_profile.token_program);
+    msg!("Risk flags: {:#x}", risk_profile.risk_flags);
+
+    return Ok(risk_profile);
+}
+
+/// Inspects the unwrapped mint's freeze authority and extensions, a legacy SPL Token
+/// mint has no extensions and can only carry the freeze authority risk.
+pub fn underlying_risk_profile(unwrapped_mint: &AccountInfo) -> Result<UnderlyingRiskProfile> {
+    let unwrapped_mint_data = unwrapped_mint.try_borrow_data()?;
+    let mint_state = StateWithExtensions::<MintState>::unpack(&unwrapped_mint_data)
+        .map_err(|_| TokenizationWrapError::InvalidUnwrappedMint)?;
+
+    let mut risk_flags = 0;
+
+    let freeze_authority: Option<Pubkey> = mint_state.base.freeze_authority.into();
+    if freeze_authority.is_some() {
+        risk_flags |= UNDERLYING_RISK_FREEZE_AUTHORITY;
+    }
+
+    // a permanent delegate can move tokens out of the vault
+    let mut permanent_delegate = None;
+    if let Ok(extension) = mint_state.get_extension::<PermanentDelegate>() {
+        permanent_delegate = extension.delegate.into();
+        if permanent_delegate.is_some() {
+            risk_flags |= UNDERLYING_RISK_PERMANENT_DELEGATE;
+        }
+    }
+
+    // a closed mint can be recreated with different extensions, see close_vault
+    let mut mint_close_authority = None;
+    if let Ok(extension) = mint_state.get_extension::<MintCloseAuthority>() {
+        mint_close_authority = extension.close_authority.into();
+        if mint_close_authority.is_some() {
+            risk_flags |= UNDERLYING_RISK_MINT_CLOSE_AUTHORITY;
+        }
+    }
+
+    // a hook authority can point the mint at a hook program later
+    let mut transfer_hook_program = None;
+    if let Ok(extension) = mint_state.get_extension::<TransferHook>() {
+        transfer_hook_program = extension.program_id.into();
+        let transfer_hook_authority: Option<Pubkey> = extension.authority.into();
+        if transfer_hook_program.is_some() || transfer_hook_authority.is_some() {
+            risk_flags |= UNDERLYING_RISK_TRANSFER_HOOK;
+        }
+    }
+
+    if let Ok(extension) = mint_state.get_extension::<DefaultAccountState>() {
+        if extension.state == AccountState::Frozen as u8 {
+            risk_flags |= UNDERLYING_RISK_DEFAULT_FROZEN;
+        }
+    }
+
+    if mint_state.get_extension::<NonTransferable>().is_ok() {
+        risk_flags |= UNDERLYING_RISK_NON_TRANSFERABLE;
+    }
+    if mint_state.get_extension::<PausableConfig>().is_ok() {
+        risk_flags |= UNDERLYING_RISK_PAUSABLE;
+    }
+    if mint_state.get_extension::<TransferFeeConfig>().is_ok() {
+        risk_flags |= UNDERLYING_RISK_TRANSFER_FEE;
+    }
+    if mint_state.get_extension::<ConfidentialTransferMint>().is_ok() {
+        risk_flags |= UNDERLYING_RISK_CONFIDENTIAL_TRANSFER;
+    }
+    if mint_state.get_extension::<InterestBearingConfig>().is_ok() {
+        risk_flags |= UNDERLYING_RISK_INTEREST_BEARING;
+    }
+    if mint_state.get_extension::<ScaledUiAmountConfig>().is_ok() {
+        risk_flags |= UNDERLYING_RISK_SCALED_UI_AMOUNT;
+    }
+
+    return Ok(UnderlyingRiskProfile {
+        unwrapped_mint: unwrapped_mint.key(),
+        token_program: *unwrapped_mint.owner,
+        risk_flags: risk_flags,
+        freeze_authority: freeze_authority,
+        permanent_delegate: permanent_delegate,
+        mint_close_authority: mint_close_authority,
+        transfer_hook_program: transfer_hook_program,
+    });
+}
+
+#[derive(Accounts)]
+pub struct GetUnderlyingRiskProfile<'info> {
+    // unwrapped mint to inspect, SPL Token or Token-2022
+    pub unwrapped_mint: InterfaceAccount<'info, Mint>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct UnderlyingRiskProfile {
+    pub unwrapped_mint: Pubkey,
+    pub token_program: Pubkey,
+    // UNDERLYING_RISK_* flags
+    pub risk_flags: u32,
+    pub freeze_authority: Option<Pubkey>,
+    pub permanent_delegate: Option<Pubkey>,
+    pub mint_close_authority: Option<Pubkey>,
+    pub transfer_hook_program: Option<Pubkey>,
+}
@@ -6,14 +6,46 @@ use anchor_lang::prelude::*;
 use anchor_spl::associated_token::AssociatedToken;
 use anchor_spl::token_2022::Token2022;
 use anchor_spl::token_interface::{
-    burn, transfer_checked, Burn, Mint, TokenAccount, TransferChecked,
+    burn, Burn, Mint, TokenAccount, TokenInterface,
 };
+use spl_token_2022_v9::onchain::invoke_transfer_checked;
 use crate::instructions::create_mint::WrappedMintState;
-
-pub fn unwrap(ctx: Context<Unwrap>, amount: u64) -> Result<()> {
+use crate::instructions::access_list::check_access_lists;
+use crate::instructions::rate_limit::check_unwrap_rate_limits;
+
+/// Burns `amount` wrapped tokens and releases the underlying from the vault to the user.
+/// When the unwrap would push the outflow over the window past the circuit breaker
+/// threshold, the wrapped mint is paused instead and the instruction succeeds without
+/// burning or releasing anything: a `CircuitBreakerTrippedEvent` is emitted, the logs
+/// carry a `CIRCUIT_BREAKER_TRIPPED` line and the return data is set to it. Only an
+/// unwrap the user is allowed to make and could burn counts towards the threshold.
+pub fn unwrap<'info>(ctx: Context<'_, '_, 'info, 'info, Unwrap<'info>>, amount: u64) -> Result<()> {
     // Validate amount
     require!(amount > 0, TokenizationWrapError::InsufficientBalance);
 
+    // access / block list, the destination account is the user's own ATA
+    // so checking the signer also covers the destination account owner
+    check_access_lists(
+        &ctx.accounts.wrapped_mint_state,
+        &ctx.accounts.user_access_list,
+        &ctx.accounts.user_block_list,
+    )?;
+
+    // the burn has to go through, an unwrap that can't burn must not trip the circuit breaker
+    let wrapped_mint_account = &ctx.accounts.wrapped_mint_account;
+    require!(wrapped_mint_account.amount >= amount, TokenizationWrapError::InsufficientBalance);
+    require!(!wrapped_mint_account.is_frozen(), TokenizationWrapError::AccountFrozen);
+
+    // rate limits of the mint and the role holder, a tripped circuit breaker
+    // pauses the mint and nothing leaves the vault
+    if check_unwrap_rate_limits(
+        &mut ctx.accounts.wrapped_mint_state,
+        &mut ctx.accounts.user_role,
+        amount,
+    )? {
+        return Ok(());
+    }
+
     let user = &ctx.accounts.user;
     let user_key = user.key();
     let unwrapped_mint = &ctx.accounts.unwrapped_mint;
@@ -24,6 +56,7 @@ pub fn unwrap(ctx: Context<Unwrap>, amount: u64) -> Result<()> {
     let wrapped_mint_account = &ctx.accounts.wrapped_mint_account;
     let unwrapped_mint_vault = &ctx.accounts.unwrapped_mint_vault;
     let unwrapped_mint_account = &ctx.accounts.unwrapped_mint_account;
+    let unwrapped_token_program = &ctx.accounts.unwrapped_token_program;
     let token_program = &ctx.accounts.token_program;
 
     // Burn wrapped tokens
@@ -36,6 +69,14 @@ pub fn unwrap(ctx: Context<Unwrap>, amount: u64) -> Result<()> {
 
     burn(cpi_ctx, amount)?;
 
+    // burned wrapped tokens lower the role holder's outstanding amount
+    let user_role = &mut ctx.accounts.user_role;
+    user_role.record_unwrap(amount);
+
+    // user balance before the transfer, an underlying with the TransferFee
+    // extension credits the user with less than amount
+    let user_balance_before = unwrapped_mint_account.amount;
+
     // Transfer unwrapped tokens from vault to user
     // wrapped mint seeds
     let wrapped_mint_bump = ctx.bumps.wrapped_mint;
@@ -46,25 +87,42 @@ pub fn unwrap(ctx: Context<Unwrap>, amount: u64) -> Result<()> {
         &[wrapped_mint_bump],
     ]];
 
-    let cpi_accounts = TransferChecked {
-        from: unwrapped_mint_vault.to_account_info(),
-        to: unwrapped_mint_account.to_account_info(),
-        authority: wrapped_mint.to_account_info(),
-        mint: unwrapped_mint.to_account_info(),
-    };
-    let cpi_ctx = CpiContext::new_with_signer(token_program.to_account_info(), cpi_accounts, wrapped_mint_seeds);
-
-    transfer_checked(cpi_ctx, amount, unwrapped_mint.decimals)?;
+    // remaining accounts carry the extra accounts of the unwrapped mint's transfer hook, if any
+    invoke_transfer_checked(
+        unwrapped_token_program.key,
+        unwrapped_mint_vault.to_account_info(),
+        unwrapped_mint.to_account_info(),
+        unwrapped_mint_account.to_account_info(),
+        wrapped_mint.to_account_info(),
+        ctx.remaining_accounts,
+        amount,
+        unwrapped_mint.decimals,
+        wrapped_mint_seeds,
+    )?;
+
+    // the outbound fee is borne by the user, the vault is debited the full amount
+    ctx.accounts.unwrapped_mint_account.reload()?;
+    let net_amount = ctx.accounts.unwrapped_mint_account.amount
+        .checked_sub(user_balance_before)
+        .ok_or(TokenizationWrapError::InsufficientBalance)?;
+    // the user can't be credited more than was sent
+    let fee = amount
+        .checked_sub(net_amount)
+        .ok_or(TokenizationWrapError::InvalidAccountData)?;
 
     emit!(UnwrapEvent {
         user: user_key,
         wrapped_mint: wrapped_mint_key,
         unwrapped_mint: unwrapped_mint_key,
         amount: amount,
+        fee: fee,
+        net_amount: net_amount,
     });
 
     msg!("User: {}", user_key);
     msg!("Unwrapped acount: {} ", amount);
+    msg!("Transfer fee: {}", fee);
+    msg!("Received amount: {}", net_amount);
     msg!("From mint: {}", wrapped_mint_key);
     msg!("To mint: {}", unwrapped_mint_key);
 
@@ -95,25 +153,42 @@ pub struct Unwrap<'info> {
 
     // wrapped mint state
     #[account(
+        mut,
         seeds = [WRAPPED_MINT_STATE_SEED, &wrapped_mint.key().as_ref()],
         bump,
+        constraint = !wrapped_mint_state.paused @ TokenizationWrapError::Paused,
     )]
     pub wrapped_mint_state: Account<'info, WrappedMintState>,
 
-    // unwrap user role check
+    // unwrap user role check, either the Wrapper or the UnwrapOnly role account
     #[account(
-        seeds=[WRAPPED_ROLE_SEED,&wrapped_mint.key().as_ref(),&[RoleKind::Wrapper.as_u8()],user.key().as_ref()],
+        mut,
+        seeds=[WRAPPED_ROLE_SEED,&wrapped_mint.key().as_ref(),&[user_role.role.as_u8()],user.key().as_ref()],
         bump,
-        constraint = (user_role.user == user.key() && user_role.role == RoleKind::Wrapper) @ TokenizationWrapError::Unauthorized, 
+        constraint = user_role.has_role(&user.key(), RoleKind::UNWRAP_ROLES) @ TokenizationWrapError::Unauthorized,
     )]
     pub user_role: Account<'info, RoleState>, 
+
+    /// CHECK: user's access list PDA, only checked for existence
+    #[account(
+        seeds=[ACCESS_LIST_SEED,&wrapped_mint.key().as_ref(),user.key().as_ref()],
+        bump,
+    )]
+    pub user_access_list: UncheckedAccount<'info>,
+
+    /// CHECK: user's block list PDA, only checked for existence
+    #[account(
+        seeds=[BLOCK_LIST_SEED,&wrapped_mint.key().as_ref(),user.key().as_ref()],
+        bump,
+    )]
+    pub user_block_list: UncheckedAccount<'info>,
     
     /// User's unwrapped account (source)
     #[account(
         mut,
         associated_token::mint = unwrapped_mint,
         associated_token::authority = user,
-        associated_token::token_program = token_program,
+        associated_token::token_program = unwrapped_token_program,
     )]
     pub unwrapped_mint_account: InterfaceAccount<'info, TokenAccount>,
     
@@ -132,11 +207,14 @@ pub struct Unwrap<'info> {
         payer = user,
         associated_token::mint = unwrapped_mint,
         associated_token::authority = wrapped_mint, // wrapped mint is authority, holding unwrapped tokens
-        associated_token::token_program = token_program,
+        associated_token::token_program = unwrapped_token_program,
         constraint = (wrapped_mint_state.unwrapped_mint_vault == unwrapped_mint_vault.key()) @ TokenizationWrapError::InvalidUnwrappedMintVault,
     )]
     pub unwrapped_mint_vault: InterfaceAccount<'info, TokenAccount>,
 
+    // token program of the unwrapped mint, SPL Token or Token-2022
+    pub unwrapped_token_program: Interface<'info, TokenInterface>,
+    // token program of the wrapped mint, always Token-2022
     pub token_program: Program<'info, Token2022>,
     pub associated_token_program: Program<'info, AssociatedToken>,
     pub system_program: Program<'info, System>,
@@ -148,5 +226,10 @@ pub struct UnwrapEvent {
     pub user: Pubkey,
     pub wrapped_mint: Pubkey,
     pub unwrapped_mint: Pubkey,
+    // gross amount burned and transferred out of the vault
     pub amount: u64,
+    // transfer fee withheld by the unwrapped mint
+    pub fee: u64,
+    // amount received by the user
+    pub net_amount: u64,
 }
\ No newline at end of file
@@ -1,20 +1,33 @@
 use anchor_lang::prelude::*;
 use anchor_spl::token_2022::{Token2022};
 use anchor_spl::token_interface::{
-    Mint, TokenAccount,
-    TransferChecked, MintTo, transfer_checked, mint_to,
+    Mint, TokenAccount, TokenInterface,
+    MintTo, mint_to,
 };
+use spl_token_2022_v9::onchain::invoke_transfer_checked;
 use anchor_spl::associated_token::AssociatedToken;
 use crate::constants::*;
 use crate::errors::TokenizationWrapError;
 use crate::instructions::role::RoleState;
 use crate::instructions::role::RoleKind;
 use crate::instructions::create_mint::WrappedMintState;
+use crate::instructions::access_list::check_access_lists;
+use crate::instructions::rate_limit::check_wrap_rate_limits;
 
-pub fn wrap(ctx: Context<Wrap>, amount: u64) -> Result<()> {
+/// Moves `amount` unwrapped tokens into the vault and mints what the vault received to the
+/// user's wrapped account. With a frozen default account state the wrapped account has to
+/// be approved with approve_holder before the first wrap, minting to a frozen account fails.
+pub fn wrap<'info>(ctx: Context<'_, '_, 'info, 'info, Wrap<'info>>, amount: u64) -> Result<()> {
     // Validate amount
     require!(amount > 0, TokenizationWrapError::InsufficientBalance);
 
+    // rate limits of the mint and the role holder
+    check_wrap_rate_limits(
+        &mut ctx.accounts.wrapped_mint_state,
+        &mut ctx.accounts.user_role,
+        amount,
+    )?;
+
     let user = &ctx.accounts.user;
     let user_key = user.key();
     let unwrapped_mint = &ctx.accounts.unwrapped_mint;
@@ -25,23 +38,46 @@ pub fn wrap(ctx: Context<Wrap>, amount: u64) -> Result<()> {
     let wrapped_mint_account = &ctx.accounts.wrapped_mint_account;
     let unwrapped_mint_vault = &ctx.accounts.unwrapped_mint_vault;
     let unwrapped_mint_account = &ctx.accounts.unwrapped_mint_account;
+    let unwrapped_token_program = &ctx.accounts.unwrapped_token_program;
     let token_program = &ctx.accounts.token_program;
-    
+
+    // access / block list, the destination account is the user's own ATA
+    // so checking the signer also covers the destination account owner
+    check_access_lists(
+        wrapped_mint_state,
+        &ctx.accounts.user_access_list,
+        &ctx.accounts.user_block_list,
+    )?;
+
+    // vault balance before the transfer, an underlying with the TransferFee
+    // extension credits the vault with less than amount
+    let vault_balance_before = unwrapped_mint_vault.amount;
+
     // Transfer unwrapped tokens to vault
-    let cpi_accounts = TransferChecked {
-        from: unwrapped_mint_account.to_account_info(),
-        to: unwrapped_mint_vault.to_account_info(),
-        authority: user.to_account_info(),
-        mint: unwrapped_mint.to_account_info(),
-    };
-    let cpi_ctx = CpiContext::new(token_program.to_account_info(), cpi_accounts);
-    
-    transfer_checked(
-        cpi_ctx,
+    // remaining accounts carry the extra accounts of the unwrapped mint's transfer hook, if any
+    invoke_transfer_checked(
+        unwrapped_token_program.key,
+        unwrapped_mint_account.to_account_info(),
+        unwrapped_mint.to_account_info(),
+        unwrapped_mint_vault.to_account_info(),
+        user.to_account_info(),
+        ctx.remaining_accounts,
         amount,
         unwrapped_mint.decimals,
+        &[],
     )?;
 
+    // only mint what the vault actually received
+    ctx.accounts.unwrapped_mint_vault.reload()?;
+    let net_amount = ctx.accounts.unwrapped_mint_vault.amount
+        .checked_sub(vault_balance_before)
+        .ok_or(TokenizationWrapError::InsufficientBalance)?;
+    require!(net_amount > 0, TokenizationWrapError::InsufficientBalance);
+    // the vault can't be credited more than was sent
+    let fee = amount
+        .checked_sub(net_amount)
+        .ok_or(TokenizationWrapError::InvalidAccountData)?;
+
     // Mint wrapped tokens to user
     // wrapped mint seeds
     let wrapped_mint_bump = ctx.bumps.wrapped_mint;
@@ -59,17 +95,25 @@ pub fn wrap(ctx: Context<Wrap>, amount: u64) -> Result<()> {
     };
     let cpi_ctx = CpiContext::new_with_signer(token_program.to_account_info(), cpi_accounts, wrapped_mint_seeds);
     
-    mint_to(cpi_ctx, amount)?;
+    mint_to(cpi_ctx, net_amount)?;
+
+    // the role holder's outstanding amount stays within its allowance
+    let user_role = &mut ctx.accounts.user_role;
+    user_role.record_wrap(net_amount)?;
 
     emit!(WrapEvent {
         user: user_key,
         unwrapped_mint: unwrapped_mint_key,
         wrapped_mint: wrapped_mint_key,
         amount: amount,
+        fee: fee,
+        net_amount: net_amount,
     });
 
     msg!("User: {}", user_key);
     msg!("Wrapped amount: {}", amount);
+    msg!("Transfer fee: {}", fee);
+    msg!("Minted amount: {}", net_amount);
     msg!("From mint: {}", unwrapped_mint_key);
     msg!("To mint: {}", wrapped_mint_key);
     
@@ -102,25 +146,42 @@ pub struct Wrap<'info> {
 
     // wrapped mint state
     #[account(
+        mut,
         seeds = [WRAPPED_MINT_STATE_SEED, &wrapped_mint.key().as_ref()],
         bump,
+        constraint = !wrapped_mint_state.paused @ TokenizationWrapError::Paused,
     )]
     pub wrapped_mint_state: Account<'info, WrappedMintState>,
 
-    // wrap user role check
+    // wrap user role check, either the Wrapper or the WrapOnly role account
     #[account(
-        seeds=[WRAPPED_ROLE_SEED,&wrapped_mint.key().as_ref(),&[RoleKind::Wrapper.as_u8()],user.key().as_ref()],
+        mut,
+        seeds=[WRAPPED_ROLE_SEED,&wrapped_mint.key().as_ref(),&[user_role.role.as_u8()],user.key().as_ref()],
         bump,
-        constraint = (user_role.user == user.key() && user_role.role == RoleKind::Wrapper) @ TokenizationWrapError::Unauthorized, 
+        constraint = user_role.has_role(&user.key(), RoleKind::WRAP_ROLES) @ TokenizationWrapError::Unauthorized,
     )]
     pub user_role: Account<'info, RoleState>,
+
+    /// CHECK: user's access list PDA, only checked for existence
+    #[account(
+        seeds=[ACCESS_LIST_SEED,&wrapped_mint.key().as_ref(),user.key().as_ref()],
+        bump,
+    )]
+    pub user_access_list: UncheckedAccount<'info>,
+
+    /// CHECK: user's block list PDA, only checked for existence
+    #[account(
+        seeds=[BLOCK_LIST_SEED,&wrapped_mint.key().as_ref(),user.key().as_ref()],
+        bump,
+    )]
+    pub user_block_list: UncheckedAccount<'info>,
     
     /// User's unwrapped account (source)
     #[account(
         mut,
         associated_token::mint = unwrapped_mint,
         associated_token::authority = user,
-        associated_token::token_program = token_program,
+        associated_token::token_program = unwrapped_token_program,
     )]
     pub unwrapped_mint_account: InterfaceAccount<'info, TokenAccount>,
     
@@ -140,11 +201,14 @@ pub struct Wrap<'info> {
         payer = user,
         associated_token::mint = unwrapped_mint,
         associated_token::authority = wrapped_mint, // wrapped mint is authority, holding unwrapped tokens
-        associated_token::token_program = token_program,
+        associated_token::token_program = unwrapped_token_program,
         constraint = (wrapped_mint_state.unwrapped_mint_vault == unwrapped_mint_vault.key()) @ TokenizationWrapError::InvalidUnwrappedMintVault,
     )]
     pub unwrapped_mint_vault: InterfaceAccount<'info, TokenAccount>,
 
+    // token program of the unwrapped mint, SPL Token or Token-2022
+    pub unwrapped_token_program: Interface<'info, TokenInterface>,
+    // token program of the wrapped mint, always Token-2022
     pub token_program: Program<'info, Token2022>,
     pub associated_token_program: Program<'info, AssociatedToken>,
     pub system_program: Program<'info, System>,
@@ -155,5 +219,10 @@ pub struct WrapEvent {
     pub user: Pubkey,
     pub unwrapped_mint: Pubkey,
     pub wrapped_mint: Pubkey,
+    // gross amount transferred from the user
     pub amount: u64,
+    // transfer fee withheld by the unwrapped mint
+    pub fee: u64,
+    // amount received by the vault and minted to the user
+    pub net_amount: u64,
 }
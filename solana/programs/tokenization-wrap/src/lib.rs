@@ -1,21 +1,56 @@
 #![allow(unexpected_cfgs)]
 #![allow(deprecated)]
+#![allow(clippy::needless_return)]
+#![allow(clippy::redundant_field_names)]
+#![allow(clippy::vec_init_then_push)]
+#![allow(clippy::too_many_arguments)]
 use anchor_lang::prelude::*;
 
 pub mod constants;
 pub mod errors;
 pub mod instructions;
+pub mod utils;
 
+use crate::instructions::access_list;
+use crate::instructions::access_list::*;
+use crate::instructions::authority;
+use crate::instructions::authority::*;
 use crate::instructions::close_vault;
 use crate::instructions::close_vault::*;
+use crate::instructions::confidential;
+use crate::instructions::confidential::*;
+use crate::instructions::config;
+use crate::instructions::config::*;
+use crate::instructions::corporate_actions;
+use crate::instructions::corporate_actions::*;
 use crate::instructions::create_mint;
 use crate::instructions::create_mint::*;
+use crate::instructions::force_transfer;
+use crate::instructions::force_transfer::*;
+use crate::instructions::force_unwrap;
+use crate::instructions::force_unwrap::*;
+use crate::instructions::freeze;
+use crate::instructions::freeze::*;
+use crate::instructions::kyc;
+use crate::instructions::kyc::*;
 use crate::instructions::manage;
 use crate::instructions::manage::*;
+use crate::instructions::metadata;
+use crate::instructions::metadata::*;
+use crate::instructions::migrate;
+use crate::instructions::migrate::*;
+use crate::instructions::pause;
+use crate::instructions::pause::*;
+use crate::instructions::rate_limit;
+use crate::instructions::rate_limit::*;
 use crate::instructions::recover_mint;
 use crate::instructions::recover_mint::*;
 use crate::instructions::role;
 use crate::instructions::role::*;
+use crate::instructions::transfer_hook;
+use crate::instructions::transfer_hook::*;
+use crate::instructions::underlying;
+use crate::instructions::underlying::*;
 use crate::instructions::unwrap;
 use crate::instructions::unwrap::*;
 use crate::instructions::wrap;
@@ -23,10 +58,42 @@ use crate::instructions::wrap::*;
 
 declare_id!("2LbadSfQEGMooXUB3tmkXufVGKrQBkjR7UybxnvmwH4L");
 
+// the transfer hook derives this program's block list and wrapped mint authority PDAs,
+// its copies of the program id and seeds can't drift from ours without breaking the build
+const _: () = assert!(utils::bytes_eq(
+    tokenization_hook::constants::TOKENIZATION_WRAP_PROGRAM_ID.as_array(),
+    ID.as_array()
+));
+const _: () = assert!(utils::bytes_eq(
+    tokenization_hook::constants::BLOCK_LIST_SEED,
+    constants::BLOCK_LIST_SEED
+));
+const _: () = assert!(utils::bytes_eq(
+    tokenization_hook::constants::WRAPPED_MINT_AUTHORITY_SEED,
+    constants::WRAPPED_MINT_AUTHORITY_SEED
+));
+const _: () = assert!(utils::bytes_eq(
+    tokenization_hook::constants::EXTRA_ACCOUNT_METAS_SEED,
+    constants::EXTRA_ACCOUNT_METAS_SEED
+));
+
 #[program]
 pub mod tokenization_wrap {
     use super::*;
 
+    // program config
+    pub fn initialize_program_config(
+        ctx: Context<InitializeProgramConfig>,
+        authority: Pubkey,
+        allowed_underlying_risks: u32,
+    ) -> Result<()> {
+        return config::initialize_program_config(ctx, authority, allowed_underlying_risks);
+    }
+
+    pub fn set_underlying_risk_policy(ctx: Context<SetUnderlyingRiskPolicy>, allowed_underlying_risks: u32) -> Result<()> {
+        return config::set_underlying_risk_policy(ctx, allowed_underlying_risks);
+    }
+
     // manage
 
     // owner set
@@ -38,6 +105,56 @@ pub mod tokenization_wrap {
         return manage::accept_ownership(ctx);
     }
 
+    // mint authority
+    pub fn delegate_mint_authorities(ctx: Context<DelegateMintAuthorities>) -> Result<()> {
+        return authority::delegate_mint_authorities(ctx);
+    }
+
+    pub fn reclaim_mint_authorities(ctx: Context<ReclaimMintAuthorities>) -> Result<()> {
+        return authority::reclaim_mint_authorities(ctx);
+    }
+
+    // transfer hook
+    pub fn attach_transfer_hook(ctx: Context<AttachTransferHook>) -> Result<()> {
+        return transfer_hook::attach_transfer_hook(ctx);
+    }
+
+    pub fn set_transfer_hook(ctx: Context<SetTransferHook>, program_id: Option<Pubkey>) -> Result<()> {
+        return transfer_hook::set_transfer_hook(ctx, program_id);
+    }
+
+    pub fn apply_transfer_hook(ctx: Context<SetTransferHook>) -> Result<()> {
+        return transfer_hook::apply_transfer_hook(ctx);
+    }
+
+    pub fn set_transfer_hook_timelock(ctx: Context<SetTransferHookTimelock>, timelock: i64) -> Result<()> {
+        return transfer_hook::set_transfer_hook_timelock(ctx, timelock);
+    }
+
+    // pause
+    pub fn pause(ctx: Context<Pause>) -> Result<()> {
+        return pause::pause(ctx);
+    }
+
+    pub fn unpause(ctx: Context<Unpause>) -> Result<()> {
+        return pause::unpause(ctx);
+    }
+
+    // rate limit
+    pub fn set_rate_limits(ctx: Context<SetRateLimits>, rate_limit: RateLimitConfig) -> Result<()> {
+        return rate_limit::set_rate_limits(ctx, rate_limit);
+    }
+
+    pub fn set_role_rate_limits(
+        ctx: Context<SetRoleRateLimits>,
+        user: Pubkey,
+        role: u8,
+        max_wrap_amount: u64,
+        max_unwrap_amount: u64,
+    ) -> Result<()> {
+        return rate_limit::set_role_rate_limits(ctx, user, role, max_wrap_amount, max_unwrap_amount);
+    }
+
     // role
     pub fn add_role(ctx: Context<AddRole>, user: Pubkey, role: u8) -> Result<()> {
         return role::add_role(ctx, user, role);
@@ -47,6 +164,172 @@ pub mod tokenization_wrap {
         return role::remove_role(ctx, user, role);
     }
 
+    pub fn add_roles<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ManageRoles<'info>>,
+        roles: Vec<RoleAssignment>,
+    ) -> Result<()> {
+        return role::add_roles(ctx, roles);
+    }
+
+    pub fn remove_roles<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ManageRoles<'info>>,
+        roles: Vec<RoleAssignment>,
+    ) -> Result<()> {
+        return role::remove_roles(ctx, roles);
+    }
+
+    pub fn replace_role_holder(
+        ctx: Context<ReplaceRoleHolder>,
+        old_user: Pubkey,
+        new_user: Pubkey,
+        role: u8,
+    ) -> Result<()> {
+        return role::replace_role_holder(ctx, old_user, new_user, role);
+    }
+
+    pub fn set_allowance(ctx: Context<SetAllowance>, user: Pubkey, role: u8, allowance: u64) -> Result<()> {
+        return role::set_allowance(ctx, user, role, allowance);
+    }
+
+    pub fn set_role_validity(
+        ctx: Context<SetRoleValidity>,
+        user: Pubkey,
+        role: u8,
+        valid_from: i64,
+        valid_until: i64,
+    ) -> Result<()> {
+        return role::set_role_validity(ctx, user, role, valid_from, valid_until);
+    }
+
+    pub fn close_expired_role(ctx: Context<CloseExpiredRole>, user: Pubkey, role: u8) -> Result<()> {
+        return role::close_expired_role(ctx, user, role);
+    }
+
+    pub fn pause_mint(ctx: Context<PauseMint>) -> Result<()> {
+        return role::pause_mint(ctx);
+    }
+
+    pub fn resume_mint(ctx: Context<ResumeMint>) -> Result<()> {
+        return role::resume_mint(ctx);
+    }
+
+    // access list
+    pub fn toggle_access_list(ctx: Context<ToggleAccessList>, enabled: bool) -> Result<()> {
+        return access_list::toggle_access_list(ctx, enabled);
+    }
+
+    pub fn access_list_add<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ManageAccessList<'info>>,
+        users: Vec<Pubkey>,
+    ) -> Result<()> {
+        return access_list::access_list_add(ctx, users);
+    }
+
+    pub fn access_list_remove<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ManageAccessList<'info>>,
+        users: Vec<Pubkey>,
+    ) -> Result<()> {
+        return access_list::access_list_remove(ctx, users);
+    }
+
+    pub fn block_list_add<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ManageAccessList<'info>>,
+        users: Vec<Pubkey>,
+    ) -> Result<()> {
+        return access_list::block_list_add(ctx, users);
+    }
+
+    pub fn block_list_remove<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ManageAccessList<'info>>,
+        users: Vec<Pubkey>,
+    ) -> Result<()> {
+        return access_list::block_list_remove(ctx, users);
+    }
+
+    // freeze
+    pub fn freeze_accounts<'info>(ctx: Context<'_, '_, 'info, 'info, FreezeAccounts<'info>>) -> Result<()> {
+        return freeze::freeze_accounts(ctx);
+    }
+
+    pub fn thaw_accounts<'info>(ctx: Context<'_, '_, 'info, 'info, FreezeAccounts<'info>>) -> Result<()> {
+        return freeze::thaw_accounts(ctx);
+    }
+
+    // recovery
+    pub fn force_transfer<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ForceTransfer<'info>>,
+        amount: u64,
+        reason_code: u16,
+        case_reference: [u8; 32],
+    ) -> Result<()> {
+        return force_transfer::force_transfer(ctx, amount, reason_code, case_reference);
+    }
+
+    pub fn force_unwrap<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ForceUnwrap<'info>>,
+        amount: u64,
+    ) -> Result<()> {
+        return force_unwrap::force_unwrap(ctx, amount);
+    }
+
+    // kyc
+    pub fn set_default_account_state(ctx: Context<SetDefaultAccountState>, frozen: bool) -> Result<()> {
+        return kyc::set_default_account_state(ctx, frozen);
+    }
+
+    pub fn approve_holder(ctx: Context<ManageHolder>) -> Result<()> {
+        return kyc::approve_holder(ctx);
+    }
+
+    pub fn revoke_holder(ctx: Context<ManageHolder>) -> Result<()> {
+        return kyc::revoke_holder(ctx);
+    }
+
+    // metadata
+    pub fn update_metadata_field(ctx: Context<UpdateMetadata>, field: String, value: String) -> Result<()> {
+        return metadata::update_metadata_field(ctx, field, value);
+    }
+
+    pub fn remove_metadata_key(ctx: Context<UpdateMetadata>, key: String, idempotent: bool) -> Result<()> {
+        return metadata::remove_metadata_key(ctx, key, idempotent);
+    }
+
+    pub fn update_metadata_authority(
+        ctx: Context<UpdateMetadata>,
+        new_authority: Option<Pubkey>,
+    ) -> Result<()> {
+        return metadata::update_metadata_authority(ctx, new_authority);
+    }
+
+    // confidential transfer
+    pub fn set_confidential_auditor(
+        ctx: Context<UpdateConfidentialTransferMint>,
+        auditor_elgamal_pubkey: Option<[u8; 32]>,
+    ) -> Result<()> {
+        return confidential::set_confidential_auditor(ctx, auditor_elgamal_pubkey);
+    }
+
+    pub fn set_confidential_auto_approve(ctx: Context<UpdateConfidentialTransferMint>, auto_approve: bool) -> Result<()> {
+        return confidential::set_confidential_auto_approve(ctx, auto_approve);
+    }
+
+    pub fn approve_confidential_account(ctx: Context<ApproveConfidentialAccount>) -> Result<()> {
+        return confidential::approve_confidential_account(ctx);
+    }
+
+    // corporate actions
+    pub fn update_multiplier(
+        ctx: Context<UpdateMultiplier>,
+        new_multiplier: f64,
+        effective_timestamp: i64,
+    ) -> Result<()> {
+        return corporate_actions::update_multiplier(ctx, new_multiplier, effective_timestamp);
+    }
+
+    pub fn set_multiplier_bound(ctx: Context<SetMultiplierBound>, max_change: f64) -> Result<()> {
+        return corporate_actions::set_multiplier_bound(ctx, max_change);
+    }
+
     // tokenization
     pub fn create_mint(
         ctx: Context<CreateMint>,
@@ -54,15 +337,31 @@ pub mod tokenization_wrap {
         name: String,
         symbol: String,
         uri: String,
+        use_program_authority: bool,
+        mint_config: CreateMintConfig,
+        additional_metadata: Vec<MetadataEntry>,
     ) -> Result<()> {
-        return create_mint::create_mint(ctx, salt, name, symbol, uri);
+        return create_mint::create_mint(
+            ctx,
+            salt,
+            name,
+            symbol,
+            uri,
+            use_program_authority,
+            mint_config,
+            additional_metadata,
+        );
     }
 
-    pub fn wrap(ctx: Context<Wrap>, amount: u64) -> Result<()> {
+    pub fn get_underlying_risk_profile(ctx: Context<GetUnderlyingRiskProfile>) -> Result<UnderlyingRiskProfile> {
+        return underlying::get_underlying_risk_profile(ctx);
+    }
+
+    pub fn wrap<'info>(ctx: Context<'_, '_, 'info, 'info, Wrap<'info>>, amount: u64) -> Result<()> {
         return wrap::wrap(ctx, amount);
     }
 
-    pub fn unwrap(ctx: Context<Unwrap>, amount: u64) -> Result<()> {
+    pub fn unwrap<'info>(ctx: Context<'_, '_, 'info, 'info, Unwrap<'info>>, amount: u64) -> Result<()> {
         return unwrap::unwrap(ctx, amount);
     }
 
@@ -73,4 +372,13 @@ pub mod tokenization_wrap {
     pub fn recover_mint(ctx: Context<RecoverMint>) -> Result<()> {
         return recover_mint::recover_mint(ctx);
     }
+
+    // migration
+    pub fn migrate_mint_state(ctx: Context<MigrateMintState>) -> Result<()> {
+        return migrate::migrate_mint_state(ctx);
+    }
+
+    pub fn migrate_role(ctx: Context<MigrateRole>) -> Result<()> {
+        return migrate::migrate_role(ctx);
+    }
 }
@@ -0,0 +1,135 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{
+    allocate, assign, create_account, transfer, Allocate, Assign, CreateAccount, Transfer,
+};
+
+/// Creates a program owned PDA the same way Anchor's `init` constraint does, for accounts
+/// passed through `remaining_accounts` that can't be declared in an accounts struct.
+pub fn create_pda_account<'info>(
+    payer: &AccountInfo<'info>,
+    account: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    space: usize,
+    signer_seeds: &[&[&[u8]]],
+) -> Result<()> {
+    let rent = Rent::get()?;
+    let current_lamports = account.lamports();
+    if current_lamports == 0 {
+        create_account(
+            CpiContext::new_with_signer(
+                system_program.clone(),
+                CreateAccount {
+                    from: payer.clone(),
+                    to: account.clone(),
+                },
+                signer_seeds,
+            ),
+            rent.minimum_balance(space),
+            space as u64,
+            &crate::ID,
+        )?;
+    } else {
+        // account already holds lamports, top up and allocate/assign instead
+        let required_lamports = rent
+            .minimum_balance(space)
+            .max(1)
+            .saturating_sub(current_lamports);
+        if required_lamports > 0 {
+            transfer(
+                CpiContext::new(
+                    system_program.clone(),
+                    Transfer {
+                        from: payer.clone(),
+                        to: account.clone(),
+                    },
+                ),
+                required_lamports,
+            )?;
+        }
+        allocate(
+            CpiContext::new_with_signer(
+                system_program.clone(),
+                Allocate {
+                    account_to_allocate: account.clone(),
+                },
+                signer_seeds,
+            ),
+            space as u64,
+        )?;
+        assign(
+            CpiContext::new_with_signer(
+                system_program.clone(),
+                Assign {
+                    account_to_assign: account.clone(),
+                },
+                signer_seeds,
+            ),
+            &crate::ID,
+        )?;
+    }
+
+    return Ok(());
+}
+
+/// Closes a program owned account and sends its rent to `destination`, same as Anchor's `close`.
+pub fn close_pda_account<'info>(
+    account: &AccountInfo<'info>,
+    destination: &AccountInfo<'info>,
+) -> Result<()> {
+    let destination_lamports = destination.lamports();
+    **destination.lamports.borrow_mut() = destination_lamports
+        .checked_add(account.lamports())
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+    **account.lamports.borrow_mut() = 0;
+
+    account.assign(&anchor_lang::system_program::ID);
+    account.realloc(0, false)?;
+
+    return Ok(());
+}
+
+/// Tops `account` up to the rent exempt minimum of its current size, for the wrapped mint
+/// after the token metadata extension has grown it.
+pub fn top_up_rent<'info>(
+    payer: &AccountInfo<'info>,
+    account: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+) -> Result<()> {
+    // lamports truly need
+    let lamports_needed = Rent::get()?.minimum_balance(account.data_len());
+    let lamports_truly = account.lamports();
+    if lamports_needed > lamports_truly {
+        transfer(
+            CpiContext::new(
+                system_program.clone(),
+                Transfer {
+                    from: payer.clone(),
+                    to: account.clone(),
+                },
+            ),
+            lamports_needed - lamports_truly,
+        )?;
+    }
+
+    return Ok(());
+}
+
+/// Whether `account` is an initialized account owned by this program.
+pub fn is_program_account(account: &AccountInfo) -> bool {
+    account.owner == &crate::ID && !account.data_is_empty()
+}
+
+/// Byte comparison usable in constant assertions.
+pub const fn bytes_eq(a: &[u8], b: &[u8]) -> bool {
+    if a.len() != b.len() {
+        return false;
+    }
+    let mut i = 0;
+    while i < a.len() {
+        if a[i] != b[i] {
+            return false;
+        }
+        i += 1;
+    }
+    return true;
+}
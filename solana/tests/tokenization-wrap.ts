@@ -1,15 +1,23 @@
 import * as anchor from "@coral-xyz/anchor";
 import { Program, AnchorError } from "@coral-xyz/anchor";
 import { TokenizationWrap } from "../target/types/tokenization_wrap";
+import { TokenizationHook } from "../target/types/tokenization_hook";
 import { Enum, Keypair, PublicKey } from "@solana/web3.js"
 import * as borsh from "borsh";
 import { createHash } from 'crypto';
-import { getAssociatedTokenAddress, getAccount } from "@solana/spl-token"
+import { readFileSync } from "fs";
+import { getAssociatedTokenAddress, getAccount, getMint, getTransferHook, getTokenMetadata, getScaledUiAmountConfig } from "@solana/spl-token"
 import {
   createMint,
+  createInitializeMintInstruction,
+  createInitializeTransferFeeConfigInstruction,
+  createTransferCheckedInstruction,
+  ExtensionType,
+  getMintLen,
   getOrCreateAssociatedTokenAccount,
   mintTo,
   TOKEN_2022_PROGRAM_ID,
+  TOKEN_PROGRAM_ID,
   ASSOCIATED_TOKEN_PROGRAM_ID
 } from "@solana/spl-token";
 import { min } from "bn.js";
@@ -23,7 +31,26 @@ const assert = chai.assert;
 
 const WRAPPED_MINT_SEED = "wrapped_mint";
 const WRAPPED_MINT_STATE_SEED = "wrapped_mint_state";
+const WRAPPED_MINT_AUTHORITY_SEED = "wrapped_mint_authority";
 const WRAPPED_ROLE_SEED = "wrapped_role";
+const ACCESS_LIST_SEED = "access_list";
+const BLOCK_LIST_SEED = "block_list";
+const FREEZE_HOLD_SEED = "freeze_hold";
+const PROGRAM_CONFIG_SEED = "program_config";
+// Token-2022 extension types whose data starts with their authority
+const EXTENSION_MINT_CLOSE_AUTHORITY = 3;
+const EXTENSION_CONFIDENTIAL_TRANSFER_MINT = 4;
+const EXTENSION_PERMANENT_DELEGATE = 12;
+const EXTENSION_TRANSFER_HOOK = 14;
+const EXTENSION_METADATA_POINTER = 18;
+const EXTENSION_TOKEN_METADATA = 19;
+const EXTENSION_SCALED_UI_AMOUNT = 25;
+const EXTENSION_PAUSABLE = 26;
+const UNDERLYING_RISK_TRANSFER_FEE = 1 << 5;
+const MAX_ADDITIONAL_METADATA = 4;
+const MAX_ADDITIONAL_METADATA_SIZE = 320;
+const MAX_METADATA_KEY_LEN = 32;
+const MAX_TRANSFER_HOOK_TIMELOCK = 30 * 24 * 60 * 60;
 const decimals = 8;
 const wrapAmount = 10000000 * 10 ** decimals;
 const wrapAmountBN = new anchor.BN(wrapAmount.toString());
@@ -34,13 +61,58 @@ const salt: Uint8Array = new Uint8Array([
   0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
   0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x01,
 ]);
+const programAuthoritySalt: Uint8Array = new Uint8Array([
+  0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+  0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+  0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+  0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x02,
+]);
+// wrapped mint of the legacy role fixtures loaded by Anchor.toml
+const legacyRoleSalt: Uint8Array = new Uint8Array([
+  0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+  0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+  0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+  0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x03,
+]);
 const name = "Test Token";
 const symbol = "TEST";
 const uri = "https://test.com";
+// same extension set as the original hardcoded create_mint
+const defaultMintConfig = {
+  permanentDelegate: true,
+  transferHook: true,
+  pausable: true,
+  mintCloseAuthority: true,
+  defaultAccountState: true,
+  defaultFrozen: false,
+  scaledUiAmount: true,
+  scaledUiMultiplier: 1.0,
+  scaledUiMaxChange: 10.0,
+  confidentialTransfer: true,
+  confidentialAutoApprove: false,
+  confidentialAuditorElgamalPubkey: null,
+  interestBearing: false,
+  interestRate: 0,
+  transferFee: false,
+  transferFeeBasisPoints: 0,
+  transferFeeMaximumFee: new anchor.BN(0),
+};
 
 enum RoleKind {
   Default = 0, // default role, no special permissions
   Wrapper = 1,
+  Pauser = 2,
+  EmergencyGuardian = 3,
+  AccessListManager = 4,
+  KycApprover = 5,
+  Freezer = 6,
+  Recoverer = 7,
+  Salvager = 8,
+  MetadataManager = 9,
+  CorporateActions = 10,
+  Compliance = 11,
+  WrapOnly = 12,
+  UnwrapOnly = 13,
 }
 
 
@@ -53,6 +125,7 @@ const connection = provider.connection;
 // 3. spl-token create-token --decimals 18 --program-id TokenzQdBNbLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb -> no more needed
 
 const TokenizationWrapProgram = anchor.workspace.TokenizationWrap as Program<TokenizationWrap>;
+const TokenizationHookProgram = anchor.workspace.TokenizationHook as Program<TokenizationHook>;
 
 // provider.wallet
 console.log("owner / deployer:", provider.wallet.publicKey.toString());
@@ -74,7 +147,7 @@ async function airdrop() {
   await provider.connection.confirmTransaction(tx1, "processed");
 }
 
-async function createUnwrappedMint(user: Keypair) {
+async function createUnwrappedMint(user: Keypair, tokenProgram: PublicKey = TOKEN_2022_PROGRAM_ID) {
   const unwrappedMint = await createMint(
     connection,
     user,
@@ -83,13 +156,34 @@ async function createUnwrappedMint(user: Keypair) {
     decimals,
     undefined,
     { commitment: "confirmed" },
-    TOKEN_2022_PROGRAM_ID,
+    tokenProgram,
   );
   console.log("Prepared unwrapped mint:", unwrappedMint);
   return unwrappedMint;
 }
 
-async function getUnwrappedMintAta(user: Keypair, unwrappedMint: PublicKey) {
+// Token-2022 unwrapped mint whose transfers pay basisPoints of the amount as fee
+async function createTransferFeeUnwrappedMint(user: Keypair, basisPoints: number) {
+  const mintKeypair = Keypair.generate();
+  const mintLen = getMintLen([ExtensionType.TransferFeeConfig]);
+  const lamports = await connection.getMinimumBalanceForRentExemption(mintLen);
+  const transaction = new anchor.web3.Transaction().add(
+    anchor.web3.SystemProgram.createAccount({
+      fromPubkey: user.publicKey,
+      newAccountPubkey: mintKeypair.publicKey,
+      space: mintLen,
+      lamports: lamports,
+      programId: TOKEN_2022_PROGRAM_ID,
+    }),
+    createInitializeTransferFeeConfigInstruction(mintKeypair.publicKey, user.publicKey, user.publicKey, basisPoints, BigInt(wrapAmount), TOKEN_2022_PROGRAM_ID),
+    createInitializeMintInstruction(mintKeypair.publicKey, decimals, user.publicKey, null, TOKEN_2022_PROGRAM_ID),
+  );
+  await anchor.web3.sendAndConfirmTransaction(connection, transaction, [user, mintKeypair], { commitment: "confirmed" });
+  console.log("Prepared transfer fee unwrapped mint:", mintKeypair.publicKey);
+  return mintKeypair.publicKey;
+}
+
+async function getUnwrappedMintAta(user: Keypair, unwrappedMint: PublicKey, tokenProgram: PublicKey = TOKEN_2022_PROGRAM_ID) {
 
   const unwrappedMintAta = await getOrCreateAssociatedTokenAccount(
     connection,
@@ -99,7 +193,7 @@ async function getUnwrappedMintAta(user: Keypair, unwrappedMint: PublicKey) {
     false,
     undefined,
     { commitment: "confirmed" },
-    TOKEN_2022_PROGRAM_ID,
+    tokenProgram,
     ASSOCIATED_TOKEN_PROGRAM_ID
   );
   console.log("created owner ATA:", unwrappedMintAta.address.toBase58());
@@ -107,7 +201,7 @@ async function getUnwrappedMintAta(user: Keypair, unwrappedMint: PublicKey) {
   return unwrappedMintAta;
 }
 
-async function mintUnwrappedToAta(user: Keypair, unwrappedMint: PublicKey, unwrappedMintAta: PublicKey, amount: number) {
+async function mintUnwrappedToAta(user: Keypair, unwrappedMint: PublicKey, unwrappedMintAta: PublicKey, amount: number, tokenProgram: PublicKey = TOKEN_2022_PROGRAM_ID) {
   await mintTo(
     connection,
     user,
@@ -117,28 +211,28 @@ async function mintUnwrappedToAta(user: Keypair, unwrappedMint: PublicKey, unwra
     amount,
     [],
     { commitment: "confirmed" },
-    TOKEN_2022_PROGRAM_ID
+    tokenProgram
   );
   console.log("Minted unwrapped tokens");
 }
 
-async function getAtaAddress(mint: PublicKey, user: PublicKey, allowOwnerOffCurve: boolean) {
+async function getAtaAddress(mint: PublicKey, user: PublicKey, allowOwnerOffCurve: boolean, tokenProgram: PublicKey = TOKEN_2022_PROGRAM_ID) {
   const ataAddress = await getAssociatedTokenAddress(
     mint,
     user,
     allowOwnerOffCurve, // allowOwnerOffCurve
-    TOKEN_2022_PROGRAM_ID,
+    tokenProgram,
     ASSOCIATED_TOKEN_PROGRAM_ID
   );
   return ataAddress;
 }
 
-async function createUnwrappedMintAndAta(user: Keypair, amount: number) {
-  const unwrappedMint = await createUnwrappedMint(user);
+async function createUnwrappedMintAndAta(user: Keypair, amount: number, tokenProgram: PublicKey = TOKEN_2022_PROGRAM_ID) {
+  const unwrappedMint = await createUnwrappedMint(user, tokenProgram);
   await sleep(sleepTime);
-  const unwrappedMintAta = await getUnwrappedMintAta(user, unwrappedMint);
+  const unwrappedMintAta = await getUnwrappedMintAta(user, unwrappedMint, tokenProgram);
   await sleep(sleepTime);
-  await mintUnwrappedToAta(user, unwrappedMint, unwrappedMintAta.address, amount * 5);
+  await mintUnwrappedToAta(user, unwrappedMint, unwrappedMintAta.address, amount * 5, tokenProgram);
   await sleep(sleepTime);
   return { unwrappedMint, unwrappedMintAta: unwrappedMintAta.address };
 }
@@ -161,21 +255,77 @@ async function getWrappedMintStatePda(wrappedMintPda: PublicKey) {
   return wrappedMintStatePda;
 }
 
-async function createTokenizationWrapMint(salt: Uint8Array, name: string, symbol: string, uri: string, unwrappedMint: PublicKey, wrappedMintPda: PublicKey, wrappedMintStatePda: PublicKey, unwrappedMintVaultAta: PublicKey) {
+async function getWrappedMintAuthorityPda(wrappedMintPda: PublicKey) {
+  const [wrappedMintAuthorityPda] = anchor.web3.PublicKey.findProgramAddressSync(
+    [Buffer.from(WRAPPED_MINT_AUTHORITY_SEED), wrappedMintPda.toBuffer()],
+    TokenizationWrapProgram.programId
+  );
+  console.log("Prepared wrapped mint authority pda:", wrappedMintAuthorityPda);
+  return wrappedMintAuthorityPda;
+}
+
+function getProgramConfigPda() {
+  const [programConfigPda] = anchor.web3.PublicKey.findProgramAddressSync(
+    [Buffer.from(PROGRAM_CONFIG_SEED)],
+    TokenizationWrapProgram.programId
+  );
+  return programConfigPda;
+}
+
+// signed by the upgrade authority, the provider wallet deploys the programs
+async function initializeProgramConfig(allowedUnderlyingRisks: number) {
+  console.log("initializeProgramConfig:");
+  const [programDataPda] = anchor.web3.PublicKey.findProgramAddressSync(
+    [TokenizationWrapProgram.programId.toBuffer()],
+    new PublicKey("BPFLoaderUpgradeab1e11111111111111111111111")
+  );
+  const tx = await TokenizationWrapProgram.methods.initializeProgramConfig(provider.wallet.publicKey, allowedUnderlyingRisks).accounts(
+    {
+      upgradeAuthority: provider.wallet.publicKey,
+      programConfig: getProgramConfigPda(),
+      program: TokenizationWrapProgram.programId,
+      programData: programDataPda,
+      systemProgram: anchor.web3.SystemProgram.programId,
+    }
+  ).rpc();
+  console.log("Initialize program config transaction signature:", tx);
+  await sleep(sleepTime);
+}
+
+async function setUnderlyingRiskPolicy(allowedUnderlyingRisks: number) {
+  console.log("setUnderlyingRiskPolicy:");
+  const tx = await TokenizationWrapProgram.methods.setUnderlyingRiskPolicy(allowedUnderlyingRisks).accounts(
+    {
+      authority: provider.wallet.publicKey,
+      programConfig: getProgramConfigPda(),
+    }
+  ).rpc();
+  console.log("Set underlying risk policy transaction signature:", tx);
+  await sleep(sleepTime);
+}
+
+async function createTokenizationWrapMint(salt: Uint8Array, name: string, symbol: string, uri: string, unwrappedMint: PublicKey, wrappedMintPda: PublicKey, wrappedMintStatePda: PublicKey, unwrappedMintVaultAta: PublicKey, useProgramAuthority: boolean = false, unwrappedTokenProgram: PublicKey = TOKEN_2022_PROGRAM_ID, mintConfig = defaultMintConfig, additionalMetadata: { key: string, value: string }[] = []) {
   console.log("createTokenizationWrapMint:");
+  const wrappedMintAuthorityPda = await getWrappedMintAuthorityPda(wrappedMintPda);
   const tx = await TokenizationWrapProgram.methods.createMint(
     [...salt],
     name,
     symbol,
-    uri
+    uri,
+    useProgramAuthority,
+    mintConfig,
+    additionalMetadata
   ).accounts(
     {
       wrappedMintOwner: provider.wallet.publicKey,
       unwrappedMint: unwrappedMint,
       wrappedMint: wrappedMintPda,
       wrappedMintState: wrappedMintStatePda,
+      wrappedMintAuthority: wrappedMintAuthorityPda,
+      programConfig: getProgramConfigPda(),
       unwrappedMintVault: unwrappedMintVaultAta,
       associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+      unwrappedTokenProgram: unwrappedTokenProgram,
       tokenProgram: TOKEN_2022_PROGRAM_ID,
       rent: anchor.web3.SYSVAR_RENT_PUBKEY,
       systemProgram: anchor.web3.SystemProgram.programId,
@@ -194,6 +344,77 @@ async function getUserRolePda(wrappedMintPda: PublicKey, user: PublicKey, role:
   return userRolePda;
 }
 
+async function getListPda(seed: string, wrappedMintPda: PublicKey, user: PublicKey) {
+  const [listPda] = anchor.web3.PublicKey.findProgramAddressSync(
+    [Buffer.from(seed), wrappedMintPda.toBuffer(), user.toBuffer()],
+    TokenizationWrapProgram.programId
+  );
+  return listPda;
+}
+
+function getFreezeHoldPda(tokenAccount: PublicKey) {
+  const [freezeHoldPda] = anchor.web3.PublicKey.findProgramAddressSync(
+    [Buffer.from(FREEZE_HOLD_SEED), tokenAccount.toBuffer()],
+    TokenizationWrapProgram.programId
+  );
+  return freezeHoldPda;
+}
+
+// extra accounts of the tokenization hook for a wrapped mint transfer
+async function getHookAccounts(wrappedMintPda: PublicKey, sourceOwner: PublicKey, destinationOwner: PublicKey) {
+  const [extraAccountMetaListPda] = anchor.web3.PublicKey.findProgramAddressSync(
+    [Buffer.from("extra-account-metas"), wrappedMintPda.toBuffer()],
+    TokenizationHookProgram.programId
+  );
+  return [
+    { pubkey: TokenizationHookProgram.programId, isSigner: false, isWritable: false },
+    { pubkey: extraAccountMetaListPda, isSigner: false, isWritable: false },
+    { pubkey: TokenizationWrapProgram.programId, isSigner: false, isWritable: false },
+    { pubkey: await getListPda(BLOCK_LIST_SEED, wrappedMintPda, sourceOwner), isSigner: false, isWritable: false },
+    { pubkey: await getListPda(BLOCK_LIST_SEED, wrappedMintPda, destinationOwner), isSigner: false, isWritable: false },
+  ];
+}
+
+async function blockListAdd(manager: Keypair, users: PublicKey[], wrappedMintPda: PublicKey, wrappedMintStatePda: PublicKey, managerRolePda: PublicKey) {
+  console.log("blockListAdd:");
+  const remainingAccounts = [];
+  for (const user of users) {
+    remainingAccounts.push({ pubkey: await getListPda(BLOCK_LIST_SEED, wrappedMintPda, user), isWritable: true, isSigner: false });
+  }
+  const tx = await TokenizationWrapProgram.methods.blockListAdd(users).accounts
+    (
+      {
+        manager: manager.publicKey,
+        wrappedMint: wrappedMintPda,
+        wrappedMintState: wrappedMintStatePda,
+        managerRole: managerRolePda,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      }
+    ).remainingAccounts(remainingAccounts).signers([manager]).rpc();
+  console.log("Block list add transaction signature:", tx);
+  await sleep(sleepTime);
+}
+
+async function blockListRemove(manager: Keypair, users: PublicKey[], wrappedMintPda: PublicKey, wrappedMintStatePda: PublicKey, managerRolePda: PublicKey) {
+  console.log("blockListRemove:");
+  const remainingAccounts = [];
+  for (const user of users) {
+    remainingAccounts.push({ pubkey: await getListPda(BLOCK_LIST_SEED, wrappedMintPda, user), isWritable: true, isSigner: false });
+  }
+  const tx = await TokenizationWrapProgram.methods.blockListRemove(users).accounts
+    (
+      {
+        manager: manager.publicKey,
+        wrappedMint: wrappedMintPda,
+        wrappedMintState: wrappedMintStatePda,
+        managerRole: managerRolePda,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      }
+    ).remainingAccounts(remainingAccounts).signers([manager]).rpc();
+  console.log("Block list remove transaction signature:", tx);
+  await sleep(sleepTime);
+}
+
 async function addRole(user: PublicKey,role: RoleKind, wrappedMintPda: PublicKey, wrappedMintStatePda: PublicKey, userRolePda: PublicKey) {
   await TokenizationWrapProgram.methods.addRole(user,role).accounts
     (
@@ -220,209 +441,1062 @@ async function removeRole(user: PublicKey, role: RoleKind, wrappedMintPda: Publi
   await sleep(sleepTime);
 }
 
-async function tokenizationWrap(user: Keypair, unwrappedMint: PublicKey, wrappedMintPda: PublicKey, wrappedMintStatePda: PublicKey, userRolePda: PublicKey, unwrappedMintAta: PublicKey, wrappedMintAta: PublicKey, unwrappedMintVault: PublicKey) {
-  console.log("tokenizationWrap:");
-  const tx = await TokenizationWrapProgram.methods.wrap(wrapAmountBN).accounts
+async function addRoles(roles: { user: PublicKey, role: RoleKind }[], wrappedMintPda: PublicKey, wrappedMintStatePda: PublicKey) {
+  console.log("addRoles:");
+  const remainingAccounts = [];
+  for (const { user, role } of roles) {
+    remainingAccounts.push({ pubkey: await getUserRolePda(wrappedMintPda, user, Uint8Array.of(role)), isWritable: true, isSigner: false });
+  }
+  const tx = await TokenizationWrapProgram.methods.addRoles(roles).accounts
     (
       {
-        user: user.publicKey,
-        unwrappedMint: unwrappedMint,
+        wrappedMintOwner: provider.wallet.publicKey,
         wrappedMint: wrappedMintPda,
         wrappedMintState: wrappedMintStatePda,
-        userRole: userRolePda,
-        unwrappedMintAccount: unwrappedMintAta,
-        wrappedMintAccount: wrappedMintAta,
-        unwrappedMintVault: unwrappedMintVault,
-        tokenProgram: TOKEN_2022_PROGRAM_ID,
-        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
         systemProgram: anchor.web3.SystemProgram.programId,
       }
-    ).signers([user]).rpc();
-  console.log("Wrap transaction signature:", tx);
+    ).remainingAccounts(remainingAccounts).rpc();
+  console.log("Add roles transaction signature:", tx);
   await sleep(sleepTime);
 }
 
-async function tokenizationUnwrap(user: Keypair, unwrappedMint: PublicKey, wrappedMintPda: PublicKey, wrappedMintStatePda: PublicKey, userRolePda: PublicKey, unwrappedMintAta: PublicKey, wrappedMintAta: PublicKey, unwrappedMintVault: PublicKey) {
-  console.log("tokenizationUnwrap:");
-  const tx = await TokenizationWrapProgram.methods.unwrap(wrapAmountBN).accounts
+async function removeRoles(roles: { user: PublicKey, role: RoleKind }[], wrappedMintPda: PublicKey, wrappedMintStatePda: PublicKey) {
+  console.log("removeRoles:");
+  const remainingAccounts = [];
+  for (const { user, role } of roles) {
+    remainingAccounts.push({ pubkey: await getUserRolePda(wrappedMintPda, user, Uint8Array.of(role)), isWritable: true, isSigner: false });
+  }
+  const tx = await TokenizationWrapProgram.methods.removeRoles(roles).accounts
     (
       {
-        user: user.publicKey,
-        unwrappedMint: unwrappedMint,
+        wrappedMintOwner: provider.wallet.publicKey,
         wrappedMint: wrappedMintPda,
         wrappedMintState: wrappedMintStatePda,
-        userRole: userRolePda,
-        unwrappedMintAccount: unwrappedMintAta,
-        wrappedMintAccount: wrappedMintAta,
-        unwrappedMintVault: unwrappedMintVault,
-        tokenProgram: TOKEN_2022_PROGRAM_ID,
-        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
         systemProgram: anchor.web3.SystemProgram.programId,
       }
-    ).signers([user]).rpc();
-  console.log("Unwrap transaction signature:", tx);
+    ).remainingAccounts(remainingAccounts).rpc();
+  console.log("Remove roles transaction signature:", tx);
   await sleep(sleepTime);
 }
 
-async function closeUnwrappedMintVault(unwrappedMint: PublicKey, wrappedMintPda: PublicKey, wrappedMintStatePda: PublicKey, unwrappedMintVault: PublicKey) {
-  console.log("closeUnwrappedMintVault:");
-  const tx = await TokenizationWrapProgram.methods.closeVault().accounts
+async function replaceRoleHolder(oldUser: PublicKey, newUser: PublicKey, role: RoleKind, wrappedMintPda: PublicKey, wrappedMintStatePda: PublicKey) {
+  console.log("replaceRoleHolder:");
+  const tx = await TokenizationWrapProgram.methods.replaceRoleHolder(oldUser, newUser, role).accounts
     (
       {
         wrappedMintOwner: provider.wallet.publicKey,
-        unwrappedMint: unwrappedMint,
         wrappedMint: wrappedMintPda,
         wrappedMintState: wrappedMintStatePda,
-        unwrappedMintVault: unwrappedMintVault,
-        tokenProgram: TOKEN_2022_PROGRAM_ID,
-        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        oldUserRole: await getUserRolePda(wrappedMintPda, oldUser, Uint8Array.of(role)),
+        newUserRole: await getUserRolePda(wrappedMintPda, newUser, Uint8Array.of(role)),
+        systemProgram: anchor.web3.SystemProgram.programId,
       }
     ).rpc();
-  console.log("Close unwrapped mint vault transaction signature:", tx);
+  console.log("Replace role holder transaction signature:", tx);
   await sleep(sleepTime);
 }
 
-async function transferOwnership(wrappedMintPda: PublicKey, wrappedMintStatePda: PublicKey) {
-
-  //if not deployer, should throw UnauthorizedInitializer
-  try {
-    await TokenizationWrapProgram.methods.transferOwnership(newOwner.publicKey).accounts
-      (
-        {
-          wrappedMintOwner: newOwner.publicKey,
-          wrappedMint: wrappedMintPda,
-          wrappedMintState: wrappedMintStatePda,
-        }
-      ).signers([newOwner]).rpc();
-  } catch (_err) {
-    assert.isTrue(_err instanceof AnchorError);
-    const err: AnchorError = _err;
-    assert.strictEqual(err.error.errorCode.code, "NotOwner");
-    assert.strictEqual(
-      err.error.errorMessage,
-      "Not owner"
-    );
-  }
-  console.log("transferOwnership:");
-  const tx = await TokenizationWrapProgram.methods.transferOwnership(newOwner.publicKey).accounts
+async function setAllowance(user: PublicKey, role: RoleKind, wrappedMintPda: PublicKey, wrappedMintStatePda: PublicKey, userRolePda: PublicKey, allowance: anchor.BN) {
+  console.log("setAllowance:");
+  const tx = await TokenizationWrapProgram.methods.setAllowance(user, role, allowance).accounts
     (
       {
         wrappedMintOwner: provider.wallet.publicKey,
         wrappedMint: wrappedMintPda,
         wrappedMintState: wrappedMintStatePda,
+        userRole: userRolePda,
       }
     ).rpc();
-  console.log("Transfer ownership transaction signature:", tx);
+  console.log("Set allowance transaction signature:", tx);
   await sleep(sleepTime);
 }
 
-async function acceptOwnership(user: Keypair, wrappedMintPda: PublicKey, wrappedMintStatePda: PublicKey) {
-
-  //if not deployer, should throw UnauthorizedInitializer
-  try {
-    await TokenizationWrapProgram.methods.acceptOwnership().accounts
-      (
-        {
-          wrappedMintPendingOwner: provider.wallet.publicKey,
-          wrappedMint: wrappedMintPda,
-          wrappedMintState: wrappedMintStatePda,
-        }
-      ).rpc();
-  } catch (_err) {
-    assert.isTrue(_err instanceof AnchorError);
-    const err: AnchorError = _err;
-    assert.strictEqual(err.error.errorCode.code, "NotPendingOwner");
-    assert.strictEqual(
-      err.error.errorMessage,
-      "Not pending owner"
-    );
-  }
-  await sleep(sleepTime);
-
-  console.log("acceptOwnership:");
-  const tx = await TokenizationWrapProgram.methods.acceptOwnership().accounts
+async function setRoleValidity(user: PublicKey, role: RoleKind, wrappedMintPda: PublicKey, wrappedMintStatePda: PublicKey, userRolePda: PublicKey, validFrom: number, validUntil: number) {
+  console.log("setRoleValidity:");
+  const tx = await TokenizationWrapProgram.methods.setRoleValidity(user, role, new anchor.BN(validFrom), new anchor.BN(validUntil)).accounts
     (
       {
-        wrappedMintPendingOwner: user.publicKey,
+        wrappedMintOwner: provider.wallet.publicKey,
         wrappedMint: wrappedMintPda,
         wrappedMintState: wrappedMintStatePda,
+        userRole: userRolePda,
       }
-    ).signers([user]).rpc();
-  console.log("Accept ownership transaction signature:", tx);
+    ).rpc();
+  console.log("Set role validity transaction signature:", tx);
   await sleep(sleepTime);
 }
 
-async function recoverMint(user: Keypair, unwrappedMint: PublicKey, wrappedMintPda: PublicKey, wrappedMintStatePda: PublicKey, wrappedMintAta: PublicKey, unwrappedMintVault: PublicKey) {
-  console.log("recoverMint:");
-  const tx = await TokenizationWrapProgram.methods.recoverMint().accounts
+async function closeExpiredRole(user: PublicKey, role: RoleKind, wrappedMintPda: PublicKey, wrappedMintStatePda: PublicKey, userRolePda: PublicKey) {
+  console.log("closeExpiredRole:");
+  const tx = await TokenizationWrapProgram.methods.closeExpiredRole(user, role).accounts
     (
       {
-        wrappedMintOwner: user.publicKey,
-        unwrappedMint: unwrappedMint,
+        wrappedMintOwner: provider.wallet.publicKey,
         wrappedMint: wrappedMintPda,
         wrappedMintState: wrappedMintStatePda,
-        wrappedMintAccount: wrappedMintAta,
-        unwrappedMintVault: unwrappedMintVault,
-        tokenProgram: TOKEN_2022_PROGRAM_ID,
-        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
-        systemProgram: anchor.web3.SystemProgram.programId,
+        userRole: userRolePda,
       }
-    ).signers([user]).rpc();
-  console.log("Recover mint transaction signature:", tx);
+    ).rpc();
+  console.log("Close expired role transaction signature:", tx);
   await sleep(sleepTime);
 }
 
-function sleep(ms: number) {
-  return new Promise(resolve => setTimeout(resolve, ms));
+async function setRateLimits(wrappedMintPda: PublicKey, wrappedMintStatePda: PublicKey, windowSeconds: number, maxWrapAmount: anchor.BN, maxUnwrapAmount: anchor.BN, circuitBreakerUnwrapAmount: anchor.BN) {
+  console.log("setRateLimits:");
+  const tx = await TokenizationWrapProgram.methods.setRateLimits({
+    windowSeconds: new anchor.BN(windowSeconds),
+    maxWrapAmount: maxWrapAmount,
+    maxUnwrapAmount: maxUnwrapAmount,
+    circuitBreakerUnwrapAmount: circuitBreakerUnwrapAmount,
+  }).accounts
+    (
+      {
+        wrappedMintOwner: provider.wallet.publicKey,
+        wrappedMint: wrappedMintPda,
+        wrappedMintState: wrappedMintStatePda,
+      }
+    ).rpc();
+  console.log("Set rate limits transaction signature:", tx);
+  await sleep(sleepTime);
 }
 
-async function main() {
-  await airdrop();
-  await sleep(sleepTime);
-  const { unwrappedMint, unwrappedMintAta } = await createUnwrappedMintAndAta(newOwner, wrapAmount);
-  await sleep(sleepTime);
-  const wrappedMintPda = await getWrappedMintPda(unwrappedMint, salt);
-  const wrappedMintStatePda = await getWrappedMintStatePda(wrappedMintPda);
+async function setRoleRateLimits(user: PublicKey, role: RoleKind, wrappedMintPda: PublicKey, wrappedMintStatePda: PublicKey, userRolePda: PublicKey, maxWrapAmount: anchor.BN, maxUnwrapAmount: anchor.BN) {
+  console.log("setRoleRateLimits:");
+  const tx = await TokenizationWrapProgram.methods.setRoleRateLimits(user, role, maxWrapAmount, maxUnwrapAmount).accounts
+    (
+      {
+        wrappedMintOwner: provider.wallet.publicKey,
+        wrappedMint: wrappedMintPda,
+        wrappedMintState: wrappedMintStatePda,
+        userRole: userRolePda,
+      }
+    ).rpc();
+  console.log("Set role rate limits transaction signature:", tx);
   await sleep(sleepTime);
+  return tx;
+}
 
-  const wrappedMintAta = await getAtaAddress(wrappedMintPda, newOwner.publicKey, false);
-  console.log("wrappedMintAta:", wrappedMintAta);
-  const unwrappedMintVaultAta = await getAtaAddress(unwrappedMint, wrappedMintPda, true);
-  console.log("unwrappedMintVaultAta:", unwrappedMintVaultAta);
-
-  // createTokenizationWrapMint
-  await createTokenizationWrapMint(salt, name, symbol, uri, unwrappedMint, wrappedMintPda, wrappedMintStatePda, unwrappedMintVaultAta);
+async function pause(user: Keypair, wrappedMintPda: PublicKey, wrappedMintStatePda: PublicKey, pauserRolePda: PublicKey) {
+  console.log("pause:");
+  const tx = await TokenizationWrapProgram.methods.pause().accounts
+    (
+      {
+        pauser: user.publicKey,
+        wrappedMint: wrappedMintPda,
+        wrappedMintState: wrappedMintStatePda,
+        pauserRole: pauserRolePda,
+      }
+    ).signers([user]).rpc();
+  console.log("Pause transaction signature:", tx);
   await sleep(sleepTime);
+}
 
-  // addRole
-  // or Uint8Array.of(id) -> [id] , or &[0 as u8] , or new anchor.BN(0).toArrayLike(Buffer)
-  const userWrapperRolePda = await getUserRolePda(wrappedMintPda, newOwner.publicKey, Uint8Array.of(RoleKind.Wrapper));
-  await addRole(newOwner.publicKey,RoleKind.Wrapper, wrappedMintPda, wrappedMintStatePda, userWrapperRolePda);
+async function unpause(wrappedMintPda: PublicKey, wrappedMintStatePda: PublicKey) {
+  console.log("unpause:");
+  const tx = await TokenizationWrapProgram.methods.unpause().accounts
+    (
+      {
+        wrappedMintOwner: provider.wallet.publicKey,
+        wrappedMint: wrappedMintPda,
+        wrappedMintState: wrappedMintStatePda,
+      }
+    ).rpc();
+  console.log("Unpause transaction signature:", tx);
   await sleep(sleepTime);
-  const userWrapperRoleData = await TokenizationWrapProgram.account.roleState.fetch(userWrapperRolePda);
-  console.log("userWrapperRoleData:", userWrapperRoleData);
-  assert.strictEqual(userWrapperRoleData.user.toString(), newOwner.publicKey.toString());
-  assert.strictEqual(userWrapperRoleData.wrappedMint.toString(), wrappedMintPda.toString());
+}
 
-  // tokenizationWrap
-  await sleep(sleepTime);
-  await tokenizationWrap(newOwner, unwrappedMint, wrappedMintPda, wrappedMintStatePda, userWrapperRolePda, unwrappedMintAta, wrappedMintAta, unwrappedMintVaultAta);
+async function pauseMint(user: Keypair, wrappedMintPda: PublicKey, wrappedMintStatePda: PublicKey, pauserRolePda: PublicKey) {
+  console.log("pauseMint:");
+  const tx = await TokenizationWrapProgram.methods.pauseMint().accounts
+    (
+      {
+        pauser: user.publicKey,
+        wrappedMint: wrappedMintPda,
+        wrappedMintState: wrappedMintStatePda,
+        pauserRole: pauserRolePda,
+        wrappedMintAuthority: await getWrappedMintAuthorityPda(wrappedMintPda),
+        tokenProgram: TOKEN_2022_PROGRAM_ID,
+      }
+    ).signers([user]).rpc();
+  console.log("Pause mint transaction signature:", tx);
   await sleep(sleepTime);
+}
 
-  // tokenizationUnwrap
-  await tokenizationUnwrap(newOwner, unwrappedMint, wrappedMintPda, wrappedMintStatePda, userWrapperRolePda, unwrappedMintAta, wrappedMintAta, unwrappedMintVaultAta);
+async function resumeMint(user: Keypair, wrappedMintPda: PublicKey, wrappedMintStatePda: PublicKey, pauserRolePda: PublicKey) {
+  console.log("resumeMint:");
+  const tx = await TokenizationWrapProgram.methods.resumeMint().accounts
+    (
+      {
+        pauser: user.publicKey,
+        wrappedMint: wrappedMintPda,
+        wrappedMintState: wrappedMintStatePda,
+        pauserRole: pauserRolePda,
+        wrappedMintAuthority: await getWrappedMintAuthorityPda(wrappedMintPda),
+        tokenProgram: TOKEN_2022_PROGRAM_ID,
+      }
+    ).signers([user]).rpc();
+  console.log("Resume mint transaction signature:", tx);
   await sleep(sleepTime);
+}
 
-  // closeUnwrappedMintVault
-  await closeUnwrappedMintVault(unwrappedMint, wrappedMintPda, wrappedMintStatePda, unwrappedMintVaultAta);
-  await sleep(sleepTime);
+// raw data of a mint extension, walking the TLV entries after the base mint
+function getExtensionBytes(tlvData: Buffer, extensionType: number): Buffer {
+  let offset = 0;
+  while (offset + 4 <= tlvData.length) {
+    const entryType = tlvData.readUInt16LE(offset);
+    const entryLength = tlvData.readUInt16LE(offset + 2);
+    if (entryType === extensionType) {
+      return tlvData.subarray(offset + 4, offset + 4 + entryLength);
+    }
+    offset += 4 + entryLength;
+  }
+  return null;
+}
 
-  // tokenizationWrap
-  await sleep(sleepTime);
-  await tokenizationWrap(newOwner, unwrappedMint, wrappedMintPda, wrappedMintStatePda, userWrapperRolePda, unwrappedMintAta, wrappedMintAta, unwrappedMintVaultAta);
-  await sleep(sleepTime);
+// authority stored in the first 32 bytes of a mint extension
+async function getExtensionAuthority(wrappedMintPda: PublicKey, extensionType: number) {
+  const mintInfo = await getMint(connection, wrappedMintPda, "confirmed", TOKEN_2022_PROGRAM_ID);
+  return new PublicKey(getExtensionBytes(mintInfo.tlvData, extensionType).subarray(0, 32));
+}
 
-  // tokenizationUnwrap
+async function attachTransferHook(wrappedMintPda: PublicKey, wrappedMintStatePda: PublicKey) {
+  console.log("attachTransferHook:");
+  const [extraAccountMetaListPda] = anchor.web3.PublicKey.findProgramAddressSync(
+    [Buffer.from("extra-account-metas"), wrappedMintPda.toBuffer()],
+    TokenizationHookProgram.programId
+  );
+  const tx = await TokenizationWrapProgram.methods.attachTransferHook().accounts
+    (
+      {
+        wrappedMintOwner: provider.wallet.publicKey,
+        wrappedMint: wrappedMintPda,
+        wrappedMintState: wrappedMintStatePda,
+        wrappedMintAuthority: await getWrappedMintAuthorityPda(wrappedMintPda),
+        extraAccountMetaList: extraAccountMetaListPda,
+        tokenizationHookProgram: TokenizationHookProgram.programId,
+        tokenProgram: TOKEN_2022_PROGRAM_ID,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      }
+    ).rpc();
+  console.log("Attach transfer hook transaction signature:", tx);
+  await sleep(sleepTime);
+}
+
+async function setTransferHook(wrappedMintPda: PublicKey, wrappedMintStatePda: PublicKey, programId: PublicKey | null) {
+  console.log("setTransferHook:");
+  const tx = await TokenizationWrapProgram.methods.setTransferHook(programId).accounts
+    (
+      {
+        wrappedMintOwner: provider.wallet.publicKey,
+        wrappedMint: wrappedMintPda,
+        wrappedMintState: wrappedMintStatePda,
+        wrappedMintAuthority: await getWrappedMintAuthorityPda(wrappedMintPda),
+        tokenProgram: TOKEN_2022_PROGRAM_ID,
+      }
+    ).rpc();
+  console.log("Set transfer hook transaction signature:", tx);
+  await sleep(sleepTime);
+}
+
+async function applyTransferHook(wrappedMintPda: PublicKey, wrappedMintStatePda: PublicKey) {
+  console.log("applyTransferHook:");
+  const tx = await TokenizationWrapProgram.methods.applyTransferHook().accounts
+    (
+      {
+        wrappedMintOwner: provider.wallet.publicKey,
+        wrappedMint: wrappedMintPda,
+        wrappedMintState: wrappedMintStatePda,
+        wrappedMintAuthority: await getWrappedMintAuthorityPda(wrappedMintPda),
+        tokenProgram: TOKEN_2022_PROGRAM_ID,
+      }
+    ).rpc();
+  console.log("Apply transfer hook transaction signature:", tx);
+  await sleep(sleepTime);
+}
+
+async function setTransferHookTimelock(wrappedMintPda: PublicKey, wrappedMintStatePda: PublicKey, timelock: number) {
+  console.log("setTransferHookTimelock:");
+  const tx = await TokenizationWrapProgram.methods.setTransferHookTimelock(new anchor.BN(timelock)).accounts
+    (
+      {
+        wrappedMintOwner: provider.wallet.publicKey,
+        wrappedMint: wrappedMintPda,
+        wrappedMintState: wrappedMintStatePda,
+      }
+    ).rpc();
+  console.log("Set transfer hook timelock transaction signature:", tx);
+  await sleep(sleepTime);
+  return tx;
+}
+
+async function reclaimMintAuthorities(wrappedMintPda: PublicKey, wrappedMintStatePda: PublicKey) {
+  console.log("reclaimMintAuthorities:");
+  const tx = await TokenizationWrapProgram.methods.reclaimMintAuthorities().accounts
+    (
+      {
+        wrappedMintOwner: provider.wallet.publicKey,
+        wrappedMint: wrappedMintPda,
+        wrappedMintState: wrappedMintStatePda,
+        wrappedMintAuthority: await getWrappedMintAuthorityPda(wrappedMintPda),
+        tokenProgram: TOKEN_2022_PROGRAM_ID,
+      }
+    ).rpc();
+  console.log("Reclaim mint authorities transaction signature:", tx);
+  await sleep(sleepTime);
+}
+
+async function setDefaultAccountState(wrappedMintPda: PublicKey, wrappedMintStatePda: PublicKey, frozen: boolean) {
+  console.log("setDefaultAccountState:");
+  const tx = await TokenizationWrapProgram.methods.setDefaultAccountState(frozen).accounts
+    (
+      {
+        wrappedMintOwner: provider.wallet.publicKey,
+        wrappedMint: wrappedMintPda,
+        wrappedMintState: wrappedMintStatePda,
+        wrappedMintAuthority: await getWrappedMintAuthorityPda(wrappedMintPda),
+        tokenProgram: TOKEN_2022_PROGRAM_ID,
+      }
+    ).rpc();
+  console.log("Set default account state transaction signature:", tx);
+  await sleep(sleepTime);
+}
+
+async function approveHolder(approver: Keypair, wrappedMintPda: PublicKey, wrappedMintStatePda: PublicKey, approverRolePda: PublicKey, holderTokenAccount: PublicKey) {
+  console.log("approveHolder:");
+  const tx = await TokenizationWrapProgram.methods.approveHolder().accounts
+    (
+      {
+        kycApprover: approver.publicKey,
+        wrappedMint: wrappedMintPda,
+        wrappedMintState: wrappedMintStatePda,
+        kycApproverRole: approverRolePda,
+        holderTokenAccount: holderTokenAccount,
+        freezeHold: getFreezeHoldPda(holderTokenAccount),
+        wrappedMintAuthority: await getWrappedMintAuthorityPda(wrappedMintPda),
+        tokenProgram: TOKEN_2022_PROGRAM_ID,
+      }
+    ).signers([approver]).rpc();
+  console.log("Approve holder transaction signature:", tx);
+  await sleep(sleepTime);
+}
+
+async function revokeHolder(approver: Keypair, wrappedMintPda: PublicKey, wrappedMintStatePda: PublicKey, approverRolePda: PublicKey, holderTokenAccount: PublicKey) {
+  console.log("revokeHolder:");
+  const tx = await TokenizationWrapProgram.methods.revokeHolder().accounts
+    (
+      {
+        kycApprover: approver.publicKey,
+        wrappedMint: wrappedMintPda,
+        wrappedMintState: wrappedMintStatePda,
+        kycApproverRole: approverRolePda,
+        holderTokenAccount: holderTokenAccount,
+        freezeHold: getFreezeHoldPda(holderTokenAccount),
+        wrappedMintAuthority: await getWrappedMintAuthorityPda(wrappedMintPda),
+        tokenProgram: TOKEN_2022_PROGRAM_ID,
+      }
+    ).signers([approver]).rpc();
+  console.log("Revoke holder transaction signature:", tx);
+  await sleep(sleepTime);
+}
+
+async function freezeAccounts(freezer: Keypair, wrappedMintPda: PublicKey, wrappedMintStatePda: PublicKey, freezerRolePda: PublicKey, tokenAccounts: PublicKey[], frozen: boolean) {
+  console.log(frozen ? "freezeAccounts:" : "thawAccounts:");
+  const method = frozen ? TokenizationWrapProgram.methods.freezeAccounts() : TokenizationWrapProgram.methods.thawAccounts();
+  const tx = await method.accounts
+    (
+      {
+        freezer: freezer.publicKey,
+        wrappedMint: wrappedMintPda,
+        wrappedMintState: wrappedMintStatePda,
+        freezerRole: freezerRolePda,
+        wrappedMintAuthority: await getWrappedMintAuthorityPda(wrappedMintPda),
+        tokenProgram: TOKEN_2022_PROGRAM_ID,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      }
+    ).remainingAccounts(tokenAccounts.flatMap((tokenAccount) => [
+      { pubkey: tokenAccount, isSigner: false, isWritable: true },
+      { pubkey: getFreezeHoldPda(tokenAccount), isSigner: false, isWritable: true },
+    ]))
+    .signers([freezer]).rpc();
+  console.log("Freeze accounts transaction signature:", tx);
+  await sleep(sleepTime);
+}
+
+async function forceTransfer(recoverer: Keypair, wrappedMintPda: PublicKey, wrappedMintStatePda: PublicKey, recovererRolePda: PublicKey, fromTokenAccount: PublicKey, toTokenAccount: PublicKey, amount: anchor.BN, reasonCode: number, hookAccounts: anchor.web3.AccountMeta[]) {
+  console.log("forceTransfer:");
+  const caseReference = createHash("sha256").update("case-0001").digest();
+  const tx = await TokenizationWrapProgram.methods.forceTransfer(amount, reasonCode, [...caseReference]).accounts
+    (
+      {
+        recoverer: recoverer.publicKey,
+        wrappedMint: wrappedMintPda,
+        wrappedMintState: wrappedMintStatePda,
+        recovererRole: recovererRolePda,
+        fromTokenAccount: fromTokenAccount,
+        toTokenAccount: toTokenAccount,
+        wrappedMintAuthority: await getWrappedMintAuthorityPda(wrappedMintPda),
+        tokenProgram: TOKEN_2022_PROGRAM_ID,
+      }
+    ).remainingAccounts(hookAccounts).signers([recoverer]).rpc();
+  console.log("Force transfer transaction signature:", tx);
+  await sleep(sleepTime);
+}
+
+// plain Token-2022 transfer of wrapped tokens signed by the holder, runs the transfer hook
+async function transferWrapped(owner: Keypair, wrappedMintPda: PublicKey, fromTokenAccount: PublicKey, toTokenAccount: PublicKey, amount: anchor.BN, hookAccounts: anchor.web3.AccountMeta[]) {
+  console.log("transferWrapped:");
+  const transferIx = createTransferCheckedInstruction(fromTokenAccount, wrappedMintPda, toTokenAccount, owner.publicKey, BigInt(amount.toString()), decimals, [], TOKEN_2022_PROGRAM_ID);
+  transferIx.keys.push(...hookAccounts);
+  const tx = await provider.sendAndConfirm(new anchor.web3.Transaction().add(transferIx), [owner]);
+  console.log("Transfer transaction signature:", tx);
+  await sleep(sleepTime);
+}
+
+// signed by the wrapped mint owner, a Salvager passes its role account instead of null
+async function forceUnwrap(unwrappedMint: PublicKey, wrappedMintPda: PublicKey, wrappedMintStatePda: PublicKey, holderTokenAccount: PublicKey, destinationTokenAccount: PublicKey, destinationOwner: PublicKey, unwrappedMintVault: PublicKey, amount: anchor.BN) {
+  console.log("forceUnwrap:");
+  const tx = await TokenizationWrapProgram.methods.forceUnwrap(amount).accounts
+    (
+      {
+        authority: provider.wallet.publicKey,
+        unwrappedMint: unwrappedMint,
+        wrappedMint: wrappedMintPda,
+        wrappedMintState: wrappedMintStatePda,
+        salvagerRole: null,
+        holderTokenAccount: holderTokenAccount,
+        destinationTokenAccount: destinationTokenAccount,
+        destinationAccessList: await getListPda(ACCESS_LIST_SEED, wrappedMintPda, destinationOwner),
+        destinationBlockList: await getListPda(BLOCK_LIST_SEED, wrappedMintPda, destinationOwner),
+        unwrappedMintVault: unwrappedMintVault,
+        wrappedMintAuthority: await getWrappedMintAuthorityPda(wrappedMintPda),
+        unwrappedTokenProgram: TOKEN_2022_PROGRAM_ID,
+        tokenProgram: TOKEN_2022_PROGRAM_ID,
+      }
+    ).rpc();
+  console.log("Force unwrap transaction signature:", tx);
+  await sleep(sleepTime);
+}
+
+async function updateMetadataField(manager: Keypair, wrappedMintPda: PublicKey, wrappedMintStatePda: PublicKey, managerRolePda: PublicKey, field: string, value: string) {
+  console.log("updateMetadataField:");
+  const tx = await TokenizationWrapProgram.methods.updateMetadataField(field, value).accounts
+    (
+      {
+        metadataManager: manager.publicKey,
+        wrappedMint: wrappedMintPda,
+        wrappedMintState: wrappedMintStatePda,
+        metadataManagerRole: managerRolePda,
+        wrappedMintAuthority: await getWrappedMintAuthorityPda(wrappedMintPda),
+        tokenProgram: TOKEN_2022_PROGRAM_ID,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      }
+    ).signers([manager]).rpc();
+  console.log("Update metadata field transaction signature:", tx);
+  await sleep(sleepTime);
+}
+
+async function removeMetadataKey(manager: Keypair, wrappedMintPda: PublicKey, wrappedMintStatePda: PublicKey, managerRolePda: PublicKey, key: string) {
+  console.log("removeMetadataKey:");
+  const tx = await TokenizationWrapProgram.methods.removeMetadataKey(key, false).accounts
+    (
+      {
+        metadataManager: manager.publicKey,
+        wrappedMint: wrappedMintPda,
+        wrappedMintState: wrappedMintStatePda,
+        metadataManagerRole: managerRolePda,
+        wrappedMintAuthority: await getWrappedMintAuthorityPda(wrappedMintPda),
+        tokenProgram: TOKEN_2022_PROGRAM_ID,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      }
+    ).signers([manager]).rpc();
+  console.log("Remove metadata key transaction signature:", tx);
+  await sleep(sleepTime);
+}
+
+async function updateMetadataAuthority(manager: Keypair, wrappedMintPda: PublicKey, wrappedMintStatePda: PublicKey, managerRolePda: PublicKey, newAuthority: PublicKey | null) {
+  console.log("updateMetadataAuthority:");
+  const tx = await TokenizationWrapProgram.methods.updateMetadataAuthority(newAuthority).accounts
+    (
+      {
+        metadataManager: manager.publicKey,
+        wrappedMint: wrappedMintPda,
+        wrappedMintState: wrappedMintStatePda,
+        metadataManagerRole: managerRolePda,
+        wrappedMintAuthority: await getWrappedMintAuthorityPda(wrappedMintPda),
+        tokenProgram: TOKEN_2022_PROGRAM_ID,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      }
+    ).signers([manager]).rpc();
+  console.log("Update metadata authority transaction signature:", tx);
+  await sleep(sleepTime);
+}
+
+async function setConfidentialAuditor(wrappedMintPda: PublicKey, wrappedMintStatePda: PublicKey, auditorElgamalPubkey: number[] | null) {
+  console.log("setConfidentialAuditor:");
+  const tx = await TokenizationWrapProgram.methods.setConfidentialAuditor(auditorElgamalPubkey).accounts
+    (
+      {
+        wrappedMintOwner: provider.wallet.publicKey,
+        wrappedMint: wrappedMintPda,
+        wrappedMintState: wrappedMintStatePda,
+        wrappedMintAuthority: await getWrappedMintAuthorityPda(wrappedMintPda),
+        tokenProgram: TOKEN_2022_PROGRAM_ID,
+      }
+    ).rpc();
+  console.log("Set confidential auditor transaction signature:", tx);
+  await sleep(sleepTime);
+}
+
+async function setConfidentialAutoApprove(wrappedMintPda: PublicKey, wrappedMintStatePda: PublicKey, autoApprove: boolean) {
+  console.log("setConfidentialAutoApprove:");
+  const tx = await TokenizationWrapProgram.methods.setConfidentialAutoApprove(autoApprove).accounts
+    (
+      {
+        wrappedMintOwner: provider.wallet.publicKey,
+        wrappedMint: wrappedMintPda,
+        wrappedMintState: wrappedMintStatePda,
+        wrappedMintAuthority: await getWrappedMintAuthorityPda(wrappedMintPda),
+        tokenProgram: TOKEN_2022_PROGRAM_ID,
+      }
+    ).rpc();
+  console.log("Set confidential auto approve transaction signature:", tx);
+  await sleep(sleepTime);
+}
+
+// the holder account must already be configured for confidential transfers
+async function approveConfidentialAccount(compliance: Keypair, wrappedMintPda: PublicKey, wrappedMintStatePda: PublicKey, complianceRolePda: PublicKey, holderTokenAccount: PublicKey) {
+  console.log("approveConfidentialAccount:");
+  const tx = await TokenizationWrapProgram.methods.approveConfidentialAccount().accounts
+    (
+      {
+        compliance: compliance.publicKey,
+        wrappedMint: wrappedMintPda,
+        wrappedMintState: wrappedMintStatePda,
+        complianceRole: complianceRolePda,
+        holderTokenAccount: holderTokenAccount,
+        wrappedMintAuthority: await getWrappedMintAuthorityPda(wrappedMintPda),
+        tokenProgram: TOKEN_2022_PROGRAM_ID,
+      }
+    ).signers([compliance]).rpc();
+  console.log("Approve confidential account transaction signature:", tx);
+  await sleep(sleepTime);
+}
+
+async function updateMultiplier(corporateActions: Keypair, wrappedMintPda: PublicKey, wrappedMintStatePda: PublicKey, corporateActionsRolePda: PublicKey, newMultiplier: number, effectiveTimestamp: number) {
+  console.log("updateMultiplier:");
+  const tx = await TokenizationWrapProgram.methods.updateMultiplier(newMultiplier, new anchor.BN(effectiveTimestamp)).accounts
+    (
+      {
+        corporateActions: corporateActions.publicKey,
+        wrappedMint: wrappedMintPda,
+        wrappedMintState: wrappedMintStatePda,
+        corporateActionsRole: corporateActionsRolePda,
+        wrappedMintAuthority: await getWrappedMintAuthorityPda(wrappedMintPda),
+        tokenProgram: TOKEN_2022_PROGRAM_ID,
+      }
+    ).signers([corporateActions]).rpc();
+  console.log("Update multiplier transaction signature:", tx);
+  await sleep(sleepTime);
+}
+
+async function setMultiplierBound(wrappedMintPda: PublicKey, wrappedMintStatePda: PublicKey, maxChange: number) {
+  console.log("setMultiplierBound:");
+  const tx = await TokenizationWrapProgram.methods.setMultiplierBound(maxChange).accounts
+    (
+      {
+        wrappedMintOwner: newOwner.publicKey,
+        wrappedMint: wrappedMintPda,
+        wrappedMintState: wrappedMintStatePda,
+      }
+    ).signers([newOwner]).rpc();
+  console.log("Set multiplier bound transaction signature:", tx);
+  await sleep(sleepTime);
+  return tx;
+}
+
+async function tokenizationWrap(user: Keypair, unwrappedMint: PublicKey, wrappedMintPda: PublicKey, wrappedMintStatePda: PublicKey, userRolePda: PublicKey, unwrappedMintAta: PublicKey, wrappedMintAta: PublicKey, unwrappedMintVault: PublicKey, unwrappedTokenProgram: PublicKey = TOKEN_2022_PROGRAM_ID, hookAccounts: anchor.web3.AccountMeta[] = [], amount: anchor.BN = wrapAmountBN) {
+  // hookAccounts are the extra accounts of the unwrapped mint's transfer hook, if any
+  console.log("tokenizationWrap:");
+  const tx = await TokenizationWrapProgram.methods.wrap(amount).accounts
+    (
+      {
+        user: user.publicKey,
+        unwrappedMint: unwrappedMint,
+        wrappedMint: wrappedMintPda,
+        wrappedMintState: wrappedMintStatePda,
+        userRole: userRolePda,
+        userAccessList: await getListPda(ACCESS_LIST_SEED, wrappedMintPda, user.publicKey),
+        userBlockList: await getListPda(BLOCK_LIST_SEED, wrappedMintPda, user.publicKey),
+        unwrappedMintAccount: unwrappedMintAta,
+        wrappedMintAccount: wrappedMintAta,
+        unwrappedMintVault: unwrappedMintVault,
+        unwrappedTokenProgram: unwrappedTokenProgram,
+        tokenProgram: TOKEN_2022_PROGRAM_ID,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      }
+    ).remainingAccounts(hookAccounts).signers([user]).rpc();
+  console.log("Wrap transaction signature:", tx);
+  await sleep(sleepTime);
+  return tx;
+}
+
+async function tokenizationUnwrap(user: Keypair, unwrappedMint: PublicKey, wrappedMintPda: PublicKey, wrappedMintStatePda: PublicKey, userRolePda: PublicKey, unwrappedMintAta: PublicKey, wrappedMintAta: PublicKey, unwrappedMintVault: PublicKey, unwrappedTokenProgram: PublicKey = TOKEN_2022_PROGRAM_ID, hookAccounts: anchor.web3.AccountMeta[] = [], amount: anchor.BN = wrapAmountBN) {
+  // hookAccounts are the extra accounts of the unwrapped mint's transfer hook, if any
+  console.log("tokenizationUnwrap:");
+  const tx = await TokenizationWrapProgram.methods.unwrap(amount).accounts
+    (
+      {
+        user: user.publicKey,
+        unwrappedMint: unwrappedMint,
+        wrappedMint: wrappedMintPda,
+        wrappedMintState: wrappedMintStatePda,
+        userRole: userRolePda,
+        userAccessList: await getListPda(ACCESS_LIST_SEED, wrappedMintPda, user.publicKey),
+        userBlockList: await getListPda(BLOCK_LIST_SEED, wrappedMintPda, user.publicKey),
+        unwrappedMintAccount: unwrappedMintAta,
+        wrappedMintAccount: wrappedMintAta,
+        unwrappedMintVault: unwrappedMintVault,
+        unwrappedTokenProgram: unwrappedTokenProgram,
+        tokenProgram: TOKEN_2022_PROGRAM_ID,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      }
+    ).remainingAccounts(hookAccounts).signers([user]).rpc();
+  console.log("Unwrap transaction signature:", tx);
+  await sleep(sleepTime);
+  return tx;
+}
+
+async function closeUnwrappedMintVault(unwrappedMint: PublicKey, wrappedMintPda: PublicKey, wrappedMintStatePda: PublicKey, unwrappedMintVault: PublicKey, unwrappedTokenProgram: PublicKey = TOKEN_2022_PROGRAM_ID) {
+  console.log("closeUnwrappedMintVault:");
+  const tx = await TokenizationWrapProgram.methods.closeVault().accounts
+    (
+      {
+        wrappedMintOwner: provider.wallet.publicKey,
+        unwrappedMint: unwrappedMint,
+        wrappedMint: wrappedMintPda,
+        wrappedMintState: wrappedMintStatePda,
+        unwrappedMintVault: unwrappedMintVault,
+        unwrappedTokenProgram: unwrappedTokenProgram,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+      }
+    ).rpc();
+  console.log("Close unwrapped mint vault transaction signature:", tx);
+  await sleep(sleepTime);
+}
+
+async function transferOwnership(wrappedMintPda: PublicKey, wrappedMintStatePda: PublicKey) {
+
+  //if not deployer, should throw UnauthorizedInitializer
+  try {
+    await TokenizationWrapProgram.methods.transferOwnership(newOwner.publicKey).accounts
+      (
+        {
+          wrappedMintOwner: newOwner.publicKey,
+          wrappedMint: wrappedMintPda,
+          wrappedMintState: wrappedMintStatePda,
+          wrappedMintAuthority: await getWrappedMintAuthorityPda(wrappedMintPda),
+          tokenProgram: TOKEN_2022_PROGRAM_ID,
+        }
+      ).signers([newOwner]).rpc();
+  } catch (_err) {
+    assert.isTrue(_err instanceof AnchorError);
+    const err: AnchorError = _err;
+    assert.strictEqual(err.error.errorCode.code, "NotOwner");
+    assert.strictEqual(
+      err.error.errorMessage,
+      "Not owner"
+    );
+  }
+  console.log("transferOwnership:");
+  const tx = await TokenizationWrapProgram.methods.transferOwnership(newOwner.publicKey).accounts
+    (
+      {
+        wrappedMintOwner: provider.wallet.publicKey,
+        wrappedMint: wrappedMintPda,
+        wrappedMintState: wrappedMintStatePda,
+        wrappedMintAuthority: await getWrappedMintAuthorityPda(wrappedMintPda),
+        tokenProgram: TOKEN_2022_PROGRAM_ID,
+      }
+    ).rpc();
+  console.log("Transfer ownership transaction signature:", tx);
+  await sleep(sleepTime);
+}
+
+async function acceptOwnership(user: Keypair, wrappedMintPda: PublicKey, wrappedMintStatePda: PublicKey) {
+
+  //if not deployer, should throw UnauthorizedInitializer
+  try {
+    await TokenizationWrapProgram.methods.acceptOwnership().accounts
+      (
+        {
+          wrappedMintPendingOwner: provider.wallet.publicKey,
+          wrappedMint: wrappedMintPda,
+          wrappedMintState: wrappedMintStatePda,
+          wrappedMintAuthority: await getWrappedMintAuthorityPda(wrappedMintPda),
+          tokenProgram: TOKEN_2022_PROGRAM_ID,
+        }
+      ).rpc();
+  } catch (_err) {
+    assert.isTrue(_err instanceof AnchorError);
+    const err: AnchorError = _err;
+    assert.strictEqual(err.error.errorCode.code, "NotPendingOwner");
+    assert.strictEqual(
+      err.error.errorMessage,
+      "Not pending owner"
+    );
+  }
+  await sleep(sleepTime);
+
+  console.log("acceptOwnership:");
+  const tx = await TokenizationWrapProgram.methods.acceptOwnership().accounts
+    (
+      {
+        wrappedMintPendingOwner: user.publicKey,
+        wrappedMint: wrappedMintPda,
+        wrappedMintState: wrappedMintStatePda,
+        wrappedMintAuthority: await getWrappedMintAuthorityPda(wrappedMintPda),
+        tokenProgram: TOKEN_2022_PROGRAM_ID,
+      }
+    ).signers([user]).rpc();
+  console.log("Accept ownership transaction signature:", tx);
+  await sleep(sleepTime);
+}
+
+async function recoverMint(user: Keypair, unwrappedMint: PublicKey, wrappedMintPda: PublicKey, wrappedMintStatePda: PublicKey, wrappedMintAta: PublicKey, unwrappedMintVault: PublicKey, unwrappedTokenProgram: PublicKey = TOKEN_2022_PROGRAM_ID) {
+  console.log("recoverMint:");
+  const tx = await TokenizationWrapProgram.methods.recoverMint().accounts
+    (
+      {
+        wrappedMintOwner: user.publicKey,
+        unwrappedMint: unwrappedMint,
+        wrappedMint: wrappedMintPda,
+        wrappedMintState: wrappedMintStatePda,
+        wrappedMintAccount: wrappedMintAta,
+        unwrappedMintVault: unwrappedMintVault,
+        unwrappedTokenProgram: unwrappedTokenProgram,
+        tokenProgram: TOKEN_2022_PROGRAM_ID,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      }
+    ).signers([user]).rpc();
+  console.log("Recover mint transaction signature:", tx);
+  await sleep(sleepTime);
+}
+
+async function migrateMintState(unwrappedMint: PublicKey, wrappedMintPda: PublicKey, wrappedMintStatePda: PublicKey) {
+  console.log("migrateMintState:");
+  const tx = await TokenizationWrapProgram.methods.migrateMintState().accounts(
+    {
+      payer: provider.wallet.publicKey,
+      wrappedMint: wrappedMintPda,
+      unwrappedMint: unwrappedMint,
+      wrappedMintState: wrappedMintStatePda,
+      systemProgram: anchor.web3.SystemProgram.programId,
+    }
+  ).rpc();
+  console.log("Migrate mint state transaction signature:", tx);
+  await sleep(sleepTime);
+}
+
+async function migrateRole(userRolePda: PublicKey) {
+  console.log("migrateRole:");
+  const tx = await TokenizationWrapProgram.methods.migrateRole().accounts(
+    {
+      payer: provider.wallet.publicKey,
+      userRole: userRolePda,
+      systemProgram: anchor.web3.SystemProgram.programId,
+    }
+  ).rpc();
+  console.log("Migrate role transaction signature:", tx);
+  await sleep(sleepTime);
+}
+
+function loadFixtureKeypair(filename: string) {
+  return Keypair.fromSecretKey(Uint8Array.from(JSON.parse(readFileSync(`tests/fixtures/${filename}`, "utf8"))));
+}
+
+// first event of the given name the tokenization wrap program emitted in the transaction
+async function getProgramEvent(tx: string, eventName: string) {
+  const transaction = await connection.getTransaction(tx, { commitment: "confirmed", maxSupportedTransactionVersion: 0 });
+  const eventParser = new anchor.EventParser(TokenizationWrapProgram.programId, TokenizationWrapProgram.coder);
+  for (const event of eventParser.parseLogs(transaction.meta.logMessages)) {
+    if (event.name === eventName) {
+      return event.data;
+    }
+  }
+  return null;
+}
+
+function sleep(ms: number) {
+  return new Promise(resolve => setTimeout(resolve, ms));
+}
+
+async function main() {
+  await airdrop();
+  await sleep(sleepTime);
+  const { unwrappedMint, unwrappedMintAta } = await createUnwrappedMintAndAta(newOwner, wrapAmount);
+  await sleep(sleepTime);
+  const wrappedMintPda = await getWrappedMintPda(unwrappedMint, salt);
+  const wrappedMintStatePda = await getWrappedMintStatePda(wrappedMintPda);
+  await sleep(sleepTime);
+
+  const wrappedMintAta = await getAtaAddress(wrappedMintPda, newOwner.publicKey, false);
+  console.log("wrappedMintAta:", wrappedMintAta);
+  const unwrappedMintVaultAta = await getAtaAddress(unwrappedMint, wrappedMintPda, true);
+  console.log("unwrappedMintVaultAta:", unwrappedMintVaultAta);
+
+  // getUnderlyingRiskProfile, the test unwrapped mint has no freeze authority and no extensions
+  const riskProfile = await TokenizationWrapProgram.methods.getUnderlyingRiskProfile().accounts({ unwrappedMint: unwrappedMint }).view();
+  assert.strictEqual(riskProfile.riskFlags, 0);
+
+  // createTokenizationWrapMint, with the structured RWA metadata of the offering
+  const offeringDocumentHash = createHash("sha256").update("offering-memorandum.pdf").digest("hex");
+  const additionalMetadata = [
+    { key: "isin", value: "US0000000000" },
+    { key: "issuer", value: "Example Issuer Ltd." },
+    { key: "jurisdiction", value: "KY" },
+    { key: "offering_document_hash", value: offeringDocumentHash },
+  ];
+  await createTokenizationWrapMint(salt, name, symbol, uri, unwrappedMint, wrappedMintPda, wrappedMintStatePda, unwrappedMintVaultAta, false, TOKEN_2022_PROGRAM_ID, defaultMintConfig, additionalMetadata);
+  const createdMetadata = await getTokenMetadata(connection, wrappedMintPda, "confirmed", TOKEN_2022_PROGRAM_ID);
+  assert.deepEqual(createdMetadata.additionalMetadata, additionalMetadata.map((entry) => [entry.key, entry.value]));
+  await sleep(sleepTime);
+  const wrappedMintStateData = await TokenizationWrapProgram.account.wrappedMintState.fetch(wrappedMintStatePda);
+  assert.strictEqual(wrappedMintStateData.underlyingRiskFlags, 0);
+  assert.strictEqual(wrappedMintStateData.mintConfig.confidentialTransfer, true);
+
+  // in owner mode the owner holds the transfer hook authority, a timelock would mean nothing
+  try {
+    await setTransferHookTimelock(wrappedMintPda, wrappedMintStatePda, 3600);
+    assert.fail("set a transfer hook timelock in owner mode");
+  } catch (_err) {
+    assert.isTrue(_err instanceof AnchorError);
+    const err: AnchorError = _err;
+    assert.strictEqual(err.error.errorCode.code, "InvalidMintAuthorityMode");
+  }
+
+  // addRole
+  // or Uint8Array.of(id) -> [id] , or &[0 as u8] , or new anchor.BN(0).toArrayLike(Buffer)
+  const userWrapperRolePda = await getUserRolePda(wrappedMintPda, newOwner.publicKey, Uint8Array.of(RoleKind.Wrapper));
+  await addRole(newOwner.publicKey,RoleKind.Wrapper, wrappedMintPda, wrappedMintStatePda, userWrapperRolePda);
+  await sleep(sleepTime);
+  const userWrapperRoleData = await TokenizationWrapProgram.account.roleState.fetch(userWrapperRolePda);
+  console.log("userWrapperRoleData:", userWrapperRoleData);
+  assert.strictEqual(userWrapperRoleData.user.toString(), newOwner.publicKey.toString());
+  assert.strictEqual(userWrapperRoleData.wrappedMint.toString(), wrappedMintPda.toString());
+
+  // tokenizationWrap
+  await sleep(sleepTime);
+  await tokenizationWrap(newOwner, unwrappedMint, wrappedMintPda, wrappedMintStatePda, userWrapperRolePda, unwrappedMintAta, wrappedMintAta, unwrappedMintVaultAta);
+  await sleep(sleepTime);
+
+  // tokenizationUnwrap
+  await tokenizationUnwrap(newOwner, unwrappedMint, wrappedMintPda, wrappedMintStatePda, userWrapperRolePda, unwrappedMintAta, wrappedMintAta, unwrappedMintVaultAta);
+  await sleep(sleepTime);
+
+  // allowance and cumulative totals of the wrapper
+  const wrapperTotals = await TokenizationWrapProgram.account.roleState.fetch(userWrapperRolePda);
+  assert.strictEqual(wrapperTotals.totalWrapped.toString(), wrapAmount.toString());
+  assert.strictEqual(wrapperTotals.totalUnwrapped.toString(), wrapAmount.toString());
+  await setAllowance(newOwner.publicKey, RoleKind.Wrapper, wrappedMintPda, wrappedMintStatePda, userWrapperRolePda, wrapAmountBN.subn(1));
+  try {
+    await tokenizationWrap(newOwner, unwrappedMint, wrappedMintPda, wrappedMintStatePda, userWrapperRolePda, unwrappedMintAta, wrappedMintAta, unwrappedMintVaultAta);
+    assert.fail("wrap beyond the allowance");
+  } catch (_err) {
+    assert.isTrue(_err instanceof AnchorError);
+    const err: AnchorError = _err;
+    assert.strictEqual(err.error.errorCode.code, "AllowanceExceeded");
+  }
+  await setAllowance(newOwner.publicKey, RoleKind.Wrapper, wrappedMintPda, wrappedMintStatePda, userWrapperRolePda, wrapAmountBN);
+
+  // wrap only and unwrap only roles
+  const userWrapOnlyRolePda = await getUserRolePda(wrappedMintPda, newOwner.publicKey, Uint8Array.of(RoleKind.WrapOnly));
+  await addRole(newOwner.publicKey, RoleKind.WrapOnly, wrappedMintPda, wrappedMintStatePda, userWrapOnlyRolePda);
+  const userUnwrapOnlyRolePda = await getUserRolePda(wrappedMintPda, newOwner.publicKey, Uint8Array.of(RoleKind.UnwrapOnly));
+  await addRole(newOwner.publicKey, RoleKind.UnwrapOnly, wrappedMintPda, wrappedMintStatePda, userUnwrapOnlyRolePda);
+  await tokenizationWrap(newOwner, unwrappedMint, wrappedMintPda, wrappedMintStatePda, userWrapOnlyRolePda, unwrappedMintAta, wrappedMintAta, unwrappedMintVaultAta);
+  try {
+    await tokenizationUnwrap(newOwner, unwrappedMint, wrappedMintPda, wrappedMintStatePda, userWrapOnlyRolePda, unwrappedMintAta, wrappedMintAta, unwrappedMintVaultAta);
+    assert.fail("unwrap with a wrap only role");
+  } catch (_err) {
+    assert.isTrue(_err instanceof AnchorError);
+    const err: AnchorError = _err;
+    assert.strictEqual(err.error.errorCode.code, "Unauthorized");
+  }
+  await tokenizationUnwrap(newOwner, unwrappedMint, wrappedMintPda, wrappedMintStatePda, userUnwrapOnlyRolePda, unwrappedMintAta, wrappedMintAta, unwrappedMintVaultAta);
+  // the capped wrapper unwraps tokens it didn't wrap, its allowance stays the cap
+  await tokenizationWrap(newOwner, unwrappedMint, wrappedMintPda, wrappedMintStatePda, userWrapOnlyRolePda, unwrappedMintAta, wrappedMintAta, unwrappedMintVaultAta);
+  await tokenizationUnwrap(newOwner, unwrappedMint, wrappedMintPda, wrappedMintStatePda, userWrapperRolePda, unwrappedMintAta, wrappedMintAta, unwrappedMintVaultAta);
+  const cappedWrapperRoleData = await TokenizationWrapProgram.account.roleState.fetch(userWrapperRolePda);
+  assert.strictEqual(cappedWrapperRoleData.allowance.toString(), wrapAmount.toString());
+  assert.strictEqual(cappedWrapperRoleData.outstanding.toString(), "0");
+  await tokenizationWrap(newOwner, unwrappedMint, wrappedMintPda, wrappedMintStatePda, userWrapperRolePda, unwrappedMintAta, wrappedMintAta, unwrappedMintVaultAta);
+  assert.strictEqual((await TokenizationWrapProgram.account.roleState.fetch(userWrapperRolePda)).outstanding.toString(), wrapAmount.toString());
+  try {
+    await tokenizationWrap(newOwner, unwrappedMint, wrappedMintPda, wrappedMintStatePda, userWrapperRolePda, unwrappedMintAta, wrappedMintAta, unwrappedMintVaultAta);
+    assert.fail("wrap beyond the cap after unwrapping tokens the wrapper didn't wrap");
+  } catch (_err) {
+    assert.isTrue(_err instanceof AnchorError);
+    const err: AnchorError = _err;
+    assert.strictEqual(err.error.errorCode.code, "AllowanceExceeded");
+  }
+  await tokenizationUnwrap(newOwner, unwrappedMint, wrappedMintPda, wrappedMintStatePda, userWrapperRolePda, unwrappedMintAta, wrappedMintAta, unwrappedMintVaultAta);
+  await setAllowance(newOwner.publicKey, RoleKind.Wrapper, wrappedMintPda, wrappedMintStatePda, userWrapperRolePda, new anchor.BN("18446744073709551615"));
+  await removeRole(newOwner.publicKey, RoleKind.WrapOnly, wrappedMintPda, wrappedMintStatePda, userWrapOnlyRolePda);
+  await removeRole(newOwner.publicKey, RoleKind.UnwrapOnly, wrappedMintPda, wrappedMintStatePda, userUnwrapOnlyRolePda);
+
+  // time-bound role, not active yet, then expired and closed by anyone
+  await addRole(newOwner.publicKey, RoleKind.WrapOnly, wrappedMintPda, wrappedMintStatePda, userWrapOnlyRolePda);
+  const now = Math.floor(Date.now() / 1000);
+  await setRoleValidity(newOwner.publicKey, RoleKind.WrapOnly, wrappedMintPda, wrappedMintStatePda, userWrapOnlyRolePda, now + 3600, 0);
+  try {
+    await tokenizationWrap(newOwner, unwrappedMint, wrappedMintPda, wrappedMintStatePda, userWrapOnlyRolePda, unwrappedMintAta, wrappedMintAta, unwrappedMintVaultAta);
+    assert.fail("wrap before the role is valid");
+  } catch (_err) {
+    assert.isTrue(_err instanceof AnchorError);
+    const err: AnchorError = _err;
+    assert.strictEqual(err.error.errorCode.code, "Unauthorized");
+  }
+  try {
+    await closeExpiredRole(newOwner.publicKey, RoleKind.WrapOnly, wrappedMintPda, wrappedMintStatePda, userWrapOnlyRolePda);
+    assert.fail("close a role that has not expired");
+  } catch (_err) {
+    assert.isTrue(_err instanceof AnchorError);
+    const err: AnchorError = _err;
+    assert.strictEqual(err.error.errorCode.code, "RoleNotExpired");
+  }
+  await setRoleValidity(newOwner.publicKey, RoleKind.WrapOnly, wrappedMintPda, wrappedMintStatePda, userWrapOnlyRolePda, 0, now - 60);
+  try {
+    await tokenizationWrap(newOwner, unwrappedMint, wrappedMintPda, wrappedMintStatePda, userWrapOnlyRolePda, unwrappedMintAta, wrappedMintAta, unwrappedMintVaultAta);
+    assert.fail("wrap after the role expired");
+  } catch (_err) {
+    assert.isTrue(_err instanceof AnchorError);
+    const err: AnchorError = _err;
+    assert.strictEqual(err.error.errorCode.code, "Unauthorized");
+  }
+  await closeExpiredRole(newOwner.publicKey, RoleKind.WrapOnly, wrappedMintPda, wrappedMintStatePda, userWrapOnlyRolePda);
+  assert.isNull(await connection.getAccountInfo(userWrapOnlyRolePda));
+
+  // batch grant, rotation of one wrapper to a new key, batch revoke
+  const firstWrapper = Keypair.generate().publicKey;
+  const secondWrapper = Keypair.generate().publicKey;
+  const rotatedWrapper = Keypair.generate().publicKey;
+  await addRoles([{ user: firstWrapper, role: RoleKind.Wrapper }, { user: secondWrapper, role: RoleKind.Wrapper }], wrappedMintPda, wrappedMintStatePda);
+  const firstWrapperRolePda = await getUserRolePda(wrappedMintPda, firstWrapper, Uint8Array.of(RoleKind.Wrapper));
+  const secondWrapperRolePda = await getUserRolePda(wrappedMintPda, secondWrapper, Uint8Array.of(RoleKind.Wrapper));
+  const rotatedWrapperRolePda = await getUserRolePda(wrappedMintPda, rotatedWrapper, Uint8Array.of(RoleKind.Wrapper));
+  assert.strictEqual((await TokenizationWrapProgram.account.roleState.fetch(secondWrapperRolePda)).user.toString(), secondWrapper.toString());
+  await setAllowance(firstWrapper, RoleKind.Wrapper, wrappedMintPda, wrappedMintStatePda, firstWrapperRolePda, wrapAmountBN);
+  await replaceRoleHolder(firstWrapper, rotatedWrapper, RoleKind.Wrapper, wrappedMintPda, wrappedMintStatePda);
+  assert.isNull(await connection.getAccountInfo(firstWrapperRolePda));
+  const rotatedWrapperRoleData = await TokenizationWrapProgram.account.roleState.fetch(rotatedWrapperRolePda);
+  assert.strictEqual(rotatedWrapperRoleData.user.toString(), rotatedWrapper.toString());
+  assert.strictEqual(rotatedWrapperRoleData.allowance.toString(), wrapAmount.toString());
+  await removeRoles([{ user: secondWrapper, role: RoleKind.Wrapper }, { user: rotatedWrapper, role: RoleKind.Wrapper }], wrappedMintPda, wrappedMintStatePda);
+  assert.isNull(await connection.getAccountInfo(secondWrapperRolePda));
+  assert.isNull(await connection.getAccountInfo(rotatedWrapperRolePda));
+
+  // role rate limits, the wrapper may wrap at most half of wrapAmount per hour
+  await setRateLimits(wrappedMintPda, wrappedMintStatePda, 3600, new anchor.BN(0), new anchor.BN(0), new anchor.BN(0));
+  const roleRateLimitsTx = await setRoleRateLimits(newOwner.publicKey, RoleKind.Wrapper, wrappedMintPda, wrappedMintStatePda, userWrapperRolePda, wrapAmountBN.divn(2), new anchor.BN(0));
+  const roleRateLimitsEvent = await getProgramEvent(roleRateLimitsTx, "roleRateLimitsUpdatedEvent");
+  assert.strictEqual(roleRateLimitsEvent.user.toString(), newOwner.publicKey.toString());
+  assert.strictEqual(roleRateLimitsEvent.maxWrapAmount.toString(), wrapAmountBN.divn(2).toString());
+  assert.strictEqual(roleRateLimitsEvent.maxUnwrapAmount.toString(), "0");
+  assert.strictEqual((await TokenizationWrapProgram.account.roleState.fetch(userWrapperRolePda)).maxWrapAmount.toString(), wrapAmountBN.divn(2).toString());
+  try {
+    await tokenizationWrap(newOwner, unwrappedMint, wrappedMintPda, wrappedMintStatePda, userWrapperRolePda, unwrappedMintAta, wrappedMintAta, unwrappedMintVaultAta);
+    assert.fail("wrap beyond the role rate limit");
+  } catch (_err) {
+    assert.isTrue(_err instanceof AnchorError);
+    const err: AnchorError = _err;
+    assert.strictEqual(err.error.errorCode.code, "RateLimitExceeded");
+  }
+  await setRoleRateLimits(newOwner.publicKey, RoleKind.Wrapper, wrappedMintPda, wrappedMintStatePda, userWrapperRolePda, new anchor.BN(0), new anchor.BN(0));
+
+  // circuit breaker, an unwrap above the hourly outflow threshold pauses the mint instead,
+  // the transaction succeeds with the marker in the logs and the return data
+  await setRateLimits(wrappedMintPda, wrappedMintStatePda, 3600, new anchor.BN(0), new anchor.BN(0), wrapAmountBN.divn(2));
+  // an unwrap that could never burn doesn't trip it, neither without the balance nor block listed
+  try {
+    await tokenizationUnwrap(newOwner, unwrappedMint, wrappedMintPda, wrappedMintStatePda, userWrapperRolePda, unwrappedMintAta, wrappedMintAta, unwrappedMintVaultAta);
+    assert.fail("unwrap above the threshold without the balance");
+  } catch (_err) {
+    assert.isTrue(_err instanceof AnchorError);
+    const err: AnchorError = _err;
+    assert.strictEqual(err.error.errorCode.code, "InsufficientBalance");
+  }
+  assert.isFalse((await TokenizationWrapProgram.account.wrappedMintState.fetch(wrappedMintStatePda)).paused);
+  await tokenizationWrap(newOwner, unwrappedMint, wrappedMintPda, wrappedMintStatePda, userWrapperRolePda, unwrappedMintAta, wrappedMintAta, unwrappedMintVaultAta);
+  const breakerManagerRolePda = await getUserRolePda(wrappedMintPda, newOwner.publicKey, Uint8Array.of(RoleKind.AccessListManager));
+  await addRole(newOwner.publicKey, RoleKind.AccessListManager, wrappedMintPda, wrappedMintStatePda, breakerManagerRolePda);
+  await blockListAdd(newOwner, [newOwner.publicKey], wrappedMintPda, wrappedMintStatePda, breakerManagerRolePda);
+  try {
+    await tokenizationUnwrap(newOwner, unwrappedMint, wrappedMintPda, wrappedMintStatePda, userWrapperRolePda, unwrappedMintAta, wrappedMintAta, unwrappedMintVaultAta);
+    assert.fail("unwrap above the threshold block listed");
+  } catch (_err) {
+    assert.isTrue(_err instanceof AnchorError);
+    const err: AnchorError = _err;
+    assert.strictEqual(err.error.errorCode.code, "BlockListed");
+  }
+  assert.isFalse((await TokenizationWrapProgram.account.wrappedMintState.fetch(wrappedMintStatePda)).paused);
+  await blockListRemove(newOwner, [newOwner.publicKey], wrappedMintPda, wrappedMintStatePda, breakerManagerRolePda);
+  await removeRole(newOwner.publicKey, RoleKind.AccessListManager, wrappedMintPda, wrappedMintStatePda, breakerManagerRolePda);
+  const trippedTx = await tokenizationUnwrap(newOwner, unwrappedMint, wrappedMintPda, wrappedMintStatePda, userWrapperRolePda, unwrappedMintAta, wrappedMintAta, unwrappedMintVaultAta);
+  const trippedLogs = (await connection.getTransaction(trippedTx, { commitment: "confirmed", maxSupportedTransactionVersion: 0 })).meta.logMessages;
+  assert.include(String(trippedLogs), "circuit breaker tripped");
+  assert.include(String(trippedLogs), `Program return: ${TokenizationWrapProgram.programId.toBase58()} ${Buffer.from("circuit breaker tripped").toString("base64")}`);
+  assert.isNotNull(await getProgramEvent(trippedTx, "circuitBreakerTrippedEvent"));
+  assert.isTrue((await TokenizationWrapProgram.account.wrappedMintState.fetch(wrappedMintStatePda)).paused);
+  assert.strictEqual((await getAccount(connection, wrappedMintAta, "confirmed", TOKEN_2022_PROGRAM_ID)).amount.toString(), wrapAmount.toString());
+  await unpause(wrappedMintPda, wrappedMintStatePda);
+  await setRateLimits(wrappedMintPda, wrappedMintStatePda, 0, new anchor.BN(0), new anchor.BN(0), new anchor.BN(0));
+  await tokenizationUnwrap(newOwner, unwrappedMint, wrappedMintPda, wrappedMintStatePda, userWrapperRolePda, unwrappedMintAta, wrappedMintAta, unwrappedMintVaultAta);
+
+  // pause
+  const userPauserRolePda = await getUserRolePda(wrappedMintPda, newOwner.publicKey, Uint8Array.of(RoleKind.Pauser));
+  await addRole(newOwner.publicKey, RoleKind.Pauser, wrappedMintPda, wrappedMintStatePda, userPauserRolePda);
+  await pause(newOwner, wrappedMintPda, wrappedMintStatePda, userPauserRolePda);
+  const tokenizationWrapPausedData = await TokenizationWrapProgram.account.wrappedMintState.fetch(wrappedMintStatePda);
+  assert.isTrue(tokenizationWrapPausedData.paused);
+  try {
+    await tokenizationWrap(newOwner, unwrappedMint, wrappedMintPda, wrappedMintStatePda, userWrapperRolePda, unwrappedMintAta, wrappedMintAta, unwrappedMintVaultAta);
+  } catch (_err) {
+    assert.isTrue(_err instanceof AnchorError);
+    const err: AnchorError = _err;
+    assert.strictEqual(err.error.errorCode.code, "Paused");
+  }
+
+  // unpause
+  await unpause(wrappedMintPda, wrappedMintStatePda);
+  await removeRole(newOwner.publicKey, RoleKind.Pauser, wrappedMintPda, wrappedMintStatePda, userPauserRolePda);
+
+  // block list
+  const userManagerRolePda = await getUserRolePda(wrappedMintPda, newOwner.publicKey, Uint8Array.of(RoleKind.AccessListManager));
+  await addRole(newOwner.publicKey, RoleKind.AccessListManager, wrappedMintPda, wrappedMintStatePda, userManagerRolePda);
+  await blockListAdd(newOwner, [newOwner.publicKey], wrappedMintPda, wrappedMintStatePda, userManagerRolePda);
+  try {
+    await tokenizationWrap(newOwner, unwrappedMint, wrappedMintPda, wrappedMintStatePda, userWrapperRolePda, unwrappedMintAta, wrappedMintAta, unwrappedMintVaultAta);
+  } catch (_err) {
+    assert.isTrue(_err instanceof AnchorError);
+    const err: AnchorError = _err;
+    assert.strictEqual(err.error.errorCode.code, "BlockListed");
+  }
+  await blockListRemove(newOwner, [newOwner.publicKey], wrappedMintPda, wrappedMintStatePda, userManagerRolePda);
+  await removeRole(newOwner.publicKey, RoleKind.AccessListManager, wrappedMintPda, wrappedMintStatePda, userManagerRolePda);
+
+  // closeUnwrappedMintVault
+  await closeUnwrappedMintVault(unwrappedMint, wrappedMintPda, wrappedMintStatePda, unwrappedMintVaultAta);
+  await sleep(sleepTime);
+
+  // tokenizationWrap
+  await sleep(sleepTime);
+  await tokenizationWrap(newOwner, unwrappedMint, wrappedMintPda, wrappedMintStatePda, userWrapperRolePda, unwrappedMintAta, wrappedMintAta, unwrappedMintVaultAta);
+  await sleep(sleepTime);
+
+  // tokenizationUnwrap
   await tokenizationUnwrap(newOwner, unwrappedMint, wrappedMintPda, wrappedMintStatePda, userWrapperRolePda, unwrappedMintAta, wrappedMintAta, unwrappedMintVaultAta);
   await sleep(sleepTime);
 
@@ -442,14 +1516,32 @@ async function main() {
   console.log("tokenizationWrapTransferredData:", tokenizationWrapTransferredData);
   assert.strictEqual(tokenizationWrapTransferredData.wrappedMintOwner.toString(), provider.wallet.publicKey.toString());
   assert.strictEqual(tokenizationWrapTransferredData.wrappedMintPendingOwner.toString(), newOwner.publicKey.toString());
+  // the owner's authorities are parked in the wrapped mint authority PDA until accepted
+  const wrappedMintAuthorityPda = await getWrappedMintAuthorityPda(wrappedMintPda);
+  assert.strictEqual((await getMint(connection, wrappedMintPda, "confirmed", TOKEN_2022_PROGRAM_ID)).freezeAuthority.toString(), wrappedMintAuthorityPda.toString());
+  assert.strictEqual((await getExtensionAuthority(wrappedMintPda, EXTENSION_PERMANENT_DELEGATE)).toString(), wrappedMintAuthorityPda.toString());
 
-  // acceptOwnership
+  // acceptOwnership, signed by the new owner only
   await acceptOwnership(newOwner, wrappedMintPda, wrappedMintStatePda);
   await sleep(sleepTime);
   const tokenizationWrapAcceptedData = await TokenizationWrapProgram.account.wrappedMintState.fetch(wrappedMintStatePda);
   console.log("tokenizationWrapAcceptedData:", tokenizationWrapAcceptedData);
   assert.strictEqual(tokenizationWrapAcceptedData.wrappedMintOwner.toString(), newOwner.publicKey.toString());
   assert.strictEqual(tokenizationWrapAcceptedData.wrappedMintPendingOwner.toString(), "11111111111111111111111111111111");
+  const wrappedMintAccepted = await getMint(connection, wrappedMintPda, "confirmed", TOKEN_2022_PROGRAM_ID);
+  assert.strictEqual(wrappedMintAccepted.freezeAuthority.toString(), newOwner.publicKey.toString());
+  for (const extensionType of [
+    EXTENSION_MINT_CLOSE_AUTHORITY,
+    EXTENSION_CONFIDENTIAL_TRANSFER_MINT,
+    EXTENSION_PERMANENT_DELEGATE,
+    EXTENSION_TRANSFER_HOOK,
+    EXTENSION_METADATA_POINTER,
+    EXTENSION_TOKEN_METADATA,
+    EXTENSION_SCALED_UI_AMOUNT,
+    EXTENSION_PAUSABLE,
+  ]) {
+    assert.strictEqual((await getExtensionAuthority(wrappedMintPda, extensionType)).toString(), newOwner.publicKey.toString());
+  }
 
   // mint unwrapped to 
   await mintUnwrappedToAta(newOwner, unwrappedMint, unwrappedMintVaultAta,  wrapAmount);
@@ -460,6 +1552,466 @@ async function main() {
   await sleep(sleepTime);
 }
 
+async function programAuthorityMain() {
+  const { unwrappedMint, unwrappedMintAta } = await createUnwrappedMintAndAta(newOwner, wrapAmount);
+  const wrappedMintPda = await getWrappedMintPda(unwrappedMint, programAuthoritySalt);
+  const wrappedMintStatePda = await getWrappedMintStatePda(wrappedMintPda);
+  const wrappedMintAuthorityPda = await getWrappedMintAuthorityPda(wrappedMintPda);
+  const unwrappedMintVaultAta = await getAtaAddress(unwrappedMint, wrappedMintPda, true);
+
+  // createTokenizationWrapMint with the program authority
+  await createTokenizationWrapMint(programAuthoritySalt, name, symbol, uri, unwrappedMint, wrappedMintPda, wrappedMintStatePda, unwrappedMintVaultAta, true);
+  const wrappedMintData = await getMint(connection, wrappedMintPda, "confirmed", TOKEN_2022_PROGRAM_ID);
+  assert.strictEqual(wrappedMintData.freezeAuthority.toString(), wrappedMintAuthorityPda.toString());
+  assert.strictEqual((await getExtensionAuthority(wrappedMintPda, EXTENSION_PERMANENT_DELEGATE)).toString(), wrappedMintAuthorityPda.toString());
+  // no program instruction repoints the metadata or closes the mint, the owner keeps those
+  assert.strictEqual((await getExtensionAuthority(wrappedMintPda, EXTENSION_METADATA_POINTER)).toString(), provider.wallet.publicKey.toString());
+  assert.strictEqual((await getExtensionAuthority(wrappedMintPda, EXTENSION_MINT_CLOSE_AUTHORITY)).toString(), provider.wallet.publicKey.toString());
+  const programAuthorityState = await TokenizationWrapProgram.account.wrappedMintState.fetch(wrappedMintStatePda);
+  assert.strictEqual(programAuthorityState.version, 1);
+
+  // migrateMintState, a state created in the current layout has nothing to migrate
+  try {
+    await migrateMintState(unwrappedMint, wrappedMintPda, wrappedMintStatePda);
+    assert.fail("migrate a current wrapped mint state");
+  } catch (_err) {
+    assert.isTrue(_err instanceof AnchorError);
+    const err: AnchorError = _err;
+    assert.strictEqual(err.error.errorCode.code, "AlreadyMigrated");
+  }
+
+  // pauseMint / resumeMint
+  const pauserRolePda = await getUserRolePda(wrappedMintPda, newOwner.publicKey, Uint8Array.of(RoleKind.Pauser));
+  await addRole(newOwner.publicKey, RoleKind.Pauser, wrappedMintPda, wrappedMintStatePda, pauserRolePda);
+  await pauseMint(newOwner, wrappedMintPda, wrappedMintStatePda, pauserRolePda);
+  await resumeMint(newOwner, wrappedMintPda, wrappedMintStatePda, pauserRolePda);
+
+  // attachTransferHook
+  await attachTransferHook(wrappedMintPda, wrappedMintStatePda);
+  const wrappedMintHooked = await getMint(connection, wrappedMintPda, "confirmed", TOKEN_2022_PROGRAM_ID);
+  assert.strictEqual(getTransferHook(wrappedMintHooked).programId.toString(), TokenizationHookProgram.programId.toString());
+  assert.strictEqual((await TokenizationWrapProgram.account.wrappedMintState.fetch(wrappedMintStatePda)).transferHookProgram.toString(), TokenizationHookProgram.programId.toString());
+
+  // setTransferHook, detach and reattach at once, then a timelocked detach stays pending
+  await setTransferHook(wrappedMintPda, wrappedMintStatePda, null);
+  assert.isNull((await TokenizationWrapProgram.account.wrappedMintState.fetch(wrappedMintStatePda)).transferHookProgram);
+  await setTransferHook(wrappedMintPda, wrappedMintStatePda, TokenizationHookProgram.programId);
+  try {
+    await setTransferHookTimelock(wrappedMintPda, wrappedMintStatePda, MAX_TRANSFER_HOOK_TIMELOCK + 1);
+    assert.fail("transfer hook timelock beyond the cap");
+  } catch (_err) {
+    assert.isTrue(_err instanceof AnchorError);
+    const err: AnchorError = _err;
+    assert.strictEqual(err.error.errorCode.code, "InvalidTimelock");
+  }
+  const timelockTx = await setTransferHookTimelock(wrappedMintPda, wrappedMintStatePda, 3600);
+  const timelockEvent = await getProgramEvent(timelockTx, "transferHookTimelockUpdatedEvent");
+  assert.strictEqual(timelockEvent.oldTimelock.toNumber(), 0);
+  assert.strictEqual(timelockEvent.newTimelock.toNumber(), 3600);
+  // with a timelock the owner can't take the transfer hook authority back and skip it
+  try {
+    await reclaimMintAuthorities(wrappedMintPda, wrappedMintStatePda);
+    assert.fail("reclaim the mint authorities with a transfer hook timelock");
+  } catch (_err) {
+    assert.isTrue(_err instanceof AnchorError);
+    const err: AnchorError = _err;
+    assert.strictEqual(err.error.errorCode.code, "TransferHookTimelocked");
+  }
+  assert.deepEqual((await TokenizationWrapProgram.account.wrappedMintState.fetch(wrappedMintStatePda)).authorityMode, { program: {} });
+  await setTransferHook(wrappedMintPda, wrappedMintStatePda, null);
+  const timelockedState = await TokenizationWrapProgram.account.wrappedMintState.fetch(wrappedMintStatePda);
+  assert.isNull(timelockedState.pendingTransferHookProgram);
+  assert.isTrue(timelockedState.pendingTransferHookEffectiveTimestamp.toNumber() > 0);
+  assert.strictEqual(timelockedState.transferHookProgram.toString(), TokenizationHookProgram.programId.toString());
+  try {
+    await applyTransferHook(wrappedMintPda, wrappedMintStatePda);
+    assert.fail("transfer hook change applied before the timelock");
+  } catch (_err) {
+    assert.isTrue(_err instanceof AnchorError);
+    const err: AnchorError = _err;
+    assert.strictEqual(err.error.errorCode.code, "TransferHookTimelocked");
+  }
+
+  // metadata manager, the mint account grows and the rent is topped up
+  const metadataManagerRolePda = await getUserRolePda(wrappedMintPda, newOwner.publicKey, Uint8Array.of(RoleKind.MetadataManager));
+  await addRole(newOwner.publicKey, RoleKind.MetadataManager, wrappedMintPda, wrappedMintStatePda, metadataManagerRolePda);
+  await updateMetadataField(newOwner, wrappedMintPda, wrappedMintStatePda, metadataManagerRolePda, "uri", "https://example.com/wrapped-token-v2.json");
+  await updateMetadataField(newOwner, wrappedMintPda, wrappedMintStatePda, metadataManagerRolePda, "isin", "US0000000000");
+  let tokenMetadata = await getTokenMetadata(connection, wrappedMintPda, "confirmed", TOKEN_2022_PROGRAM_ID);
+  assert.strictEqual(tokenMetadata.uri, "https://example.com/wrapped-token-v2.json");
+  assert.deepEqual(tokenMetadata.additionalMetadata, [["isin", "US0000000000"]]);
+  await removeMetadataKey(newOwner, wrappedMintPda, wrappedMintStatePda, metadataManagerRolePda, "isin");
+  tokenMetadata = await getTokenMetadata(connection, wrappedMintPda, "confirmed", TOKEN_2022_PROGRAM_ID);
+  assert.strictEqual(tokenMetadata.additionalMetadata.length, 0);
+
+  // corporate actions, a 2:1 split now, a 2:1 split a few seconds later and a 5:1 split
+  // that stays within the bound only after the second one took effect
+  const corporateActionsRolePda = await getUserRolePda(wrappedMintPda, newOwner.publicKey, Uint8Array.of(RoleKind.CorporateActions));
+  await addRole(newOwner.publicKey, RoleKind.CorporateActions, wrappedMintPda, wrappedMintStatePda, corporateActionsRolePda);
+  let now = Math.floor(Date.now() / 1000);
+  await updateMultiplier(newOwner, wrappedMintPda, wrappedMintStatePda, corporateActionsRolePda, 2.0, now);
+  assert.strictEqual(getScaledUiAmountConfig(await getMint(connection, wrappedMintPda, "confirmed", TOKEN_2022_PROGRAM_ID)).multiplier, 2.0);
+  await updateMultiplier(newOwner, wrappedMintPda, wrappedMintStatePda, corporateActionsRolePda, 4.0, now + 3);
+  let scaledUiAmountConfig = getScaledUiAmountConfig(await getMint(connection, wrappedMintPda, "confirmed", TOKEN_2022_PROGRAM_ID));
+  assert.strictEqual(scaledUiAmountConfig.multiplier, 2.0);
+  assert.strictEqual(scaledUiAmountConfig.newMultiplier, 4.0);
+  await sleep(5000);
+  now = Math.floor(Date.now() / 1000);
+  try {
+    await updateMultiplier(newOwner, wrappedMintPda, wrappedMintStatePda, corporateActionsRolePda, 80.0, now + 3600);
+    assert.fail("multiplier change beyond the bound");
+  } catch (_err) {
+    assert.isTrue(_err instanceof AnchorError);
+    const err: AnchorError = _err;
+    assert.strictEqual(err.error.errorCode.code, "MultiplierOutOfRange");
+  }
+  await updateMultiplier(newOwner, wrappedMintPda, wrappedMintStatePda, corporateActionsRolePda, 20.0, now + 3600);
+  // the 4.0 in effect is locked in, the UI amount doesn't fall back to 2.0 until the 20.0 kicks in
+  scaledUiAmountConfig = getScaledUiAmountConfig(await getMint(connection, wrappedMintPda, "confirmed", TOKEN_2022_PROGRAM_ID));
+  assert.strictEqual(scaledUiAmountConfig.multiplier, 4.0);
+  assert.strictEqual(scaledUiAmountConfig.newMultiplier, 20.0);
+  assert.strictEqual(Number(scaledUiAmountConfig.newMultiplierEffectiveTimestamp), now + 3600);
+  const multiplierBoundTx = await setMultiplierBound(wrappedMintPda, wrappedMintStatePda, 20.0);
+  const multiplierBoundEvent = await getProgramEvent(multiplierBoundTx, "multiplierBoundUpdatedEvent");
+  assert.strictEqual(multiplierBoundEvent.oldMaxChange, 10.0);
+  assert.strictEqual(multiplierBoundEvent.newMaxChange, 20.0);
+  assert.strictEqual((await TokenizationWrapProgram.account.wrappedMintState.fetch(wrappedMintStatePda)).mintConfig.scaledUiMaxChange, 20.0);
+
+  // confidential transfer auditor and auto approve, signed by the wrapped mint authority PDA
+  const auditorElgamalPubkey = [...createHash("sha256").update("auditor").digest()];
+  await setConfidentialAuditor(wrappedMintPda, wrappedMintStatePda, auditorElgamalPubkey);
+  await setConfidentialAutoApprove(wrappedMintPda, wrappedMintStatePda, true);
+  const confidentialState = await TokenizationWrapProgram.account.wrappedMintState.fetch(wrappedMintStatePda);
+  assert.deepEqual(confidentialState.mintConfig.confidentialAuditorElgamalPubkey, auditorElgamalPubkey);
+  assert.isTrue(confidentialState.mintConfig.confidentialAutoApprove);
+  // ConfidentialTransferMint extension: authority 32 bytes, auto approve 1 byte, auditor 32 bytes
+  let confidentialTransferMint = getExtensionBytes((await getMint(connection, wrappedMintPda, "confirmed", TOKEN_2022_PROGRAM_ID)).tlvData, EXTENSION_CONFIDENTIAL_TRANSFER_MINT);
+  assert.strictEqual(confidentialTransferMint[32], 1);
+  assert.deepEqual([...confidentialTransferMint.subarray(33, 65)], auditorElgamalPubkey);
+  // turning auto approve off keeps the auditor on the mint
+  await setConfidentialAutoApprove(wrappedMintPda, wrappedMintStatePda, false);
+  confidentialTransferMint = getExtensionBytes((await getMint(connection, wrappedMintPda, "confirmed", TOKEN_2022_PROGRAM_ID)).tlvData, EXTENSION_CONFIDENTIAL_TRANSFER_MINT);
+  assert.strictEqual(confidentialTransferMint[32], 0);
+  assert.deepEqual([...confidentialTransferMint.subarray(33, 65)], auditorElgamalPubkey);
+
+  // KYC mode, new wrapped token accounts start frozen until approved
+  await setDefaultAccountState(wrappedMintPda, wrappedMintStatePda, true);
+  const holderAta = await getOrCreateAssociatedTokenAccount(connection, newOwner, wrappedMintPda, newOwner.publicKey, false, "confirmed", { commitment: "confirmed" }, TOKEN_2022_PROGRAM_ID, ASSOCIATED_TOKEN_PROGRAM_ID);
+  assert.strictEqual(holderAta.isFrozen, true);
+  const kycApproverRolePda = await getUserRolePda(wrappedMintPda, newOwner.publicKey, Uint8Array.of(RoleKind.KycApprover));
+  await addRole(newOwner.publicKey, RoleKind.KycApprover, wrappedMintPda, wrappedMintStatePda, kycApproverRolePda);
+  await approveHolder(newOwner, wrappedMintPda, wrappedMintStatePda, kycApproverRolePda, holderAta.address);
+  assert.strictEqual((await getAccount(connection, holderAta.address, "confirmed", TOKEN_2022_PROGRAM_ID)).isFrozen, false);
+  await revokeHolder(newOwner, wrappedMintPda, wrappedMintStatePda, kycApproverRolePda, holderAta.address);
+  assert.strictEqual((await getAccount(connection, holderAta.address, "confirmed", TOKEN_2022_PROGRAM_ID)).isFrozen, true);
+
+  // confidential account approval is gated by a valid compliance role
+  const complianceRolePda = await getUserRolePda(wrappedMintPda, newOwner.publicKey, Uint8Array.of(RoleKind.Compliance));
+  await addRole(newOwner.publicKey, RoleKind.Compliance, wrappedMintPda, wrappedMintStatePda, complianceRolePda);
+  const complianceNow = Math.floor(Date.now() / 1000);
+  await setRoleValidity(newOwner.publicKey, RoleKind.Compliance, wrappedMintPda, wrappedMintStatePda, complianceRolePda, complianceNow + 3600, 0);
+  try {
+    await approveConfidentialAccount(newOwner, wrappedMintPda, wrappedMintStatePda, complianceRolePda, holderAta.address);
+    assert.fail("approve a confidential account before the compliance role is valid");
+  } catch (_err) {
+    assert.isTrue(_err instanceof AnchorError);
+    const err: AnchorError = _err;
+    assert.strictEqual(err.error.errorCode.code, "Unauthorized");
+  }
+  // with a valid role the approval reaches Token-2022, which rejects an account that
+  // isn't configured for confidential transfers
+  await setRoleValidity(newOwner.publicKey, RoleKind.Compliance, wrappedMintPda, wrappedMintStatePda, complianceRolePda, 0, 0);
+  try {
+    await approveConfidentialAccount(newOwner, wrappedMintPda, wrappedMintStatePda, complianceRolePda, holderAta.address);
+    assert.fail("approve an account without the confidential transfer extension");
+  } catch (_err) {
+    assert.include(String(_err.logs), `Program ${TOKEN_2022_PROGRAM_ID.toBase58()} invoke [2]`);
+  }
+  await removeRole(newOwner.publicKey, RoleKind.Compliance, wrappedMintPda, wrappedMintStatePda, complianceRolePda);
+
+  // batch thaw / freeze
+  const freezerRolePda = await getUserRolePda(wrappedMintPda, newOwner.publicKey, Uint8Array.of(RoleKind.Freezer));
+  await addRole(newOwner.publicKey, RoleKind.Freezer, wrappedMintPda, wrappedMintStatePda, freezerRolePda);
+  await freezeAccounts(newOwner, wrappedMintPda, wrappedMintStatePda, freezerRolePda, [holderAta.address], false);
+  assert.strictEqual((await getAccount(connection, holderAta.address, "confirmed", TOKEN_2022_PROGRAM_ID)).isFrozen, false);
+  await freezeAccounts(newOwner, wrappedMintPda, wrappedMintStatePda, freezerRolePda, [holderAta.address], true);
+  assert.strictEqual((await getAccount(connection, holderAta.address, "confirmed", TOKEN_2022_PROGRAM_ID)).isFrozen, true);
+
+  // the freezer's hold can't be lifted by a KYC approval, only by the freezer
+  const freezeHold = await TokenizationWrapProgram.account.freezeHoldState.fetch(getFreezeHoldPda(holderAta.address));
+  assert.strictEqual(freezeHold.freezer.toString(), newOwner.publicKey.toString());
+  try {
+    await approveHolder(newOwner, wrappedMintPda, wrappedMintStatePda, kycApproverRolePda, holderAta.address);
+    assert.fail("approve a holder under a freeze hold");
+  } catch (_err) {
+    assert.isTrue(_err instanceof AnchorError);
+    const err: AnchorError = _err;
+    assert.strictEqual(err.error.errorCode.code, "FreezeHold");
+  }
+  assert.strictEqual((await getAccount(connection, holderAta.address, "confirmed", TOKEN_2022_PROGRAM_ID)).isFrozen, true);
+
+  // wrap can't mint into a frozen account, a KYC holder is approved before the first wrap
+  const wrapperRolePda = await getUserRolePda(wrappedMintPda, newOwner.publicKey, Uint8Array.of(RoleKind.Wrapper));
+  await addRole(newOwner.publicKey, RoleKind.Wrapper, wrappedMintPda, wrappedMintStatePda, wrapperRolePda);
+  try {
+    await tokenizationWrap(newOwner, unwrappedMint, wrappedMintPda, wrappedMintStatePda, wrapperRolePda, unwrappedMintAta, holderAta.address, unwrappedMintVaultAta);
+    assert.fail("wrap into a frozen account");
+  } catch (_err) {
+    assert.include(String(_err.logs), "Account is frozen");
+  }
+
+  // forceTransfer, seize the wrapped tokens of a frozen holder
+  await freezeAccounts(newOwner, wrappedMintPda, wrappedMintStatePda, freezerRolePda, [holderAta.address], false);
+  assert.isNull(await connection.getAccountInfo(getFreezeHoldPda(holderAta.address)));
+  await tokenizationWrap(newOwner, unwrappedMint, wrappedMintPda, wrappedMintStatePda, wrapperRolePda, unwrappedMintAta, holderAta.address, unwrappedMintVaultAta);
+  const recipientAta = await getOrCreateAssociatedTokenAccount(connection, newOwner, wrappedMintPda, provider.wallet.publicKey, false, "confirmed", { commitment: "confirmed" }, TOKEN_2022_PROGRAM_ID, ASSOCIATED_TOKEN_PROGRAM_ID);
+  await approveHolder(newOwner, wrappedMintPda, wrappedMintStatePda, kycApproverRolePda, recipientAta.address);
+  const hookAccounts = await getHookAccounts(wrappedMintPda, newOwner.publicKey, provider.wallet.publicKey);
+
+  // the transfer hook rejects the block listed holder's own transfer
+  const accessListManagerRolePda = await getUserRolePda(wrappedMintPda, newOwner.publicKey, Uint8Array.of(RoleKind.AccessListManager));
+  await addRole(newOwner.publicKey, RoleKind.AccessListManager, wrappedMintPda, wrappedMintStatePda, accessListManagerRolePda);
+  await blockListAdd(newOwner, [newOwner.publicKey], wrappedMintPda, wrappedMintStatePda, accessListManagerRolePda);
+  try {
+    await transferWrapped(newOwner, wrappedMintPda, holderAta.address, recipientAta.address, wrapAmountBN, hookAccounts);
+    assert.fail("transfer from a block listed holder");
+  } catch (_err) {
+    assert.include(String(_err.logs), "SourceBlockListed");
+  }
+  assert.strictEqual((await getAccount(connection, holderAta.address, "confirmed", TOKEN_2022_PROGRAM_ID)).amount.toString(), wrapAmount.toString());
+  await freezeAccounts(newOwner, wrappedMintPda, wrappedMintStatePda, freezerRolePda, [holderAta.address], true);
+
+  // the seizure is signed by the wrapped mint authority PDA as permanent delegate and passes
+  // the hook's exemption although the holder is still block listed
+  const recovererRolePda = await getUserRolePda(wrappedMintPda, newOwner.publicKey, Uint8Array.of(RoleKind.Recoverer));
+  await addRole(newOwner.publicKey, RoleKind.Recoverer, wrappedMintPda, wrappedMintStatePda, recovererRolePda);
+  await forceTransfer(newOwner, wrappedMintPda, wrappedMintStatePda, recovererRolePda, holderAta.address, recipientAta.address, wrapAmountBN, 1, hookAccounts);
+  const seizedAccount = await getAccount(connection, holderAta.address, "confirmed", TOKEN_2022_PROGRAM_ID);
+  assert.strictEqual(seizedAccount.amount.toString(), "0");
+  assert.strictEqual(seizedAccount.isFrozen, true);
+  const recipientAccount = await getAccount(connection, recipientAta.address, "confirmed", TOKEN_2022_PROGRAM_ID);
+  assert.strictEqual(recipientAccount.amount.toString(), wrapAmount.toString());
+  await blockListRemove(newOwner, [newOwner.publicKey], wrappedMintPda, wrappedMintStatePda, accessListManagerRolePda);
+
+  // forceUnwrap, redeem the recipient's position to the owner's unwrapped account
+  const ownerUnwrappedAta = await getOrCreateAssociatedTokenAccount(connection, newOwner, unwrappedMint, provider.wallet.publicKey, false, "confirmed", { commitment: "confirmed" }, TOKEN_2022_PROGRAM_ID, ASSOCIATED_TOKEN_PROGRAM_ID);
+  // a block listed destination can't receive the underlying
+  await blockListAdd(newOwner, [provider.wallet.publicKey], wrappedMintPda, wrappedMintStatePda, accessListManagerRolePda);
+  try {
+    await forceUnwrap(unwrappedMint, wrappedMintPda, wrappedMintStatePda, recipientAta.address, ownerUnwrappedAta.address, provider.wallet.publicKey, unwrappedMintVaultAta, wrapAmountBN);
+    assert.fail("force unwrap to a block listed destination");
+  } catch (_err) {
+    assert.isTrue(_err instanceof AnchorError);
+    const err: AnchorError = _err;
+    assert.strictEqual(err.error.errorCode.code, "BlockListed");
+  }
+  await blockListRemove(newOwner, [provider.wallet.publicKey], wrappedMintPda, wrappedMintStatePda, accessListManagerRolePda);
+  await forceUnwrap(unwrappedMint, wrappedMintPda, wrappedMintStatePda, recipientAta.address, ownerUnwrappedAta.address, provider.wallet.publicKey, unwrappedMintVaultAta, wrapAmountBN);
+  const wrappedMintRedeemed = await getMint(connection, wrappedMintPda, "confirmed", TOKEN_2022_PROGRAM_ID);
+  const vaultRedeemed = await getAccount(connection, unwrappedMintVaultAta, "confirmed", TOKEN_2022_PROGRAM_ID);
+  assert.strictEqual(wrappedMintRedeemed.supply.toString(), vaultRedeemed.amount.toString());
+  assert.strictEqual((await getAccount(connection, ownerUnwrappedAta.address, "confirmed", TOKEN_2022_PROGRAM_ID)).amount.toString(), wrapAmount.toString());
+}
+
+async function legacyTokenMain() {
+  // unwrapped mint owned by the legacy SPL Token program, wrapped mint stays Token-2022
+  const { unwrappedMint, unwrappedMintAta } = await createUnwrappedMintAndAta(newOwner, wrapAmount, TOKEN_PROGRAM_ID);
+  const wrappedMintPda = await getWrappedMintPda(unwrappedMint, salt);
+  const wrappedMintStatePda = await getWrappedMintStatePda(wrappedMintPda);
+  const wrappedMintAta = await getAtaAddress(wrappedMintPda, newOwner.publicKey, false);
+  const unwrappedMintVaultAta = await getAtaAddress(unwrappedMint, wrappedMintPda, true, TOKEN_PROGRAM_ID);
+
+  // create_mint carries the additional metadata in its single transaction, check both limits
+  const limitValueLen = MAX_ADDITIONAL_METADATA_SIZE / MAX_ADDITIONAL_METADATA - MAX_METADATA_KEY_LEN;
+  const limitMetadata = [...Array(MAX_ADDITIONAL_METADATA).keys()].map((i) => ({ key: `key_${i}_`.padEnd(MAX_METADATA_KEY_LEN, "k"), value: "v".repeat(limitValueLen) }));
+  try {
+    const tooManyMetadata = [...limitMetadata.map((entry) => ({ key: entry.key, value: "v" })), { key: "extra", value: "v" }];
+    await createTokenizationWrapMint(salt, name, symbol, uri, unwrappedMint, wrappedMintPda, wrappedMintStatePda, unwrappedMintVaultAta, false, TOKEN_PROGRAM_ID, { ...defaultMintConfig, transferHook: false }, tooManyMetadata);
+    assert.fail("create a mint with too many metadata fields");
+  } catch (_err) {
+    assert.isTrue(_err instanceof AnchorError);
+    const err: AnchorError = _err;
+    assert.strictEqual(err.error.errorCode.code, "TooManyMetadataFields");
+  }
+  try {
+    const tooLargeMetadata = limitMetadata.map((entry, i) => (i == 0 ? { key: entry.key, value: entry.value + "v" } : entry));
+    await createTokenizationWrapMint(salt, name, symbol, uri, unwrappedMint, wrappedMintPda, wrappedMintStatePda, unwrappedMintVaultAta, false, TOKEN_PROGRAM_ID, { ...defaultMintConfig, transferHook: false }, tooLargeMetadata);
+    assert.fail("create a mint with too large metadata");
+  } catch (_err) {
+    assert.isTrue(_err instanceof AnchorError);
+    const err: AnchorError = _err;
+    assert.strictEqual(err.error.errorCode.code, "AdditionalMetadataTooLarge");
+  }
+
+  // MAX_ADDITIONAL_METADATA fields filling MAX_ADDITIONAL_METADATA_SIZE still fit the transaction
+  await createTokenizationWrapMint(salt, name, symbol, uri, unwrappedMint, wrappedMintPda, wrappedMintStatePda, unwrappedMintVaultAta, false, TOKEN_PROGRAM_ID, { ...defaultMintConfig, transferHook: false }, limitMetadata);
+  const limitTokenMetadata = await getTokenMetadata(connection, wrappedMintPda, "confirmed", TOKEN_2022_PROGRAM_ID);
+  assert.deepEqual(limitTokenMetadata.additionalMetadata, limitMetadata.map((entry) => [entry.key, entry.value]));
+
+  // the transfer hook extension can't be added after create_mint
+  try {
+    await attachTransferHook(wrappedMintPda, wrappedMintStatePda);
+    assert.fail("attach a transfer hook without the extension");
+  } catch (_err) {
+    assert.isTrue(_err instanceof AnchorError);
+    const err: AnchorError = _err;
+    assert.strictEqual(err.error.errorCode.code, "InvalidMintConfig");
+  }
+  const userWrapperRolePda = await getUserRolePda(wrappedMintPda, newOwner.publicKey, Uint8Array.of(RoleKind.Wrapper));
+  await addRole(newOwner.publicKey, RoleKind.Wrapper, wrappedMintPda, wrappedMintStatePda, userWrapperRolePda);
+
+  await tokenizationWrap(newOwner, unwrappedMint, wrappedMintPda, wrappedMintStatePda, userWrapperRolePda, unwrappedMintAta, wrappedMintAta, unwrappedMintVaultAta, TOKEN_PROGRAM_ID);
+  const vaultAccount = await getAccount(connection, unwrappedMintVaultAta, "confirmed", TOKEN_PROGRAM_ID);
+  assert.strictEqual(vaultAccount.amount.toString(), wrapAmount.toString());
+
+  await tokenizationUnwrap(newOwner, unwrappedMint, wrappedMintPda, wrappedMintStatePda, userWrapperRolePda, unwrappedMintAta, wrappedMintAta, unwrappedMintVaultAta, TOKEN_PROGRAM_ID);
+  const vaultAccountAfter = await getAccount(connection, unwrappedMintVaultAta, "confirmed", TOKEN_PROGRAM_ID);
+  assert.strictEqual(vaultAccountAfter.amount.toString(), "0");
+}
+
+async function transferFeeMain() {
+  // unwrapped mint charging 1% on every transfer, the wrapped supply only follows what the vault holds
+  const unwrappedMint = await createTransferFeeUnwrappedMint(newOwner, 100);
+  await sleep(sleepTime);
+  const unwrappedMintAta = (await getUnwrappedMintAta(newOwner, unwrappedMint)).address;
+  await mintUnwrappedToAta(newOwner, unwrappedMint, unwrappedMintAta, wrapAmount * 5);
+  const wrappedMintPda = await getWrappedMintPda(unwrappedMint, salt);
+  const wrappedMintStatePda = await getWrappedMintStatePda(wrappedMintPda);
+  const wrappedMintAta = await getAtaAddress(wrappedMintPda, newOwner.publicKey, false);
+  const unwrappedMintVaultAta = await getAtaAddress(unwrappedMint, wrappedMintPda, true);
+
+  // the program config only allows underlyings without risk flags so far
+  try {
+    await createTokenizationWrapMint(salt, name, symbol, uri, unwrappedMint, wrappedMintPda, wrappedMintStatePda, unwrappedMintVaultAta);
+    assert.fail("wrap an underlying with a transfer fee");
+  } catch (_err) {
+    assert.isTrue(_err instanceof AnchorError);
+    const err: AnchorError = _err;
+    assert.strictEqual(err.error.errorCode.code, "UnderlyingRiskNotAllowed");
+  }
+  await setUnderlyingRiskPolicy(UNDERLYING_RISK_TRANSFER_FEE);
+  await createTokenizationWrapMint(salt, name, symbol, uri, unwrappedMint, wrappedMintPda, wrappedMintStatePda, unwrappedMintVaultAta);
+  await setUnderlyingRiskPolicy(0);
+  assert.strictEqual((await TokenizationWrapProgram.account.wrappedMintState.fetch(wrappedMintStatePda)).underlyingRiskFlags, UNDERLYING_RISK_TRANSFER_FEE);
+  const userWrapperRolePda = await getUserRolePda(wrappedMintPda, newOwner.publicKey, Uint8Array.of(RoleKind.Wrapper));
+  await addRole(newOwner.publicKey, RoleKind.Wrapper, wrappedMintPda, wrappedMintStatePda, userWrapperRolePda);
+
+  // wrap, the inbound fee is withheld on the vault and not minted
+  const wrapFee = wrapAmount / 100;
+  const wrapTx = await tokenizationWrap(newOwner, unwrappedMint, wrappedMintPda, wrappedMintStatePda, userWrapperRolePda, unwrappedMintAta, wrappedMintAta, unwrappedMintVaultAta);
+  const wrapEvent = await getProgramEvent(wrapTx, "wrapEvent");
+  assert.strictEqual(wrapEvent.amount.toString(), wrapAmount.toString());
+  assert.strictEqual(wrapEvent.fee.toString(), wrapFee.toString());
+  assert.strictEqual(wrapEvent.netAmount.toString(), (wrapAmount - wrapFee).toString());
+  let wrappedMintData = await getMint(connection, wrappedMintPda, "confirmed", TOKEN_2022_PROGRAM_ID);
+  let vaultAccount = await getAccount(connection, unwrappedMintVaultAta, "confirmed", TOKEN_2022_PROGRAM_ID);
+  assert.strictEqual(vaultAccount.amount.toString(), (wrapAmount - wrapFee).toString());
+  assert.strictEqual(wrappedMintData.supply.toString(), vaultAccount.amount.toString());
+
+  // unwrap everything, the vault is debited the full amount and the user bears the outbound fee
+  const unwrapAmount = wrapAmount - wrapFee;
+  const unwrapFee = unwrapAmount / 100;
+  const unwrapTx = await tokenizationUnwrap(newOwner, unwrappedMint, wrappedMintPda, wrappedMintStatePda, userWrapperRolePda, unwrappedMintAta, wrappedMintAta, unwrappedMintVaultAta, TOKEN_2022_PROGRAM_ID, [], new anchor.BN(unwrapAmount.toString()));
+  const unwrapEvent = await getProgramEvent(unwrapTx, "unwrapEvent");
+  assert.strictEqual(unwrapEvent.amount.toString(), unwrapAmount.toString());
+  assert.strictEqual(unwrapEvent.fee.toString(), unwrapFee.toString());
+  assert.strictEqual(unwrapEvent.netAmount.toString(), (unwrapAmount - unwrapFee).toString());
+  wrappedMintData = await getMint(connection, wrappedMintPda, "confirmed", TOKEN_2022_PROGRAM_ID);
+  vaultAccount = await getAccount(connection, unwrappedMintVaultAta, "confirmed", TOKEN_2022_PROGRAM_ID);
+  assert.strictEqual(vaultAccount.amount.toString(), "0");
+  assert.strictEqual(wrappedMintData.supply.toString(), vaultAccount.amount.toString());
+}
+
+async function legacyRoleMain() {
+  // the Wrapper and Pauser role accounts of the holder were preloaded in the 73 byte layout
+  // that only holds the wrapped mint, user and role
+  const holder = loadFixtureKeypair("legacy-role-holder.json");
+  const airdropTx = await connection.requestAirdrop(holder.publicKey, anchor.web3.LAMPORTS_PER_SOL);
+  await connection.confirmTransaction(airdropTx, "confirmed");
+  const unwrappedMint = await createMint(connection, holder, holder.publicKey, null, decimals, loadFixtureKeypair("legacy-role-unwrapped-mint.json"), { commitment: "confirmed" }, TOKEN_2022_PROGRAM_ID);
+  const unwrappedMintAta = (await getUnwrappedMintAta(holder, unwrappedMint)).address;
+  await mintUnwrappedToAta(holder, unwrappedMint, unwrappedMintAta, wrapAmount);
+  const wrappedMintPda = await getWrappedMintPda(unwrappedMint, legacyRoleSalt);
+  const wrappedMintStatePda = await getWrappedMintStatePda(wrappedMintPda);
+  const wrappedMintAta = await getAtaAddress(wrappedMintPda, holder.publicKey, false);
+  const unwrappedMintVaultAta = await getAtaAddress(unwrappedMint, wrappedMintPda, true);
+  const wrapperRolePda = await getUserRolePda(wrappedMintPda, holder.publicKey, Uint8Array.of(RoleKind.Wrapper));
+  const pauserRolePda = await getUserRolePda(wrappedMintPda, holder.publicKey, Uint8Array.of(RoleKind.Pauser));
+  assert.strictEqual((await connection.getAccountInfo(wrapperRolePda)).data.length, 73);
+  assert.strictEqual((await connection.getAccountInfo(pauserRolePda)).data.length, 73);
+
+  await createTokenizationWrapMint(legacyRoleSalt, name, symbol, uri, unwrappedMint, wrappedMintPda, wrappedMintStatePda, unwrappedMintVaultAta);
+
+  // the old layout doesn't load until migrated
+  try {
+    await tokenizationWrap(holder, unwrappedMint, wrappedMintPda, wrappedMintStatePda, wrapperRolePda, unwrappedMintAta, wrappedMintAta, unwrappedMintVaultAta);
+    assert.fail("wrap with a role account in the old layout");
+  } catch (_err) {
+    assert.isTrue(_err instanceof AnchorError);
+    const err: AnchorError = _err;
+    assert.strictEqual(err.error.errorCode.code, "AccountDidNotDeserialize");
+  }
+  await migrateRole(wrapperRolePda);
+  const migratedRole = await TokenizationWrapProgram.account.roleState.fetch(wrapperRolePda);
+  assert.strictEqual(migratedRole.user.toString(), holder.publicKey.toString());
+  assert.strictEqual(migratedRole.allowance.toString(), "18446744073709551615");
+  assert.strictEqual(migratedRole.outstanding.toString(), "0");
+  assert.strictEqual(migratedRole.validUntil.toNumber(), 0);
+  await tokenizationWrap(holder, unwrappedMint, wrappedMintPda, wrappedMintStatePda, wrapperRolePda, unwrappedMintAta, wrappedMintAta, unwrappedMintVaultAta);
+  assert.strictEqual((await getAccount(connection, wrappedMintAta, "confirmed", TOKEN_2022_PROGRAM_ID)).amount.toString(), wrapAmount.toString());
+  try {
+    await migrateRole(wrapperRolePda);
+    assert.fail("migrate a role twice");
+  } catch (_err) {
+    assert.isTrue(_err instanceof AnchorError);
+    const err: AnchorError = _err;
+    assert.strictEqual(err.error.errorCode.code, "AlreadyMigrated");
+  }
+
+  // a pauser migrates its role in the same transaction as the pause
+  const migratePauserRoleIx = await TokenizationWrapProgram.methods.migrateRole().accounts(
+    {
+      payer: holder.publicKey,
+      userRole: pauserRolePda,
+      systemProgram: anchor.web3.SystemProgram.programId,
+    }
+  ).instruction();
+  await TokenizationWrapProgram.methods.pause().accounts(
+    {
+      pauser: holder.publicKey,
+      wrappedMint: wrappedMintPda,
+      wrappedMintState: wrappedMintStatePda,
+      pauserRole: pauserRolePda,
+    }
+  ).preInstructions([migratePauserRoleIx]).signers([holder]).rpc();
+  await sleep(sleepTime);
+  assert.isTrue((await TokenizationWrapProgram.account.wrappedMintState.fetch(wrappedMintStatePda)).paused);
+  await unpause(wrappedMintPda, wrappedMintStatePda);
+}
+
+it("initialize program config", async () => {
+  await initializeProgramConfig(0);
+  const programConfig = await TokenizationWrapProgram.account.programConfig.fetch(getProgramConfigPda());
+  assert.strictEqual(programConfig.authority.toString(), provider.wallet.publicKey.toString());
+  assert.strictEqual(programConfig.allowedUnderlyingRisks, 0);
+});
+
 it("test tokenization wrap", async () => {
   await main();
 });
+
+it("test tokenization wrap with program authority", async () => {
+  await programAuthorityMain();
+});
+
+it("test tokenization wrap with legacy SPL Token underlying", async () => {
+  await legacyTokenMain();
+});
+
+it("test tokenization wrap with TransferFee underlying", async () => {
+  await transferFeeMain();
+});
+
+it("test tokenization wrap with role accounts in the old layout", async () => {
+  await legacyRoleMain();
+});